@@ -1,11 +1,12 @@
 use crate::{First, Main, MainSchedulePlugin, Plugin, Plugins, StateTransition};
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
+    event::EventRetention,
     prelude::*,
     schedule::{
         apply_state_transition, common_conditions::run_once as run_once_condition,
-        run_enter_schedule, BoxedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs,
-        ScheduleLabel,
+        run_enter_schedule, setup_computed_state, setup_sub_state, ApplyStateTransition,
+        BoxedScheduleLabel, IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel,
     },
 };
 use bevy_utils::{tracing::debug, HashMap, HashSet};
@@ -347,7 +348,8 @@ impl App {
                     run_enter_schedule::<S>.run_if(run_once_condition()),
                     apply_state_transition::<S>,
                 )
-                    .chain(),
+                    .chain()
+                    .in_set(ApplyStateTransition::<S>::default()),
             );
 
         // The OnEnter, OnExit, and OnTransition schedules are lazily initialized
@@ -357,6 +359,41 @@ impl App {
         self
     }
 
+    /// Adds the [computed state](ComputedStates) `S`, whose [`State<S>`] resource is updated
+    /// whenever one of its source states changes.
+    ///
+    /// The source states must be added with [`App::add_state`], [`App::add_sub_state`] or
+    /// [`App::add_computed_state`] first. The [`OnExit`] schedules of `S` run before those of its
+    /// source states, and its [`OnTransition`] and [`OnEnter`] schedules after those of its source
+    /// states.
+    pub fn add_computed_state<S: ComputedStates>(&mut self) -> &mut Self {
+        setup_computed_state::<S>(&mut self.world);
+        self.configure_set(
+            StateTransition,
+            ApplyStateTransition::<S>::after_states::<S::SourceStates>(),
+        )
+    }
+
+    /// Adds the [sub-state](SubStates) `S`, whose [`State<S>`] resource only exists while its
+    /// source states have the values it requires, along with its [`NextState<S>`] resource and
+    /// an instance of [`apply_state_transition::<S>`] in [`StateTransition`].
+    ///
+    /// The source states must be added with [`App::add_state`], [`App::add_sub_state`] or
+    /// [`App::add_computed_state`] first. The [`OnExit`] schedules of `S` run before those of its
+    /// source states, and its [`OnTransition`] and [`OnEnter`] schedules after those of its source
+    /// states. Transitions queued in [`NextState<S>`] are applied after those of the source states.
+    pub fn add_sub_state<S: SubStates>(&mut self) -> &mut Self {
+        setup_sub_state::<S>(&mut self.world);
+        self.configure_set(
+            StateTransition,
+            ApplyStateTransition::<S>::after_states::<S::SourceStates>(),
+        )
+        .add_systems(
+            StateTransition,
+            apply_state_transition::<S>.in_set(ApplyStateTransition::<S>::default()),
+        )
+    }
+
     /// Adds a system to the given schedule in this app's [`Schedules`].
     ///
     /// # Examples
@@ -456,6 +493,35 @@ impl App {
         self
     }
 
+    /// Like [`add_event`](Self::add_event), but the [`Events<T>`] resource drops old events
+    /// according to the given [`EventRetention`] policy instead of after two updates.
+    ///
+    /// If the event was already added, only its retention policy is changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::{event::EventRetention, prelude::*};
+    /// #
+    /// # #[derive(Event)]
+    /// # struct Collision;
+    /// #
+    /// // Keep collisions until every reader, such as a fixed-timestep system, has seen them.
+    /// App::new()
+    ///     .add_event_with_retention::<Collision>(EventRetention::UntilConsumed);
+    /// ```
+    pub fn add_event_with_retention<T>(&mut self, retention: EventRetention) -> &mut Self
+    where
+        T: Event,
+    {
+        self.add_event::<T>();
+        self.world
+            .resource_mut::<Events<T>>()
+            .set_retention(retention);
+        self
+    }
+
     /// Inserts a [`Resource`] to the current [`App`] and overwrites any [`Resource`] previously added of the same type.
     ///
     /// A [`Resource`] in Bevy represents globally unique data. [`Resource`]s must be added to Bevy apps
@@ -877,7 +943,7 @@ pub struct AppExit;
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
-        schedule::{OnEnter, States},
+        schedule::{NextState, OnEnter, State, States, SubStates},
         system::Commands,
     };
 
@@ -944,6 +1010,7 @@ mod tests {
     enum AppState {
         #[default]
         MainMenu,
+        InGame,
     }
     fn bar(mut commands: Commands) {
         commands.spawn_empty();
@@ -972,4 +1039,57 @@ mod tests {
         app.world.run_schedule(OnEnter(AppState::MainMenu));
         assert_eq!(app.world.entities().len(), 2);
     }
+
+    #[derive(States, PartialEq, Eq, Debug, Default, Hash, Clone)]
+    enum Menu {
+        #[default]
+        Main,
+        Options,
+    }
+
+    impl SubStates for Menu {
+        type SourceStates = AppState;
+
+        fn should_exist(state: AppState) -> bool {
+            state == AppState::MainMenu
+        }
+    }
+
+    #[test]
+    fn sub_state_follows_parent_state() {
+        let mut app = App::new();
+        app.add_state::<AppState>()
+            .add_sub_state::<Menu>()
+            .add_systems(OnEnter(Menu::Main), foo);
+
+        app.update();
+        assert_eq!(*app.world.resource::<State<Menu>>(), Menu::Main);
+        assert_eq!(app.world.entities().len(), 1);
+
+        app.world
+            .resource_mut::<NextState<Menu>>()
+            .set(Menu::Options);
+        app.update();
+        assert_eq!(*app.world.resource::<State<Menu>>(), Menu::Options);
+    }
+
+    #[test]
+    fn sub_state_transition_is_applied_after_parent_transition() {
+        let mut app = App::new();
+        app.insert_resource(State::new(AppState::InGame))
+            .add_state::<AppState>()
+            .add_sub_state::<Menu>();
+        app.update();
+        assert!(!app.world.contains_resource::<State<Menu>>());
+
+        // `Menu` only exists once the `AppState` transition is applied.
+        app.world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::MainMenu);
+        app.world
+            .resource_mut::<NextState<Menu>>()
+            .set(Menu::Options);
+        app.update();
+        assert_eq!(*app.world.resource::<State<Menu>>(), Menu::Options);
+    }
 }
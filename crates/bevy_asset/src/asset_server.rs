@@ -1,17 +1,25 @@
 use crate::{
+    meta::SettingsOverride,
+    meta_path,
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetProcessor, AssetReader, AssetSources, Assets, Handle, HandleId,
+    HandleUntyped, ImportedAssets, LabelId, LoadContext, LoadState, LoaderMeta, ProcessError,
+    ProcessStatus, ProcessedAsset, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{get_short_name, Entry, HashMap, Uuid};
 use crossbeam_channel::TryRecvError;
+use futures_lite::io::Cursor;
 use parking_lot::{Mutex, RwLock};
-use std::{path::Path, sync::Arc};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an [`AssetServer`].
@@ -41,6 +49,23 @@ pub enum AssetServerError {
     /// Encountered an error while reading an asset from disk.
     #[error("encountered an error while reading an asset: {0}")]
     AssetIoError(#[from] AssetIoError),
+
+    /// Encountered an error while processing an asset.
+    #[error("encountered an error while processing an asset: {0}")]
+    ProcessError(#[from] ProcessError),
+
+    /// No asset loader was found with the name given in a meta file.
+    #[error("no `AssetLoader` named `{0}`")]
+    MissingNamedAssetLoader(String),
+
+    /// The meta file of an asset couldn't be deserialized.
+    #[error("invalid meta file `{path}`: {error}")]
+    InvalidMeta {
+        /// The path of the meta file.
+        path: PathBuf,
+        /// The deserialization error.
+        error: ron::error::SpannedError,
+    },
 }
 
 fn format_missing_asset_ext(exts: &[String]) -> String {
@@ -75,12 +100,14 @@ enum MaybeAssetLoader {
 ///
 /// [`AssetServer`] is the public API for interacting with the asset server.
 pub struct AssetServerInternal {
-    pub(crate) asset_io: Box<dyn AssetIo>,
+    pub(crate) asset_io: AssetSources,
     pub(crate) asset_ref_counter: AssetRefCounter,
     pub(crate) asset_sources: Arc<RwLock<HashMap<SourcePathId, SourceInfo>>>,
     pub(crate) asset_lifecycles: Arc<RwLock<HashMap<Uuid, Box<dyn AssetLifecycle>>>>,
     loaders: RwLock<Vec<MaybeAssetLoader>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
+    extension_to_processor: RwLock<HashMap<String, Arc<dyn AssetProcessor>>>,
+    imported_assets: RwLock<Option<ImportedAssets>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
 }
 
@@ -138,18 +165,45 @@ impl AssetServer {
             server: Arc::new(AssetServerInternal {
                 loaders: Default::default(),
                 extension_to_loader_index: Default::default(),
+                extension_to_processor: Default::default(),
+                imported_assets: Default::default(),
                 asset_sources: Default::default(),
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
-                asset_io,
+                asset_io: AssetSources::new(asset_io),
             }),
         }
     }
 
-    /// Returns the associated asset I/O.
+    /// Returns the default asset I/O, which loads the paths without a source.
     pub fn asset_io(&self) -> &dyn AssetIo {
-        &*self.server.asset_io
+        self.server.asset_io.default_source()
+    }
+
+    /// Returns the asset I/O sources, which load the paths with and without a source.
+    ///
+    /// See [`AssetSources`] for how paths are resolved.
+    pub fn asset_sources(&self) -> &AssetSources {
+        &self.server.asset_io
+    }
+
+    /// Adds an asset I/O source named `name`, from which the paths prefixed with `name://` are
+    /// loaded, such as `dlc://levels/castle.scn.ron`.
+    ///
+    /// See [`AssetSources::add_source`].
+    pub fn add_source<T: AssetIo>(&self, name: impl Into<String>, asset_io: T) {
+        self.server.asset_io.add_source(name, Box::new(asset_io));
+    }
+
+    /// Adds an asset I/O source named `name`, which also overrides the assets of the default
+    /// source and of the previously added overriding sources, for instance for mods.
+    ///
+    /// See [`AssetSources::add_overriding_source`].
+    pub fn add_overriding_source<T: AssetIo>(&self, name: impl Into<String>, asset_io: T) {
+        self.server
+            .asset_io
+            .add_overriding_source(name, Box::new(asset_io));
     }
 
     pub(crate) fn register_asset_type<T: Asset>(&self) -> Assets<T> {
@@ -243,6 +297,36 @@ impl AssetServer {
         }
     }
 
+    /// Adds the provided asset processor to the server.
+    ///
+    /// Processors are only used once [`ImportedAssets`] are set with
+    /// [`set_imported_assets`](Self::set_imported_assets).
+    /// If `processor` has one or more supported extensions in conflict with processors that came
+    /// before it, it will replace them.
+    pub fn add_processor<T>(&self, processor: T)
+    where
+        T: AssetProcessor,
+    {
+        let processor: Arc<dyn AssetProcessor> = Arc::new(processor);
+        let mut processors = self.server.extension_to_processor.write();
+        for extension in processor.extensions() {
+            processors.insert(extension.to_string(), processor.clone());
+        }
+    }
+
+    /// Sets the folder where processed assets are cached, enabling asset processing.
+    ///
+    /// Once set, sources with an [`AssetProcessor`] are loaded from their processed artifact,
+    /// which is only processed again when it's out of date.
+    pub fn set_imported_assets(&self, imported_assets: ImportedAssets) {
+        *self.server.imported_assets.write() = Some(imported_assets);
+    }
+
+    /// Returns the folder where processed assets are cached, if asset processing is enabled.
+    pub fn imported_assets(&self) -> Option<ImportedAssets> {
+        self.server.imported_assets.read().clone()
+    }
+
     /// Gets a strong handle for an asset with the provided id.
     pub fn get_handle<T: Asset, I: Into<HandleId>>(&self, id: I) -> Handle<T> {
         let sender = self.server.asset_ref_counter.channel.sender.clone();
@@ -301,6 +385,128 @@ impl AssetServer {
         })
     }
 
+    /// Returns the loader named `name`, selected by the meta file of the asset at `path`.
+    ///
+    /// Pre-registered loaders don't have a name until they're added, so if the loader of the
+    /// extension of `path` is still pending, it's returned when `include_pending` is set, to wait
+    /// for it before looking the name up again.
+    fn get_named_asset_loader(
+        &self,
+        name: &str,
+        path: &Path,
+        include_pending: bool,
+    ) -> Result<MaybeAssetLoader, AssetServerError> {
+        let loader = self
+            .server
+            .loaders
+            .read()
+            .iter()
+            .find(|loader| match loader {
+                MaybeAssetLoader::Ready(loader) => {
+                    loader.name() == name || get_short_name(loader.name()) == name
+                }
+                MaybeAssetLoader::Pending { .. } => false,
+            })
+            .cloned();
+        if let Some(loader) = loader {
+            return Ok(loader);
+        }
+        match self.get_path_asset_loader(path, true) {
+            Ok(pending @ MaybeAssetLoader::Pending { .. }) if include_pending => Ok(pending),
+            _ => Err(AssetServerError::MissingNamedAssetLoader(name.to_string())),
+        }
+    }
+
+    /// Reads the meta file of the asset at `path`, returning its bytes and the loader it selects.
+    async fn read_meta(
+        &self,
+        path: &Path,
+    ) -> Result<Option<(Vec<u8>, Option<String>)>, AssetServerError> {
+        let meta_path = meta_path(path);
+        let bytes = match self.asset_sources().load_path(&meta_path).await {
+            Ok(bytes) => bytes,
+            Err(AssetIoError::NotFound(_)) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let meta = ron::de::from_bytes::<LoaderMeta<IgnoredAny>>(&bytes).map_err(|error| {
+            AssetServerError::InvalidMeta {
+                path: meta_path.clone(),
+                error,
+            }
+        })?;
+        self.asset_sources()
+            .watch_path_for_changes(&meta_path, Some(path.to_owned()))?;
+        Ok(Some((bytes, meta.loader)))
+    }
+
+    fn get_path_asset_processor<P: AsRef<Path>>(&self, path: P) -> Option<Arc<dyn AssetProcessor>> {
+        let file_name = path.as_ref().file_name()?.to_str()?.to_lowercase();
+        let processors = self.server.extension_to_processor.read();
+        let mut ext = file_name.as_str();
+        while let Some(idx) = ext.find('.') {
+            ext = &ext[idx + 1..];
+            if let Some(processor) = processors.get(ext) {
+                return Some(processor.clone());
+            }
+        }
+        None
+    }
+
+    /// Processes the source at `path` if asset processing is enabled and a processor supports it.
+    ///
+    /// Returns the processed artifact, which may have been cached by a previous run.
+    async fn process_async(&self, path: &Path) -> Result<Option<ProcessedAsset>, ProcessError> {
+        let Some(imported_assets) = self.imported_assets() else {
+            return Ok(None);
+        };
+        let Some(processor) = self.get_path_asset_processor(path) else {
+            return Ok(None);
+        };
+        imported_assets
+            .process(path, &*processor, self.asset_sources())
+            .await
+            .map(Some)
+    }
+
+    /// Processes the sources in the specified folder recursively, without loading them.
+    ///
+    /// Only sources which are new or out of date are processed, and their paths are returned.
+    /// Other sources are processed when they are loaded, so this is mostly useful to process all
+    /// assets ahead of time, for instance before shipping the imported folder.
+    ///
+    /// # Errors
+    ///
+    /// - If the provided path is not a directory, it will fail with
+    /// [`AssetServerError::AssetFolderNotADirectory`].
+    /// - If a source fails to be processed, it will fail with [`AssetServerError::ProcessError`].
+    pub async fn process_folder<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<PathBuf>, AssetServerError> {
+        let path = path.as_ref();
+        if !self.asset_sources().is_dir(path) {
+            return Err(AssetServerError::AssetFolderNotADirectory(
+                path.to_str().unwrap().to_string(),
+            ));
+        }
+
+        let mut processed = Vec::new();
+        let mut folders = vec![path.to_owned()];
+        while let Some(folder) = folders.pop() {
+            for child_path in self.asset_sources().read_directory(&folder)? {
+                if self.asset_sources().is_dir(&child_path) {
+                    folders.push(child_path);
+                } else if let Some(asset) = self.process_async(&child_path).await? {
+                    if asset.status == ProcessStatus::Processed {
+                        processed.push(child_path);
+                    }
+                }
+            }
+        }
+
+        Ok(processed)
+    }
+
     /// Gets the source path of an asset from the provided handle.
     pub fn get_handle_path<H: Into<HandleId>>(&self, handle: H) -> Option<AssetPath<'_>> {
         self.server
@@ -364,6 +570,10 @@ impl AssetServer {
     /// [`AssetPlugin`](crate::AssetPlugin). The default name is
     /// `"assets"`.
     ///
+    /// The path can start with the name of an asset source added with
+    /// [`add_source`](Self::add_source), such as `mods://weapons/sword.gltf`, to load the asset
+    /// from this source. See [`AssetSources`] for how the other paths are resolved.
+    ///
     /// The asset is loaded asynchronously, and will generally not be available by the time
     /// this calls returns. Use [`AssetServer::get_load_state`] to determine when the asset is
     /// effectively loaded and available in the [`Assets`] collection. The asset will always fail to
@@ -375,10 +585,54 @@ impl AssetServer {
         self.load_untyped(path).typed()
     }
 
+    /// Queues an [`Asset`] at the provided path for asynchronous loading, with loader settings.
+    ///
+    /// The settings of type `S` are read by the loader with [`LoadContext::settings`], and
+    /// `settings` is applied to them after the settings of the [meta file](LoaderMeta) of the
+    /// asset. Loading fails if the loader reads settings of another type.
+    ///
+    /// The settings are only used by the load this call starts, later reloads, for instance
+    /// when it's hot reloaded, use the settings of its meta file. An asset which is already
+    /// loaded or loading isn't reloaded, so the settings are dropped without being used.
+    ///
+    /// See [`load`](AssetServer::load).
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn load_with_settings<'a, T, S, P>(
+        &self,
+        path: P,
+        settings: impl Fn(&mut S) + Send + Sync + 'static,
+    ) -> Handle<T>
+    where
+        T: Asset,
+        S: DeserializeOwned + Default + 'static,
+        P: Into<AssetPath<'a>>,
+    {
+        let path = path.into();
+        let settings_override: SettingsOverride =
+            Arc::new(move |any_settings| match any_settings.downcast_mut::<S>() {
+                Some(any_settings) => {
+                    settings(any_settings);
+                    true
+                }
+                None => false,
+            });
+        let handle_id = self.load_untracked_with_settings(path, false, Some(settings_override));
+        self.get_handle_untyped(handle_id).typed()
+    }
+
     async fn load_async(
         &self,
         asset_path: AssetPath<'_>,
         force: bool,
+    ) -> Result<AssetPathId, AssetServerError> {
+        self.load_async_with_settings(asset_path, force, None).await
+    }
+
+    async fn load_async_with_settings(
+        &self,
+        asset_path: AssetPath<'_>,
+        force: bool,
+        settings_override: Option<SettingsOverride>,
     ) -> Result<AssetPathId, AssetServerError> {
         let asset_path_id: AssetPathId = asset_path.get_id();
 
@@ -414,7 +668,6 @@ impl AssetServer {
             source_info.meta = None;
             source_info.version
         };
-
         let set_asset_failed = || {
             let mut asset_sources = self.server.asset_sources.write();
             let source_info = asset_sources
@@ -423,13 +676,39 @@ impl AssetServer {
             source_info.load_state = LoadState::Failed;
         };
 
+        // process the asset source if it has a processor, which is skipped if it's up to date
+        let processed_asset = match self.process_async(asset_path.path()).await {
+            Ok(processed_asset) => processed_asset,
+            Err(err) => {
+                set_asset_failed();
+                return Err(AssetServerError::ProcessError(err));
+            }
+        };
+        let loader_path = processed_asset
+            .as_ref()
+            .map_or(asset_path.path(), |asset| asset.artifact_path.as_path());
+
+        // read the meta file of the asset, which can select the loader and carry its settings
+        let (meta, meta_loader) = match self.read_meta(asset_path.path()).await {
+            Ok(Some((meta, meta_loader))) => (Some(meta), meta_loader),
+            Ok(None) => (None, None),
+            Err(err) => {
+                set_asset_failed();
+                return Err(err);
+            }
+        };
+
         // get the according asset loader
-        let mut maybe_asset_loader = self.get_path_asset_loader(asset_path.path(), true);
+        let get_asset_loader = |include_pending| match &meta_loader {
+            Some(name) => self.get_named_asset_loader(name, loader_path, include_pending),
+            None => self.get_path_asset_loader(loader_path, include_pending),
+        };
+        let mut maybe_asset_loader = get_asset_loader(true);
 
         // if it's still pending, block until notified and refetch the new asset loader
         if let Ok(MaybeAssetLoader::Pending { receiver, .. }) = maybe_asset_loader {
             let _ = receiver.recv().await;
-            maybe_asset_loader = self.get_path_asset_loader(asset_path.path(), false);
+            maybe_asset_loader = get_asset_loader(false);
         }
 
         let asset_loader = match maybe_asset_loader {
@@ -441,25 +720,30 @@ impl AssetServer {
             Ok(MaybeAssetLoader::Pending { .. }) => unreachable!(),
         };
 
-        // load the asset bytes
-        let bytes = match self.asset_io().load_path(asset_path.path()).await {
-            Ok(bytes) => bytes,
-            Err(err) => {
-                set_asset_failed();
-                return Err(AssetServerError::AssetIoError(err));
-            }
+        // open a reader of the asset, over the processed artifact if there is one
+        let mut reader: Box<dyn AssetReader> = match processed_asset {
+            Some(processed_asset) => Box::new(Cursor::new(processed_asset.bytes)),
+            None => match self.asset_sources().read_path(asset_path.path()).await {
+                Ok(reader) => reader,
+                Err(err) => {
+                    set_asset_failed();
+                    return Err(AssetServerError::AssetIoError(err));
+                }
+            },
         };
 
         // load the asset source using the corresponding AssetLoader
         let mut load_context = LoadContext::new(
             asset_path.path(),
             &self.server.asset_ref_counter.channel,
-            self.asset_io(),
+            self.asset_sources(),
             version,
         );
+        load_context.meta = meta.as_deref();
+        load_context.settings_override = settings_override;
 
         if let Err(err) = asset_loader
-            .load(&bytes, &mut load_context)
+            .load_from_reader(&mut *reader, &mut load_context)
             .await
             .map_err(AssetServerError::AssetLoaderError)
         {
@@ -501,7 +785,7 @@ impl AssetServer {
             }
         }
 
-        self.asset_io()
+        self.asset_sources()
             .watch_path_for_changes(asset_path.path(), None)
             .unwrap();
         self.create_assets_in_load_context(&mut load_context);
@@ -526,11 +810,23 @@ impl AssetServer {
     }
 
     pub(crate) fn load_untracked(&self, asset_path: AssetPath<'_>, force: bool) -> HandleId {
+        self.load_untracked_with_settings(asset_path, force, None)
+    }
+
+    fn load_untracked_with_settings(
+        &self,
+        asset_path: AssetPath<'_>,
+        force: bool,
+        settings_override: Option<SettingsOverride>,
+    ) -> HandleId {
         let server = self.clone();
         let owned_path = asset_path.to_owned();
         IoTaskPool::get()
             .spawn(async move {
-                if let Err(err) = server.load_async(owned_path, force).await {
+                if let Err(err) = server
+                    .load_async_with_settings(owned_path, force, settings_override)
+                    .await
+                {
                     warn!("{}", err);
                 }
             })
@@ -560,15 +856,15 @@ impl AssetServer {
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
         let path = path.as_ref();
-        if !self.asset_io().is_dir(path) {
+        if !self.asset_sources().is_dir(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
         let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
-            if self.asset_io().is_dir(&child_path) {
+        for child_path in self.asset_sources().read_directory(path.as_ref())? {
+            if self.asset_sources().is_dir(&child_path) {
                 handles.extend(self.load_folder(&child_path)?);
             } else {
                 if self.get_path_asset_loader(&child_path, true).is_err() {
@@ -1040,4 +1336,582 @@ mod test {
         let invalid_path = AssetPath::new("some/path.ext".into(), None);
         assert!(server.get_handle_path(invalid_path).is_none());
     }
+
+    /// Prepends the bytes of `header.part` to `.body` sources, and uppercases them.
+    #[derive(Default)]
+    struct HeaderProcessor {
+        runs: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl AssetProcessor for HeaderProcessor {
+        fn process<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            process_context: &'a mut crate::ProcessContext,
+        ) -> BoxedFuture<'a, Result<Vec<u8>, anyhow::Error>> {
+            Box::pin(async move {
+                self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                if bytes == b"fail" {
+                    anyhow::bail!("failed");
+                }
+                let mut processed = process_context.read_asset_bytes("header.part").await?;
+                processed.extend_from_slice(bytes);
+                Ok(processed.to_ascii_uppercase())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["body"]
+        }
+
+        fn artifact_extension(&self) -> Option<&str> {
+            Some("upper")
+        }
+    }
+
+    struct UpperLoader {
+        loaded: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl AssetLoader for UpperLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            self.loaded.lock().push(bytes.to_vec());
+            ctx.set_default_asset(LoadedAsset::new(PngAsset));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["upper"]
+        }
+    }
+
+    fn setup_processing() -> (AssetServer, tempfile::TempDir, tempfile::TempDir) {
+        let asset_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(asset_dir.path().join("sub")).unwrap();
+        std::fs::write(asset_dir.path().join("header.part"), "header ").unwrap();
+        std::fs::write(asset_dir.path().join("a.body"), "a").unwrap();
+        std::fs::write(asset_dir.path().join("sub/b.body"), "b").unwrap();
+        let imported_dir = tempfile::tempdir().unwrap();
+
+        let asset_server = setup(asset_dir.path());
+        asset_server.set_imported_assets(ImportedAssets::new(imported_dir.path()));
+        (asset_server, asset_dir, imported_dir)
+    }
+
+    #[test]
+    fn test_process_folder_only_reprocesses_changes() {
+        let (asset_server, asset_dir, imported_dir) = setup_processing();
+        let processor = HeaderProcessor::default();
+        let runs = processor.runs.clone();
+        asset_server.add_processor(processor);
+        let process = || {
+            let mut processed =
+                futures_lite::future::block_on(asset_server.process_folder(".")).unwrap();
+            processed.sort();
+            processed
+        };
+
+        assert_eq!(process(), [Path::new("a.body"), Path::new("sub/b.body")]);
+        assert_eq!(
+            std::fs::read(imported_dir.path().join("sub/b.body.upper")).unwrap(),
+            b"HEADER B"
+        );
+        let imported_assets = asset_server.imported_assets().unwrap();
+        let record =
+            futures_lite::future::block_on(imported_assets.read_record(Path::new("a.body")))
+                .unwrap();
+        assert_eq!(record.artifact, Path::new("a.body.upper"));
+        assert_eq!(record.source_hash, crate::hash_asset_bytes(b"a"));
+        assert_eq!(record.dependencies[0].path, Path::new("header.part"));
+
+        // nothing changed
+        assert!(process().is_empty());
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // a source changed
+        std::fs::write(asset_dir.path().join("a.body"), "c").unwrap();
+        assert_eq!(process(), [Path::new("a.body")]);
+        assert_eq!(
+            std::fs::read(imported_dir.path().join("a.body.upper")).unwrap(),
+            b"HEADER C"
+        );
+
+        // a dependency changed
+        std::fs::write(asset_dir.path().join("header.part"), "title ").unwrap();
+        assert_eq!(process(), [Path::new("a.body"), Path::new("sub/b.body")]);
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 5);
+    }
+
+    #[test]
+    fn test_process_only_reads_sources_with_new_stamps() {
+        let (asset_server, asset_dir, _imported_dir) = setup_processing();
+        let processor = HeaderProcessor::default();
+        let runs = processor.runs.clone();
+        asset_server.add_processor(processor);
+        let imported_assets = asset_server.imported_assets().unwrap();
+        let source = asset_dir.path().join("a.body");
+        let set_modified = |path: &Path, seconds| {
+            let modified = filetime::FileTime::from_unix_time(seconds, 0);
+            filetime::set_file_mtime(path, modified).unwrap();
+        };
+        let process = || {
+            futures_lite::future::block_on(asset_server.process_async(Path::new("a.body")))
+                .unwrap()
+                .unwrap()
+                .status
+        };
+        let record = || {
+            futures_lite::future::block_on(imported_assets.read_record(Path::new("a.body")))
+                .unwrap()
+        };
+
+        // sources modified just before being processed have no stamp
+        assert_eq!(process(), ProcessStatus::Processed);
+        assert_eq!(record().source_stamp, None);
+
+        // the stamps are recorded once the sources are found unchanged
+        set_modified(&source, 1_000_000);
+        set_modified(&asset_dir.path().join("header.part"), 1_000_000);
+        assert_eq!(process(), ProcessStatus::UpToDate);
+        assert_eq!(record().source_stamp.unwrap().len, 1);
+        assert!(record().dependencies[0].stamp.is_some());
+
+        // a source with the same stamp isn't read again
+        std::fs::write(&source, "c").unwrap();
+        set_modified(&source, 1_000_000);
+        assert_eq!(process(), ProcessStatus::UpToDate);
+
+        // a source with a new stamp is
+        set_modified(&source, 2_000_000);
+        assert_eq!(process(), ProcessStatus::Processed);
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_load_processed_asset() {
+        let (asset_server, asset_dir, _imported_dir) = setup_processing();
+        let loaded = Arc::new(Mutex::new(Vec::new()));
+        asset_server.add_loader(UpperLoader {
+            loaded: loaded.clone(),
+        });
+        let processor = HeaderProcessor::default();
+        let runs = processor.runs.clone();
+        asset_server.add_processor(processor);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let path: AssetPath = "a.body".into();
+        for _ in 0..2 {
+            futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        }
+        assert_eq!(*loaded.lock(), [b"HEADER A".to_vec(), b"HEADER A".to_vec()]);
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        std::fs::write(asset_dir.path().join("a.body"), "fail").unwrap();
+        let err = futures_lite::future::block_on(asset_server.load_async(path.clone(), true))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AssetServerError::ProcessError(ProcessError::ProcessorError(_))
+        ));
+        assert_eq!(
+            asset_server.get_load_state(path.get_id()),
+            LoadState::Failed
+        );
+    }
+
+    #[test]
+    fn test_process_validates_cached_artifacts() {
+        let (asset_server, asset_dir, imported_dir) = setup_processing();
+        let processor = HeaderProcessor::default();
+        let runs = processor.runs.clone();
+        asset_server.add_processor(processor);
+        let process = |path: &str| {
+            futures_lite::future::block_on(asset_server.process_async(Path::new(path)))
+                .unwrap()
+                .unwrap()
+        };
+
+        // concurrent processing of the same source only processes it once
+        let (first, second) = futures_lite::future::block_on(futures_lite::future::zip(
+            asset_server.process_async(Path::new("a.body")),
+            asset_server.process_async(Path::new("a.body")),
+        ));
+        let mut statuses = [
+            first.unwrap().unwrap().status,
+            second.unwrap().unwrap().status,
+        ];
+        statuses.sort_by_key(|status| *status == ProcessStatus::Processed);
+        assert_eq!(
+            statuses,
+            [ProcessStatus::UpToDate, ProcessStatus::Processed]
+        );
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // the artifact is loaded without its source
+        std::fs::remove_file(asset_dir.path().join("a.body")).unwrap();
+        let asset = process("a.body");
+        assert_eq!(asset.status, ProcessStatus::UpToDate);
+        assert_eq!(asset.bytes, b"HEADER A");
+
+        // a record can't point outside of the imported folder
+        process("sub/b.body");
+        let record_path = imported_dir.path().join("sub/b.body.record");
+        let record = std::fs::read_to_string(&record_path).unwrap();
+        std::fs::write(
+            &record_path,
+            record.replace("\"sub/b.body.upper\"", "\"../secret\""),
+        )
+        .unwrap();
+        assert_eq!(process("sub/b.body").status, ProcessStatus::Processed);
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[derive(serde::Deserialize, Default, Debug, PartialEq, Clone)]
+    #[serde(default)]
+    struct GreetingSettings {
+        greeting: String,
+        loud: bool,
+    }
+
+    struct GreetingLoader {
+        loaded: Arc<Mutex<Vec<GreetingSettings>>>,
+    }
+
+    impl AssetLoader for GreetingLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                self.loaded.lock().push(ctx.settings()?);
+                ctx.set_default_asset(LoadedAsset::new(PngAsset));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["greet"]
+        }
+    }
+
+    fn setup_meta(files: &[(&str, &str)]) -> (AssetServer, tempfile::TempDir) {
+        let asset_dir = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            std::fs::write(asset_dir.path().join(path), contents).unwrap();
+        }
+        let asset_server = setup(asset_dir.path());
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        (asset_server, asset_dir)
+    }
+
+    fn load_settings(
+        asset_server: &AssetServer,
+        path: &str,
+    ) -> Result<GreetingSettings, AssetServerError> {
+        let loaded = Arc::new(Mutex::new(Vec::new()));
+        asset_server.add_loader(GreetingLoader {
+            loaded: loaded.clone(),
+        });
+        futures_lite::future::block_on(asset_server.load_async(path.into(), true))?;
+        let settings = loaded.lock().pop().unwrap();
+        Ok(settings)
+    }
+
+    #[test]
+    fn test_meta_loader_settings() {
+        let (asset_server, _dir) = setup_meta(&[
+            ("a.greet", ""),
+            (
+                "a.greet.meta",
+                "(settings: Some((greeting: \"hi\", loud: true)))",
+            ),
+            ("b.greet", ""),
+        ]);
+
+        assert_eq!(
+            load_settings(&asset_server, "a.greet").unwrap(),
+            GreetingSettings {
+                greeting: "hi".to_string(),
+                loud: true,
+            }
+        );
+        assert_eq!(
+            load_settings(&asset_server, "b.greet").unwrap(),
+            GreetingSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_load_with_settings() {
+        let (asset_server, _dir) = setup_meta(&[
+            ("a.greet", ""),
+            (
+                "a.greet.meta",
+                "(settings: Some((greeting: \"hi\", loud: true)))",
+            ),
+        ]);
+
+        let loaded = Arc::new(Mutex::new(Vec::new()));
+        asset_server.add_loader(GreetingLoader {
+            loaded: loaded.clone(),
+        });
+        let _handle: Handle<PngAsset> =
+            asset_server.load_with_settings("a.greet", |settings: &mut GreetingSettings| {
+                settings.loud = false;
+            });
+        let start = std::time::Instant::now();
+        while loaded.lock().is_empty() {
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            loaded.lock().pop().unwrap(),
+            GreetingSettings {
+                greeting: "hi".to_string(),
+                loud: false,
+            }
+        );
+        // the settings are not kept for reloads
+        assert!(load_settings(&asset_server, "a.greet").unwrap().loud);
+
+        // nor are the settings given for an asset which is already loading
+        let _handle: Handle<PngAsset> =
+            asset_server.load_with_settings("a.greet", |settings: &mut u32| *settings = 1);
+        assert!(load_settings(&asset_server, "a.greet").unwrap().loud);
+
+        let err = futures_lite::future::block_on(asset_server.load_async_with_settings(
+            "a.greet".into(),
+            true,
+            Some(Arc::new(|settings| {
+                settings.downcast_mut::<u32>().is_some()
+            })),
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "encountered an error while loading an asset: \
+                the settings given to `load_with_settings` are not `{}`",
+                std::any::type_name::<GreetingSettings>()
+            )
+        );
+    }
+
+    #[test]
+    fn test_meta_selects_loader() {
+        let (asset_server, _dir) = setup_meta(&[
+            ("a.png", ""),
+            (
+                "a.png.meta",
+                "(loader: Some(\"GreetingLoader\"), settings: Some((greeting: \"png\")))",
+            ),
+            ("b.png", ""),
+            ("b.png.meta", "(loader: Some(\"MissingLoader\"))"),
+            ("c.greet", ""),
+            ("c.greet.meta", "(settings: Some((loud: 1)))"),
+        ]);
+        asset_server.add_loader(FakePngLoader);
+
+        assert_eq!(
+            load_settings(&asset_server, "a.png").unwrap().greeting,
+            "png"
+        );
+        assert!(matches!(
+            load_settings(&asset_server, "b.png").unwrap_err(),
+            AssetServerError::MissingNamedAssetLoader(name) if name == "MissingLoader"
+        ));
+        assert!(matches!(
+            load_settings(&asset_server, "c.greet").unwrap_err(),
+            AssetServerError::AssetLoaderError(_)
+        ));
+    }
+
+    #[test]
+    fn test_meta_waits_for_preregistered_loader() {
+        let (asset_server, _dir) = setup_meta(&[
+            ("a.greet", ""),
+            ("a.greet.meta", "(loader: Some(\"GreetingLoader\"))"),
+        ]);
+        asset_server.preregister_loader(&["greet"]);
+
+        let load = std::thread::spawn({
+            let asset_server = asset_server.clone();
+            move || futures_lite::future::block_on(asset_server.load_async("a.greet".into(), true))
+        });
+        let loaded = Arc::new(Mutex::new(Vec::new()));
+        asset_server.add_loader(GreetingLoader {
+            loaded: loaded.clone(),
+        });
+        load.join().unwrap().unwrap();
+        assert_eq!(loaded.lock().len(), 1);
+    }
+
+    fn create_dir_with_files(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let asset_dir = tempfile::tempdir().unwrap();
+        for (path, contents) in files {
+            std::fs::write(asset_dir.path().join(path), contents).unwrap();
+        }
+        asset_dir
+    }
+
+    #[test]
+    fn test_asset_sources_override_order() {
+        use crate::FileAssetIo;
+
+        let base = create_dir_with_files(&[("a.txt", "base"), ("b.txt", "base")]);
+        let pack = create_dir_with_files(&[("a.txt", "pack"), ("c.txt", "pack")]);
+        let mods = create_dir_with_files(&[("a.txt", "mods")]);
+        let extra = create_dir_with_files(&[("a.txt", "extra")]);
+        let asset_server = setup(base.path());
+        asset_server.add_overriding_source("pack", FileAssetIo::new(pack.path(), &None));
+        asset_server.add_overriding_source("mods", FileAssetIo::new(mods.path(), &None));
+        asset_server.add_source("extra", FileAssetIo::new(extra.path(), &None));
+
+        let sources = asset_server.asset_sources();
+        assert_eq!(sources.overriding_sources(), ["mods", "pack"]);
+        let load = |path: &str| {
+            futures_lite::future::block_on(sources.load_path(Path::new(path)))
+                .map(|bytes| String::from_utf8(bytes).unwrap())
+        };
+        assert_eq!(load("a.txt").unwrap(), "mods");
+        assert_eq!(load("b.txt").unwrap(), "base");
+        assert_eq!(load("c.txt").unwrap(), "pack");
+        assert_eq!(load("pack://a.txt").unwrap(), "pack");
+        assert_eq!(load("extra://a.txt").unwrap(), "extra");
+        assert!(matches!(
+            load("extra://b.txt").unwrap_err(),
+            AssetIoError::NotFound(_)
+        ));
+        assert!(matches!(
+            load("dlc://a.txt").unwrap_err(),
+            AssetIoError::MissingSource(name) if name == "dlc"
+        ));
+
+        let mut entries = sources
+            .read_directory(Path::new(""))
+            .unwrap()
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, ["a.txt", "b.txt", "c.txt"].map(PathBuf::from));
+        let mut entries = sources
+            .read_directory(Path::new("pack://"))
+            .unwrap()
+            .collect::<Vec<_>>();
+        entries.sort();
+        assert_eq!(entries, ["pack://a.txt", "pack://c.txt"].map(PathBuf::from));
+
+        assert!(sources.remove_source("mods"));
+        assert_eq!(load("a.txt").unwrap(), "pack");
+    }
+
+    #[test]
+    fn test_load_from_named_source() {
+        use crate::FileAssetIo;
+
+        let base = create_dir_with_files(&[]);
+        let extra = create_dir_with_files(&[("fake.png", "")]);
+        let asset_server = setup(base.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        asset_server.add_source("extra", FileAssetIo::new(extra.path(), &None));
+
+        let path: AssetPath = "extra://fake.png#label".into();
+        assert_eq!(path.source(), Some("extra"));
+        assert_eq!(path.path_in_source(), Path::new("fake.png"));
+        assert_eq!(path.label(), Some("label"));
+        let unqualified: AssetPath = "fake.png".into();
+        assert_eq!(unqualified.source(), None);
+        let source_of = |path: &str| AssetPath::from(path).source().map(str::to_owned);
+        assert_eq!(source_of("c://fake.png"), Some("c".to_owned()));
+        assert_eq!(source_of("mods:/fake.png"), None);
+        assert_eq!(source_of("C:\\fake.png"), None);
+        assert_eq!(source_of("a/b://fake.png"), None);
+        let absolute: AssetPath = "extra:///fake.png".into();
+        assert_eq!(absolute.path_in_source(), Path::new("fake.png"));
+        assert_ne!(
+            path.get_id().source_path_id(),
+            unqualified.get_id().source_path_id()
+        );
+
+        let path: AssetPath = "extra://fake.png".into();
+        futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+        assert!(matches!(
+            futures_lite::future::block_on(asset_server.load_async(unqualified, true)),
+            Err(AssetServerError::AssetIoError(AssetIoError::NotFound(_)))
+        ));
+
+        let handles = asset_server.load_folder("extra://").unwrap();
+        assert_eq!(
+            asset_server.get_handle_path(&handles[0]).unwrap().path(),
+            Path::new("extra://fake.png")
+        );
+    }
+
+    struct RangeLoader {
+        read: Arc<Mutex<Vec<u8>>>,
+    }
+
+    impl AssetLoader for RangeLoader {
+        fn load<'a>(
+            &'a self,
+            bytes: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let mut reader = futures_lite::io::Cursor::new(bytes);
+                self.load_from_reader(&mut reader, ctx).await
+            })
+        }
+
+        fn load_from_reader<'a>(
+            &'a self,
+            reader: &'a mut dyn AssetReader,
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            use futures_lite::{AsyncReadExt, AsyncSeekExt};
+
+            Box::pin(async move {
+                let mut range = [0; 2];
+                reader.seek(std::io::SeekFrom::Start(5)).await?;
+                reader.read_exact(&mut range).await?;
+                *self.read.lock() = range.to_vec();
+                ctx.set_default_asset(LoadedAsset::new(PngAsset));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["range"]
+        }
+    }
+
+    #[test]
+    fn test_loader_reads_range() {
+        let dir = create_dir_with_files(&[("a.range", "skip-ok-skip")]);
+        let asset_server = setup(dir.path());
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        let read = Arc::new(Mutex::new(Vec::new()));
+        asset_server.add_loader(RangeLoader { read: read.clone() });
+
+        futures_lite::future::block_on(asset_server.load_async("a.range".into(), true)).unwrap();
+        assert_eq!(*read.lock(), b"ok");
+
+        read.lock().clear();
+        let mut ctx = LoadContext::new(
+            Path::new("a.range"),
+            &asset_server.server.asset_ref_counter.channel,
+            asset_server.asset_io(),
+            0,
+        );
+        futures_lite::future::block_on(
+            RangeLoader { read: read.clone() }.load(b"12345ok", &mut ctx),
+        )
+        .unwrap();
+        assert_eq!(*read.lock(), b"ok");
+    }
 }
@@ -1,6 +1,6 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetEvents, AssetLoader, AssetServer, Handle, HandleId,
-    LoadAssets, RefChange, ReflectAsset, ReflectHandle,
+    update_asset_storage_system, Asset, AssetEvents, AssetLoader, AssetProcessor, AssetServer,
+    Handle, HandleId, LoadAssets, RefChange, ReflectAsset, ReflectHandle,
 };
 use bevy_app::App;
 use bevy_ecs::prelude::*;
@@ -321,6 +321,14 @@ pub trait AddAsset {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader(&mut self, extensions: &[&str]) -> &mut Self;
+
+    /// Adds the provided asset processor to the application.
+    ///
+    /// Processors are only used when [`AssetPlugin::imported_asset_folder`](crate::AssetPlugin)
+    /// is set.
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor;
 }
 
 impl AddAsset for App {
@@ -415,6 +423,16 @@ impl AddAsset for App {
             .preregister_loader(extensions);
         self
     }
+
+    fn add_asset_processor<T>(&mut self, processor: T) -> &mut Self
+    where
+        T: AssetProcessor,
+    {
+        self.world
+            .resource_mut::<AssetServer>()
+            .add_processor(processor);
+        self
+    }
 }
 
 /// Loads an internal asset from a project source file.
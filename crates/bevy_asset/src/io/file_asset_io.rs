@@ -1,6 +1,6 @@
 #[cfg(feature = "filesystem_watcher")]
 use crate::{filesystem_watcher::FilesystemWatcher, AssetServer};
-use crate::{AssetIo, AssetIoError, ChangeWatcher, Metadata};
+use crate::{AssetIo, AssetIoError, AssetReader, ChangeWatcher, Metadata};
 use anyhow::Result;
 #[cfg(feature = "filesystem_watcher")]
 use bevy_ecs::system::{Local, Res};
@@ -10,6 +10,7 @@ use bevy_utils::{default, HashMap, Instant};
 #[cfg(feature = "filesystem_watcher")]
 use crossbeam_channel::TryRecvError;
 use fs::File;
+use futures_lite::io::AssertAsync;
 #[cfg(feature = "filesystem_watcher")]
 use parking_lot::RwLock;
 #[cfg(feature = "filesystem_watcher")]
@@ -17,13 +18,16 @@ use std::sync::Arc;
 use std::{
     convert::TryFrom,
     env, fs,
-    io::Read,
+    io::{BufReader, Read},
     path::{Path, PathBuf},
 };
 
 /// I/O implementation for the local filesystem.
 ///
 /// This asset I/O is fully featured but it's not available on `android` and `wasm` targets.
+///
+/// Files are read with blocking calls, including through the readers returned by
+/// [`read_path`](AssetIo::read_path), so they block the thread of the task loading the asset.
 pub struct FileAssetIo {
     root_path: PathBuf,
     #[cfg(feature = "filesystem_watcher")]
@@ -109,6 +113,30 @@ impl AssetIo for FileAssetIo {
         })
     }
 
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let full_path = self.root_path.join(path);
+            // Without the `multi-threaded` feature of `bevy_tasks`, the task pool drops the tasks
+            // which are still pending after being polled, so reading the file asynchronously
+            // would cancel the load.
+            match File::open(&full_path) {
+                Ok(file) => {
+                    Ok(Box::new(AssertAsync::new(BufReader::new(file))) as Box<dyn AssetReader>)
+                }
+                Err(e) => {
+                    if e.kind() == std::io::ErrorKind::NotFound {
+                        Err(AssetIoError::NotFound(full_path))
+                    } else {
+                        Err(e.into())
+                    }
+                }
+            }
+        })
+    }
+
     fn read_directory(
         &self,
         path: &Path,
@@ -170,6 +198,9 @@ impl AssetIo for FileAssetIo {
 }
 
 /// Watches for file changes in the local file system.
+///
+/// Changes are watched in the default asset source and in the named asset sources which are
+/// [`FileAssetIo`]s.
 #[cfg(all(
     feature = "filesystem_watcher",
     all(not(target_arch = "wasm32"), not(target_os = "android"))
@@ -178,15 +209,17 @@ pub fn filesystem_watcher_system(
     asset_server: Res<AssetServer>,
     mut changed: Local<HashMap<PathBuf, Instant>>,
 ) {
-    let asset_io =
-        if let Some(asset_io) = asset_server.server.asset_io.downcast_ref::<FileAssetIo>() {
-            asset_io
-        } else {
-            return;
+    let mut delay = None;
+    for asset_io in asset_server.asset_sources().asset_ios() {
+        let Some(asset_io) = asset_io.downcast_ref::<FileAssetIo>() else {
+            continue;
         };
-    let watcher = asset_io.filesystem_watcher.read();
+        let watcher = asset_io.filesystem_watcher.read();
+        let Some(ref watcher) = *watcher else {
+            continue;
+        };
+        delay = delay.max(Some(watcher.delay));
 
-    if let Some(ref watcher) = *watcher {
         loop {
             let event = match watcher.receiver.try_recv() {
                 Ok(result) => result.unwrap(),
@@ -209,16 +242,18 @@ pub fn filesystem_watcher_system(
                 }
             }
         }
+    }
 
-        // Reload all assets whose last modification was at least 50ms ago.
-        //
-        // When changing and then saving a shader, several modification events are sent in short succession.
-        // Unless we wait until we are sure the shader is finished being modified (and that there will be no more events coming),
-        // we will sometimes get a crash when trying to reload a partially-modified shader.
-        for (to_reload, _) in
-            changed.extract_if(|_, last_modified| last_modified.elapsed() >= watcher.delay)
-        {
-            let _ = asset_server.load_untracked(to_reload.as_path().into(), true);
-        }
+    let Some(delay) = delay else {
+        return;
+    };
+
+    // Reload all assets whose last modification was at least 50ms ago.
+    //
+    // When changing and then saving a shader, several modification events are sent in short succession.
+    // Unless we wait until we are sure the shader is finished being modified (and that there will be no more events coming),
+    // we will sometimes get a crash when trying to reload a partially-modified shader.
+    for (to_reload, _) in changed.extract_if(|_, last_modified| last_modified.elapsed() >= delay) {
+        let _ = asset_server.load_untracked(to_reload.as_path().into(), true);
     }
 }
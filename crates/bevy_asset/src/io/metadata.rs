@@ -1,4 +1,7 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    time::SystemTime,
+};
 
 /// A enum representing a type of file.
 #[non_exhaustive]
@@ -47,12 +50,30 @@ impl TryFrom<std::fs::FileType> for FileType {
 #[derive(Debug, Clone)]
 pub struct Metadata {
     file_type: FileType,
+    size: Option<u64>,
+    modified: Option<SystemTime>,
 }
 
 impl Metadata {
     /// Creates new metadata information.
     pub fn new(file_type: FileType) -> Self {
-        Self { file_type }
+        Self {
+            file_type,
+            size: None,
+            modified: None,
+        }
+    }
+
+    /// Sets the size of the file, in bytes.
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Sets the last modification time of the file.
+    pub fn with_modified(mut self, modified: SystemTime) -> Self {
+        self.modified = Some(modified);
+        self
     }
 
     /// Returns the file type.
@@ -72,6 +93,18 @@ impl Metadata {
     pub const fn is_file(&self) -> bool {
         self.file_type.is_file()
     }
+
+    /// Returns the size of the file in bytes, if it's known.
+    #[inline]
+    pub const fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Returns the last modification time of the file, if it's known.
+    #[inline]
+    pub const fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
 }
 
 impl TryFrom<std::fs::Metadata> for Metadata {
@@ -80,6 +113,8 @@ impl TryFrom<std::fs::Metadata> for Metadata {
     fn try_from(metadata: std::fs::Metadata) -> Result<Self, Self::Error> {
         Ok(Self {
             file_type: metadata.file_type().try_into()?,
+            size: Some(metadata.len()),
+            modified: metadata.modified().ok(),
         })
     }
 }
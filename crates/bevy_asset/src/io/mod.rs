@@ -6,6 +6,8 @@ mod file_asset_io;
 mod wasm_asset_io;
 
 mod metadata;
+mod pack_asset_io;
+mod sources;
 
 #[cfg(target_os = "android")]
 pub use android_asset_io::*;
@@ -15,10 +17,13 @@ pub use file_asset_io::*;
 pub use wasm_asset_io::*;
 
 pub use metadata::*;
+pub use pack_asset_io::*;
+pub use sources::*;
 
 use anyhow::Result;
 use bevy_utils::BoxedFuture;
 use downcast_rs::{impl_downcast, Downcast};
+use futures_lite::io::{AsyncRead, AsyncSeek, Cursor};
 use std::{
     io,
     path::{Path, PathBuf},
@@ -41,8 +46,20 @@ pub enum AssetIoError {
     /// Failed to watch path.
     #[error("failed to watch path: {0}")]
     PathWatchError(PathBuf),
+
+    /// No asset source has the name given in the path.
+    #[error("no asset source named `{0}`")]
+    MissingSource(String),
 }
 
+/// An asynchronous reader of the bytes of an asset, returned by [`AssetIo::read_path`].
+///
+/// Readers can [seek](futures_lite::AsyncSeekExt::seek) to read only some ranges of an asset, for
+/// instance some mip levels of a texture, without loading the rest of the asset into memory.
+pub trait AssetReader: AsyncRead + AsyncSeek + Unpin + Send {}
+
+impl<T: AsyncRead + AsyncSeek + Unpin + Send + ?Sized> AssetReader for T {}
+
 /// A storage provider for an [`AssetServer`].
 ///
 /// An asset I/O is the backend actually providing data for the asset loaders managed by the asset
@@ -58,6 +75,21 @@ pub trait AssetIo: Downcast + Send + Sync + 'static {
     /// Returns a future to load the full file data at the provided path.
     fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>>;
 
+    /// Returns a future to open a reader of the file data at the provided path.
+    ///
+    /// Unlike [`load_path`](AssetIo::load_path), this lets loaders stream the file or read only
+    /// some ranges of it. The default implementation loads the full file data with `load_path`,
+    /// so asset I/Os which can read files incrementally should override it.
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let bytes = self.load_path(path).await?;
+            Ok(Box::new(Cursor::new(bytes)) as Box<dyn AssetReader>)
+        })
+    }
+
     /// Returns an iterator of directory entry names at the provided path.
     fn read_directory(
         &self,
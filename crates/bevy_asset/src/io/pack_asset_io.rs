@@ -0,0 +1,707 @@
+use crate::{AssetIo, AssetIoError, AssetReader, ChangeWatcher, FileType, Metadata};
+use anyhow::Result;
+use bevy_utils::{BoxedFuture, HashMap};
+use futures_lite::io::{AsyncRead, AsyncSeek};
+use parking_lot::Mutex;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Component, Path, PathBuf},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// The bytes starting an asset pack.
+const PACK_MAGIC: &[u8; 8] = b"BEVYPACK";
+/// The version of the asset pack format.
+const PACK_VERSION: u32 = 1;
+/// The length of the magic bytes and the version.
+const PACK_HEADER_LEN: u64 = 12;
+/// The smallest length of a file in the index: the path length, the compression, the offset and
+/// the lengths.
+const PACK_ENTRY_MIN_LEN: u64 = 4 + 1 + 3 * 8;
+
+/// How the files of an asset pack are compressed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PackCompression {
+    /// Files are stored uncompressed.
+    #[default]
+    None,
+    /// Files are compressed with deflate, unless this doesn't make them smaller.
+    ///
+    /// Requires the `asset_pack_compression` feature, to build and to read the pack. Without it,
+    /// both fail with an [`Unsupported`](io::ErrorKind::Unsupported) error.
+    Deflate,
+}
+
+impl PackCompression {
+    fn to_byte(self) -> u8 {
+        match self {
+            PackCompression::None => 0,
+            PackCompression::Deflate => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> io::Result<Self> {
+        match byte {
+            0 => Ok(PackCompression::None),
+            #[cfg(feature = "asset_pack_compression")]
+            1 => Ok(PackCompression::Deflate),
+            #[cfg(not(feature = "asset_pack_compression"))]
+            1 => Err(compression_unsupported()),
+            _ => Err(invalid_pack("unknown compression")),
+        }
+    }
+
+    fn compress(self, bytes: Vec<u8>) -> io::Result<(Self, Vec<u8>)> {
+        match self {
+            PackCompression::None => Ok((self, bytes)),
+            #[cfg(feature = "asset_pack_compression")]
+            PackCompression::Deflate => {
+                let mut encoder =
+                    flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&bytes)?;
+                let compressed = encoder.finish()?;
+                if compressed.len() < bytes.len() {
+                    Ok((self, compressed))
+                } else {
+                    Ok((PackCompression::None, bytes))
+                }
+            }
+            #[cfg(not(feature = "asset_pack_compression"))]
+            PackCompression::Deflate => Err(compression_unsupported()),
+        }
+    }
+
+    /// Decompresses a file, reading at most one byte more than its length `len` so that a
+    /// corrupted pack can't make us allocate more than the file.
+    #[cfg_attr(not(feature = "asset_pack_compression"), allow(unused_variables))]
+    fn decompress(self, bytes: Vec<u8>, len: u64) -> io::Result<Vec<u8>> {
+        match self {
+            PackCompression::None => Ok(bytes),
+            #[cfg(feature = "asset_pack_compression")]
+            PackCompression::Deflate => {
+                let mut decompressed = Vec::new();
+                flate2::read::DeflateDecoder::new(bytes.as_slice())
+                    .take(len.saturating_add(1))
+                    .read_to_end(&mut decompressed)?;
+                Ok(decompressed)
+            }
+            #[cfg(not(feature = "asset_pack_compression"))]
+            PackCompression::Deflate => Err(compression_unsupported()),
+        }
+    }
+}
+
+/// A file in an asset pack.
+#[derive(Clone, Debug)]
+struct PackEntry {
+    offset: u64,
+    stored_len: u64,
+    len: u64,
+    compression: PackCompression,
+}
+
+trait PackReader: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> PackReader for T {}
+
+/// I/O implementation reading assets from a single asset pack, built with [`AssetPackBuilder`].
+///
+/// The index of the pack is read when it's opened, then files are read on demand. Uncompressed
+/// files can be [streamed](AssetIo::read_path) without reading them fully. Packs can be
+/// read from any [`Read`] + [`Seek`] source, so they can also be embedded in the executable or
+/// downloaded, and used on every target.
+///
+/// Packs can't change, so changes aren't watched. A pack can be added as an
+/// [overriding source](crate::AssetServer::add_overriding_source) to patch the assets of a game,
+/// for instance with downloadable content.
+///
+/// ```no_run
+/// # use bevy_asset::{AssetPackBuilder, AssetServer, PackAssetIo};
+/// // when building a release
+/// let mut builder = AssetPackBuilder::new();
+/// builder.add_directory("assets").unwrap();
+/// builder.write_to_file("assets.pack").unwrap();
+///
+/// // in the game
+/// let asset_server = AssetServer::new(PackAssetIo::open("assets.pack").unwrap());
+/// ```
+pub struct PackAssetIo {
+    reader: Arc<Mutex<Box<dyn PackReader>>>,
+    entries: HashMap<PathBuf, PackEntry>,
+    directories: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl PackAssetIo {
+    /// Opens the asset pack at `path` on the local filesystem.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads an asset pack from `reader`, for instance a [`Cursor`](std::io::Cursor) over the
+    /// bytes of a pack.
+    pub fn from_reader<R: Read + Seek + Send + 'static>(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; 8];
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut magic)?;
+        if &magic != PACK_MAGIC {
+            return Err(invalid_pack("not an asset pack"));
+        }
+        let version = read_u32(&mut reader)?;
+        if version != PACK_VERSION {
+            return Err(invalid_pack(&format!("unsupported version {version}")));
+        }
+
+        // the lengths read from the pack are checked against its length before allocating, so
+        // that a corrupted pack can't make us allocate more than its size
+        let pack_len = reader.seek(SeekFrom::End(0))?;
+        if pack_len < PACK_HEADER_LEN + 8 {
+            return Err(invalid_pack("truncated pack"));
+        }
+        reader.seek(SeekFrom::End(-8))?;
+        let index_offset = read_u64(&mut reader)?;
+        let index_end = pack_len - 8;
+        if !(PACK_HEADER_LEN..=index_end).contains(&index_offset) {
+            return Err(invalid_pack("invalid index offset"));
+        }
+        let index_len = index_end - index_offset;
+        reader.seek(SeekFrom::Start(index_offset))?;
+        let mut index = BufReader::new(&mut reader);
+
+        let mut entries = HashMap::default();
+        let mut directories: HashMap<PathBuf, Vec<PathBuf>> = HashMap::default();
+        directories.insert(PathBuf::new(), Vec::new());
+        let file_count = read_u32(&mut index)?;
+        if u64::from(file_count) * PACK_ENTRY_MIN_LEN > index_len {
+            return Err(invalid_pack("invalid file count"));
+        }
+        for _ in 0..file_count {
+            let path_len = read_u32(&mut index)?;
+            if u64::from(path_len) > index_len {
+                return Err(invalid_pack("invalid path length"));
+            }
+            let mut path = vec![0; path_len as usize];
+            index.read_exact(&mut path)?;
+            let path = String::from_utf8(path).map_err(|_| invalid_pack("invalid path"))?;
+            if path
+                .split('/')
+                .any(|component| matches!(component, "" | "." | ".."))
+            {
+                return Err(invalid_pack("invalid path"));
+            }
+            let path = PathBuf::from_iter(path.split('/'));
+            if entries.contains_key(&path) {
+                return Err(invalid_pack("duplicate path"));
+            }
+            let mut compression = [0];
+            index.read_exact(&mut compression)?;
+            let entry = PackEntry {
+                compression: PackCompression::from_byte(compression[0])?,
+                offset: read_u64(&mut index)?,
+                stored_len: read_u64(&mut index)?,
+                len: read_u64(&mut index)?,
+            };
+            let in_bounds = match entry.offset.checked_add(entry.stored_len) {
+                Some(stored_end) => entry.offset >= PACK_HEADER_LEN && stored_end <= index_offset,
+                None => false,
+            };
+            if !in_bounds {
+                return Err(invalid_pack("invalid file bounds"));
+            }
+            if entry.compression == PackCompression::None && entry.len != entry.stored_len {
+                return Err(invalid_pack("invalid file length"));
+            }
+
+            // register the file in its directory, and the directories in their parents
+            let mut child = path.clone();
+            while let Some(parent) = child.parent() {
+                let new_directory = !directories.contains_key(parent);
+                directories
+                    .entry(parent.to_owned())
+                    .or_default()
+                    .push(child.clone());
+                if !new_directory {
+                    break;
+                }
+                child = parent.to_owned();
+            }
+            entries.insert(path, entry);
+        }
+
+        Ok(Self {
+            reader: Arc::new(Mutex::new(Box::new(reader))),
+            entries,
+            directories,
+        })
+    }
+
+    /// Returns an iterator over the paths of the files of this pack.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.entries.keys().map(PathBuf::as_path)
+    }
+
+    fn read_entry(&self, entry: &PackEntry) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0; entry.stored_len as usize];
+        {
+            let mut reader = self.reader.lock();
+            reader.seek(SeekFrom::Start(entry.offset))?;
+            reader.read_exact(&mut bytes)?;
+        }
+        let bytes = entry.compression.decompress(bytes, entry.len)?;
+        if bytes.len() as u64 != entry.len {
+            return Err(invalid_pack("corrupted file"));
+        }
+        Ok(bytes)
+    }
+
+    fn entry(&self, path: &Path) -> Result<&PackEntry, AssetIoError> {
+        self.entries
+            .get(&normalize(path))
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))
+    }
+}
+
+impl AssetIo for PackAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move { Ok(self.read_entry(self.entry(path)?)?) })
+    }
+
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let entry = self.entry(path)?;
+            if entry.compression != PackCompression::None {
+                let bytes = self.read_entry(entry)?;
+                return Ok(Box::new(futures_lite::io::Cursor::new(bytes)) as Box<dyn AssetReader>);
+            }
+            Ok(Box::new(PackEntryReader {
+                reader: self.reader.clone(),
+                offset: entry.offset,
+                len: entry.len,
+                position: 0,
+            }) as Box<dyn AssetReader>)
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let children = self
+            .directories
+            .get(&normalize(path))
+            .ok_or_else(|| AssetIoError::NotFound(path.to_owned()))?;
+        Ok(Box::new(children.clone().into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let path_in_pack = normalize(path);
+        if self.entries.contains_key(&path_in_pack) {
+            Ok(Metadata::new(FileType::File))
+        } else if self.directories.contains_key(&path_in_pack) {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_owned()))
+        }
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        _to_watch: &Path,
+        _to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self, _configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+        bevy_log::warn!("Watching for changes is not supported by asset packs");
+        Ok(())
+    }
+}
+
+/// A reader of an uncompressed file of a [`PackAssetIo`], reading the pack on demand.
+struct PackEntryReader {
+    reader: Arc<Mutex<Box<dyn PackReader>>>,
+    offset: u64,
+    len: u64,
+    position: u64,
+}
+
+impl AsyncRead for PackEntryReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let len = (buf.len() as u64).min(this.len.saturating_sub(this.position)) as usize;
+        if len == 0 {
+            return Poll::Ready(Ok(0));
+        }
+        let mut reader = this.reader.lock();
+        let read = reader
+            .seek(SeekFrom::Start(this.offset + this.position))
+            .and_then(|_| reader.read(&mut buf[..len]));
+        if let Ok(read) = read {
+            this.position += read as u64;
+        }
+        Poll::Ready(read)
+    }
+}
+
+impl AsyncSeek for PackEntryReader {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        pos: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+        let position = match pos {
+            SeekFrom::Start(position) => Some(position),
+            SeekFrom::End(offset) => this.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => this.position.checked_add_signed(offset),
+        };
+        Poll::Ready(match position {
+            Some(position) => {
+                this.position = position;
+                Ok(position)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        })
+    }
+}
+
+/// The contents of a file added to an [`AssetPackBuilder`].
+#[derive(Debug)]
+enum PackSource {
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+/// Builds an asset pack, a single file containing many assets which is read by [`PackAssetIo`].
+///
+/// Files are added with the path at which they're loaded, and are only read from the filesystem
+/// when the pack is written.
+#[derive(Debug, Default)]
+pub struct AssetPackBuilder {
+    files: BTreeMap<String, PackSource>,
+    compression: PackCompression,
+}
+
+impl AssetPackBuilder {
+    /// Creates a builder for an empty pack.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how the files of the pack are compressed.
+    pub fn with_compression(mut self, compression: PackCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Adds a file with the given contents at `path` in the pack, replacing any file at this path.
+    pub fn add_file<P: AsRef<Path>>(&mut self, path: P, bytes: Vec<u8>) -> io::Result<()> {
+        self.files
+            .insert(pack_path(path.as_ref())?, PackSource::Bytes(bytes));
+        Ok(())
+    }
+
+    /// Adds the files in the `root` directory of the local filesystem recursively, at their path
+    /// relative to `root`, replacing any file at these paths.
+    pub fn add_directory<P: AsRef<Path>>(&mut self, root: P) -> io::Result<()> {
+        let root = root.as_ref();
+        let mut directories = vec![root.to_owned()];
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(directory)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else {
+                    let path_in_pack = path.strip_prefix(root).unwrap();
+                    self.files
+                        .insert(pack_path(path_in_pack)?, PackSource::File(path));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the pack to `writer`.
+    pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writer.write_all(PACK_MAGIC)?;
+        writer.write_all(&PACK_VERSION.to_le_bytes())?;
+
+        let mut index = Vec::new();
+        let mut offset = PACK_HEADER_LEN;
+        for (path, source) in &self.files {
+            let bytes = match source {
+                PackSource::Bytes(bytes) => bytes.clone(),
+                PackSource::File(path) => fs::read(path)?,
+            };
+            let len = bytes.len() as u64;
+            let (compression, bytes) = self.compression.compress(bytes)?;
+            writer.write_all(&bytes)?;
+
+            index.extend((path.len() as u32).to_le_bytes());
+            index.extend(path.as_bytes());
+            index.push(compression.to_byte());
+            index.extend(offset.to_le_bytes());
+            index.extend((bytes.len() as u64).to_le_bytes());
+            index.extend(len.to_le_bytes());
+            offset += bytes.len() as u64;
+        }
+
+        writer.write_all(&(self.files.len() as u32).to_le_bytes())?;
+        writer.write_all(&index)?;
+        writer.write_all(&offset.to_le_bytes())?;
+        writer.flush()
+    }
+
+    /// Writes the pack to a file at `path` on the local filesystem.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(File::create(path)?)
+    }
+}
+
+/// Returns the path in a pack of a file at `path`, with `/` separators.
+///
+/// The path must be relative and stay within the pack, so `..`, root and prefix components are
+/// rejected.
+fn pack_path(path: &Path) -> io::Result<String> {
+    let path_in_pack = normalize(path);
+    if path_in_pack.as_os_str().is_empty()
+        || path_in_pack
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid asset pack path: {}", path.display()),
+        ));
+    }
+    let components = path_in_pack
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("asset pack paths must be valid unicode: {}", path.display()),
+            )
+        })?;
+    Ok(components.join("/"))
+}
+
+/// Removes the `.` components of `path`.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+fn invalid_pack(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid asset pack: {message}"),
+    )
+}
+
+#[cfg(not(feature = "asset_pack_compression"))]
+fn compression_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "compressed asset packs require the `asset_pack_compression` feature",
+    )
+}
+
+fn read_u32(reader: &mut impl Read) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_lite::{AsyncReadExt, AsyncSeekExt};
+    use std::io::Cursor;
+
+    fn pack(compression: PackCompression) -> PackAssetIo {
+        let asset_dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(asset_dir.path().join("textures/ui")).unwrap();
+        fs::write(asset_dir.path().join("textures/grass.png"), [1; 64]).unwrap();
+        fs::write(asset_dir.path().join("textures/ui/button.png"), [2, 3, 4]).unwrap();
+
+        let mut builder = AssetPackBuilder::new().with_compression(compression);
+        builder.add_directory(asset_dir.path()).unwrap();
+        builder.add_file("scene.scn.ron", b"()".to_vec()).unwrap();
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        PackAssetIo::from_reader(Cursor::new(bytes)).unwrap()
+    }
+
+    fn load(pack: &PackAssetIo, path: &str) -> Result<Vec<u8>, AssetIoError> {
+        futures_lite::future::block_on(pack.load_path(Path::new(path)))
+    }
+
+    fn read_directory(pack: &PackAssetIo, path: &str) -> Vec<PathBuf> {
+        let mut entries = pack
+            .read_directory(Path::new(path))
+            .unwrap()
+            .collect::<Vec<_>>();
+        entries.sort();
+        entries
+    }
+
+    fn check_pack(pack: &PackAssetIo) {
+        assert_eq!(load(pack, "textures/grass.png").unwrap(), [1; 64]);
+        assert_eq!(load(pack, "./textures/ui/button.png").unwrap(), [2, 3, 4]);
+        assert_eq!(load(pack, "scene.scn.ron").unwrap(), b"()");
+        assert!(matches!(
+            load(pack, "textures/dirt.png"),
+            Err(AssetIoError::NotFound(_))
+        ));
+
+        let path = Path::new("textures/ui/button.png");
+        let mut reader = futures_lite::future::block_on(pack.read_path(path)).unwrap();
+        let mut bytes = Vec::new();
+        futures_lite::future::block_on(async {
+            reader.seek(SeekFrom::Start(1)).await.unwrap();
+            reader.read_to_end(&mut bytes).await.unwrap();
+            assert_eq!(reader.seek(SeekFrom::End(-3)).await.unwrap(), 0);
+            reader.read_exact(&mut bytes[..1]).await.unwrap();
+        });
+        assert_eq!(bytes, [2, 4]);
+
+        assert_eq!(
+            read_directory(pack, ""),
+            [PathBuf::from("scene.scn.ron"), PathBuf::from("textures")]
+        );
+        assert_eq!(
+            read_directory(pack, "textures"),
+            [
+                PathBuf::from("textures/grass.png"),
+                PathBuf::from("textures/ui")
+            ]
+        );
+        assert!(pack.read_directory(Path::new("models")).is_err());
+
+        assert!(pack.is_file(Path::new("textures/ui/button.png")));
+        assert!(pack.is_dir(Path::new("textures/ui")));
+        assert!(!pack.is_dir(Path::new("textures/grass.png")));
+        assert!(pack.get_metadata(Path::new("models")).is_err());
+    }
+
+    #[test]
+    fn test_pack_round_trip() {
+        check_pack(&pack(PackCompression::None));
+        assert!(PackAssetIo::from_reader(Cursor::new(b"not a pack".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_corrupted_pack() {
+        let mut builder = AssetPackBuilder::new();
+        builder.add_file("a.txt", vec![1; 16]).unwrap();
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        let index_offset = bytes.len() - 8 - (4 + 4 + 5 + 1 + 3 * 8);
+        let is_invalid_data = |bytes: Vec<u8>| {
+            PackAssetIo::from_reader(Cursor::new(bytes))
+                .err()
+                .map(|err| err.kind())
+                == Some(io::ErrorKind::InvalidData)
+        };
+
+        let mut corrupted = bytes.clone();
+        corrupted[index_offset..index_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(is_invalid_data(corrupted), "file count");
+        let mut corrupted = bytes.clone();
+        corrupted[index_offset + 4..index_offset + 8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(is_invalid_data(corrupted), "path length");
+        let stored_len = index_offset + 4 + 4 + 5 + 1 + 8;
+        let mut corrupted = bytes.clone();
+        corrupted[stored_len..stored_len + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(is_invalid_data(corrupted), "stored length");
+        let mut corrupted = bytes.clone();
+        let len = corrupted.len();
+        corrupted[len - 8..].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(is_invalid_data(corrupted), "index offset");
+        let mut corrupted = bytes.clone();
+        corrupted[index_offset + 8..index_offset + 13].copy_from_slice(b"../at");
+        assert!(is_invalid_data(corrupted), "path");
+        assert!(PackAssetIo::from_reader(Cursor::new(bytes)).is_ok());
+    }
+
+    #[test]
+    fn test_pack_paths_stay_in_pack() {
+        let mut builder = AssetPackBuilder::new();
+        assert!(builder.add_file("../a.txt", Vec::new()).is_err());
+        assert!(builder
+            .add_file("textures/../../a.txt", Vec::new())
+            .is_err());
+        assert!(builder.add_file("/a.txt", Vec::new()).is_err());
+        assert!(builder.add_file(".", Vec::new()).is_err());
+        builder.add_file("./textures/a.txt", Vec::new()).unwrap();
+    }
+
+    #[test]
+    fn test_duplicate_pack_paths() {
+        let mut builder = AssetPackBuilder::new();
+        builder.add_file("a.txt", vec![1]).unwrap();
+        builder.add_file("b.txt", vec![2]).unwrap();
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        let second_path = bytes.len() - 8 - (5 + 1 + 3 * 8);
+        bytes[second_path..second_path + 5].copy_from_slice(b"a.txt");
+        assert_eq!(
+            PackAssetIo::from_reader(Cursor::new(bytes))
+                .err()
+                .map(|err| err.kind()),
+            Some(io::ErrorKind::InvalidData)
+        );
+    }
+
+    #[cfg(feature = "asset_pack_compression")]
+    #[test]
+    fn test_compressed_pack_round_trip() {
+        check_pack(&pack(PackCompression::Deflate));
+    }
+
+    #[cfg(feature = "asset_pack_compression")]
+    #[test]
+    fn test_compressed_file_longer_than_its_length() {
+        let mut builder = AssetPackBuilder::new().with_compression(PackCompression::Deflate);
+        builder.add_file("a.txt", vec![1; 1024]).unwrap();
+        let mut bytes = Vec::new();
+        builder.write(&mut bytes).unwrap();
+        let len = bytes.len() - 8 - 8;
+        bytes[len..len + 8].copy_from_slice(&4u64.to_le_bytes());
+        let pack = PackAssetIo::from_reader(Cursor::new(bytes)).unwrap();
+        assert!(load(&pack, "a.txt").is_err());
+    }
+
+    #[cfg(not(feature = "asset_pack_compression"))]
+    #[test]
+    fn test_compression_unsupported() {
+        let mut builder = AssetPackBuilder::new().with_compression(PackCompression::Deflate);
+        builder.add_file("a.txt", vec![1; 64]).unwrap();
+        let err = builder.write(Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{path::split_source, AssetIo, AssetIoError, AssetReader, ChangeWatcher, Metadata};
+use anyhow::Result;
+use bevy_log::warn;
+use bevy_utils::{BoxedFuture, HashSet};
+use parking_lot::RwLock;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+struct NamedSource {
+    name: String,
+    asset_io: Arc<dyn AssetIo>,
+    overriding: bool,
+}
+
+/// The asset I/O sources of an [`AssetServer`](crate::AssetServer).
+///
+/// Assets are loaded from the default source, unless their path starts with the name of another
+/// source followed by `://`, such as `mods://weapons/sword.gltf`.
+///
+/// Sources added with [`add_overriding_source`](Self::add_overriding_source) can also override
+/// the assets of the default source: paths without a source are searched in the overriding
+/// sources first, from the most recently added one to the first one, then in the default source.
+/// For instance, with the sources of a content pack and of a mods directory added in this order,
+/// `weapons/sword.gltf` is loaded from the mods directory if it's there, else from the content
+/// pack if it's there, else from the default source. Directories without a source list the
+/// entries of all these sources.
+///
+/// Changes are watched by each source, see [`AssetIo::watch_for_changes`], including the sources
+/// added after watching started. A path without a source is only watched in the source it was
+/// loaded from: if an overriding source with a higher priority gains the file later, the asset is
+/// not reloaded from it until it is loaded again.
+pub struct AssetSources {
+    default: Arc<dyn AssetIo>,
+    named: RwLock<Vec<NamedSource>>,
+    /// The configuration to watch the added sources with, once watching started.
+    change_watcher: RwLock<Option<ChangeWatcher>>,
+}
+
+impl AssetSources {
+    /// Creates asset sources with only the default source.
+    pub fn new(default: Box<dyn AssetIo>) -> Self {
+        Self {
+            default: default.into(),
+            named: Default::default(),
+            change_watcher: Default::default(),
+        }
+    }
+
+    /// Returns the default source, which loads paths without a source.
+    pub fn default_source(&self) -> &dyn AssetIo {
+        &*self.default
+    }
+
+    /// Returns the source named `name`.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn AssetIo>> {
+        self.named
+            .read()
+            .iter()
+            .find(|source| source.name == name)
+            .map(|source| source.asset_io.clone())
+    }
+
+    /// Adds a source named `name`, which loads the paths prefixed with `name://`.
+    ///
+    /// Replaces any source with the same name.
+    pub fn add_source(&self, name: impl Into<String>, asset_io: Box<dyn AssetIo>) {
+        self.insert(name.into(), asset_io, false);
+    }
+
+    /// Adds a source named `name`, which loads the paths prefixed with `name://` and overrides the
+    /// assets of the default source and of the previously added overriding sources.
+    ///
+    /// Replaces any source with the same name, which then takes the highest priority.
+    pub fn add_overriding_source(&self, name: impl Into<String>, asset_io: Box<dyn AssetIo>) {
+        self.insert(name.into(), asset_io, true);
+    }
+
+    /// Removes the source named `name`, returning `true` if it existed.
+    ///
+    /// Loaded assets aren't unloaded, but they aren't reloaded from this source anymore.
+    pub fn remove_source(&self, name: &str) -> bool {
+        let mut named = self.named.write();
+        let len = named.len();
+        named.retain(|source| source.name != name);
+        named.len() != len
+    }
+
+    /// Returns the names of the overriding sources, from the highest priority to the lowest.
+    pub fn overriding_sources(&self) -> Vec<String> {
+        self.named
+            .read()
+            .iter()
+            .rev()
+            .filter(|source| source.overriding)
+            .map(|source| source.name.clone())
+            .collect()
+    }
+
+    fn insert(&self, name: String, asset_io: Box<dyn AssetIo>, overriding: bool) {
+        if let Some(configuration) = &*self.change_watcher.read() {
+            if let Err(err) = asset_io.watch_for_changes(configuration) {
+                warn!("failed to watch the asset source `{name}` for changes: {err}");
+            }
+        }
+        let mut named = self.named.write();
+        named.retain(|source| source.name != name);
+        named.push(NamedSource {
+            name,
+            asset_io: asset_io.into(),
+            overriding,
+        });
+    }
+
+    /// Returns the default source followed by the named sources.
+    pub(crate) fn asset_ios(&self) -> Vec<Arc<dyn AssetIo>> {
+        std::iter::once(self.default.clone())
+            .chain(
+                self.named
+                    .read()
+                    .iter()
+                    .map(|source| source.asset_io.clone()),
+            )
+            .collect()
+    }
+
+    /// Returns the sources where `path` is searched, by priority, and the path within them.
+    fn resolve<'a>(
+        &self,
+        path: &'a Path,
+    ) -> Result<(Vec<Arc<dyn AssetIo>>, &'a Path), AssetIoError> {
+        match split_source(path) {
+            (Some(name), path_in_source) => {
+                let asset_io = self
+                    .get(name)
+                    .ok_or_else(|| AssetIoError::MissingSource(name.to_string()))?;
+                Ok((vec![asset_io], path_in_source))
+            }
+            (None, path) => {
+                let mut asset_ios = self
+                    .named
+                    .read()
+                    .iter()
+                    .rev()
+                    .filter(|source| source.overriding)
+                    .map(|source| source.asset_io.clone())
+                    .collect::<Vec<_>>();
+                asset_ios.push(self.default.clone());
+                Ok((asset_ios, path))
+            }
+        }
+    }
+}
+
+impl AssetIo for AssetSources {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            let (asset_ios, path_in_source) = self.resolve(path)?;
+            let last = asset_ios.len() - 1;
+            for (index, asset_io) in asset_ios.into_iter().enumerate() {
+                match asset_io.load_path(path_in_source).await {
+                    Err(AssetIoError::NotFound(_)) if index != last => continue,
+                    result => return result,
+                }
+            }
+            unreachable!("the default source is always searched last")
+        })
+    }
+
+    fn read_path<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<dyn AssetReader>, AssetIoError>> {
+        Box::pin(async move {
+            let (asset_ios, path_in_source) = self.resolve(path)?;
+            let last = asset_ios.len() - 1;
+            for (index, asset_io) in asset_ios.into_iter().enumerate() {
+                match asset_io.read_path(path_in_source).await {
+                    Err(AssetIoError::NotFound(_)) if index != last => continue,
+                    result => return result,
+                }
+            }
+            unreachable!("the default source is always searched last")
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let (asset_ios, path_in_source) = self.resolve(path)?;
+        if let (Some(name), _) = split_source(path) {
+            let prefix = PathBuf::from(format!("{name}://"));
+            let entries = asset_ios[0].read_directory(path_in_source)?;
+            return Ok(Box::new(entries.map(move |entry| prefix.join(entry))));
+        }
+
+        // list the entries of every source with this directory, without duplicates
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        let mut found = false;
+        for asset_io in &asset_ios {
+            if asset_io.is_dir(path_in_source) {
+                found = true;
+                for entry in asset_io.read_directory(path_in_source)? {
+                    if seen.insert(entry.clone()) {
+                        entries.push(entry);
+                    }
+                }
+            }
+        }
+        if !found {
+            return self.default.read_directory(path_in_source);
+        }
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        let (asset_ios, path_in_source) = self.resolve(path)?;
+        let last = asset_ios.len() - 1;
+        for (index, asset_io) in asset_ios.iter().enumerate() {
+            match asset_io.get_metadata(path_in_source) {
+                Err(AssetIoError::NotFound(_)) if index != last => continue,
+                result => return result,
+            }
+        }
+        unreachable!("the default source is always searched last")
+    }
+
+    /// Watches `to_watch` in the source it is loaded from.
+    ///
+    /// The sources with a higher priority, where the path doesn't exist yet, are not watched.
+    fn watch_path_for_changes(
+        &self,
+        to_watch: &Path,
+        to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        let (asset_ios, path_in_source) = self.resolve(to_watch)?;
+        // the source reloads the full path, including the `name://` prefix
+        let to_reload = to_reload.unwrap_or_else(|| to_watch.to_owned());
+        let asset_io = asset_ios
+            .iter()
+            .find(|asset_io| asset_io.get_metadata(path_in_source).is_ok())
+            .unwrap_or(&asset_ios[asset_ios.len() - 1]);
+        asset_io.watch_path_for_changes(path_in_source, Some(to_reload))
+    }
+
+    fn watch_for_changes(&self, configuration: &ChangeWatcher) -> Result<(), AssetIoError> {
+        *self.change_watcher.write() = Some(configuration.clone());
+        for asset_io in self.asset_ios() {
+            asset_io.watch_for_changes(configuration)?;
+        }
+        Ok(())
+    }
+}
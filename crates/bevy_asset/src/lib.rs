@@ -25,7 +25,9 @@ mod handle;
 mod info;
 mod io;
 mod loader;
+mod meta;
 mod path;
+mod processor;
 mod reflect;
 
 /// The `bevy_asset` prelude.
@@ -44,7 +46,9 @@ pub use handle::*;
 pub use info::*;
 pub use io::*;
 pub use loader::*;
+pub use meta::*;
 pub use path::*;
+pub use processor::*;
 pub use reflect::*;
 
 use bevy_app::{prelude::*, MainScheduleOrder};
@@ -93,6 +97,12 @@ pub struct AssetPlugin {
     /// Whether to watch for changes in asset files. Requires the `filesystem_watcher` feature,
     /// and cannot be supported on the wasm32 arch nor android os.
     pub watch_for_changes: Option<ChangeWatcher>,
+    /// The folder where processed assets are cached, relative to the executable.
+    ///
+    /// When set, assets with an [`AssetProcessor`] are processed once and loaded from their cached
+    /// artifact until their source changes, see [`ImportedAssets`].
+    /// Cannot be supported on the wasm32 arch nor android os.
+    pub imported_asset_folder: Option<String>,
 }
 
 impl Default for AssetPlugin {
@@ -100,6 +110,7 @@ impl Default for AssetPlugin {
         Self {
             asset_folder: "assets".to_string(),
             watch_for_changes: None,
+            imported_asset_folder: None,
         }
     }
 }
@@ -126,6 +137,17 @@ impl Plugin for AssetPlugin {
         if !app.world.contains_resource::<AssetServer>() {
             let source = self.create_platform_default_asset_io();
             let asset_server = AssetServer::with_boxed_io(source);
+            if let Some(imported_asset_folder) = &self.imported_asset_folder {
+                #[cfg(all(not(target_arch = "wasm32"), not(target_os = "android")))]
+                asset_server.set_imported_assets(ImportedAssets::new(
+                    FileAssetIo::get_base_path().join(imported_asset_folder),
+                ));
+                #[cfg(any(target_arch = "wasm32", target_os = "android"))]
+                bevy_log::warn!(
+                    "Asset processing is not supported on wasm32 / android targets, \
+                    `{imported_asset_folder}` will not be used"
+                );
+            }
             app.insert_resource(asset_server);
         }
 
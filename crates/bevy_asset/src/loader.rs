@@ -1,6 +1,8 @@
 use crate::{
-    path::AssetPath, AssetIo, AssetIoError, AssetMeta, AssetServer, Assets, Handle, HandleId,
-    HandleUntyped, RefChangeChannel,
+    meta::{read_settings, SettingsOverride},
+    path::AssetPath,
+    AssetIo, AssetIoError, AssetMeta, AssetReader, AssetServer, Assets, Handle, HandleId,
+    HandleUntyped, LoaderSettingsError, RefChangeChannel,
 };
 use anyhow::Error;
 use anyhow::Result;
@@ -10,12 +12,18 @@ use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
+use futures_lite::AsyncReadExt;
+use serde::de::DeserializeOwned;
 use std::path::Path;
 
 /// A loader for an asset source.
 ///
 /// Types implementing this trait are used by the [`AssetServer`] to load assets
 /// into their respective asset storages.
+///
+/// A loader can be configured per asset with settings read by [`LoadContext::settings`], which come
+/// from the [meta file](crate::LoaderMeta) of the asset and from
+/// [`AssetServer::load_with_settings`].
 pub trait AssetLoader: Send + Sync + 'static {
     /// Processes the asset in an asynchronous closure.
     fn load<'a>(
@@ -24,8 +32,32 @@ pub trait AssetLoader: Send + Sync + 'static {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), Error>>;
 
+    /// Processes the asset from a reader of its bytes, in an asynchronous closure.
+    ///
+    /// This is the method called by the [`AssetServer`]. The default implementation reads all the
+    /// bytes and calls [`load`](Self::load), so loaders of large assets can override it to stream
+    /// the asset, or to [seek](futures_lite::AsyncSeekExt::seek) and read only the ranges they
+    /// need. Their `load` can then call this method with a
+    /// [`Cursor`](futures_lite::io::Cursor) over the bytes.
+    fn load_from_reader<'a>(
+        &'a self,
+        reader: &'a mut dyn AssetReader,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            self.load(&bytes, load_context).await
+        })
+    }
+
     /// Returns a list of extensions supported by this asset loader, without the preceding dot.
     fn extensions(&self) -> &[&str];
+
+    /// Returns the name of this asset loader, used by [meta files](crate::LoaderMeta) to select it.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
 }
 
 /// An essential piece of data of an application.
@@ -115,8 +147,8 @@ impl<T: Asset> From<LoadedAsset<T>> for BoxedLoadedAsset {
 
 /// An asynchronous context where an [`Asset`] is processed.
 ///
-/// The load context is created by the [`AssetServer`] to process an asset source after loading its
-/// contents into memory. It is then passed to the appropriate [`AssetLoader`] based on the file
+/// The load context is created by the [`AssetServer`] to process an asset source after opening a
+/// reader of its contents. It is then passed to the appropriate [`AssetLoader`] based on the file
 /// extension of the asset's path.
 ///
 /// An asset source can define one or more assets from a single source path. The main asset is set
@@ -128,6 +160,8 @@ pub struct LoadContext<'a> {
     pub(crate) labeled_assets: HashMap<Option<String>, BoxedLoadedAsset>,
     pub(crate) path: &'a Path,
     pub(crate) version: usize,
+    pub(crate) meta: Option<&'a [u8]>,
+    pub(crate) settings_override: Option<SettingsOverride>,
 }
 
 impl<'a> LoadContext<'a> {
@@ -143,6 +177,8 @@ impl<'a> LoadContext<'a> {
             labeled_assets: Default::default(),
             version,
             path,
+            meta: None,
+            settings_override: None,
         }
     }
 
@@ -151,6 +187,18 @@ impl<'a> LoadContext<'a> {
         self.path
     }
 
+    /// Reads the settings of the loader for this asset.
+    ///
+    /// The settings are deserialized from the [meta file](crate::LoaderMeta) of the asset, or
+    /// default to `S::default()`, then modified by the function given to
+    /// [`AssetServer::load_with_settings`] if any.
+    pub fn settings<S>(&self) -> Result<S, LoaderSettingsError>
+    where
+        S: DeserializeOwned + Default + 'static,
+    {
+        read_settings(self.path, self.meta, self.settings_override.as_ref())
+    }
+
     /// Returns `true` if the load context contains an asset with the specified label.
     pub fn has_labeled_asset(&self, label: &str) -> bool {
         self.labeled_assets.contains_key(&Some(label.to_string()))
@@ -187,6 +235,20 @@ impl<'a> LoadContext<'a> {
         self.asset_io.load_path(path.as_ref()).await
     }
 
+    /// Opens a reader of the contents of the asset at the specified path through the
+    /// [`AssetIo`] associated with this context, to read only some ranges of it.
+    ///
+    /// Changes to the asset reload the asset of this context, like
+    /// [`read_asset_bytes`](Self::read_asset_bytes).
+    pub async fn read_asset<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Box<dyn AssetReader>, AssetIoError> {
+        self.asset_io
+            .watch_path_for_changes(path.as_ref(), Some(self.path.to_owned()))?;
+        self.asset_io.read_path(path.as_ref()).await
+    }
+
     /// Generates metadata for the assets managed by this load context.
     pub fn get_asset_metas(&self) -> Vec<AssetMeta> {
         let mut asset_metas = Vec::new();
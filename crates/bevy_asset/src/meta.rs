@@ -0,0 +1,99 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    any::Any,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// The extension of the meta files of assets.
+pub const META_EXTENSION: &str = "meta";
+
+/// Returns the path of the meta file of the asset at `path`, which is `path` with the
+/// [`META_EXTENSION`] appended.
+pub fn meta_path(path: &Path) -> PathBuf {
+    let mut meta_path = path.as_os_str().to_owned();
+    meta_path.push(".");
+    meta_path.push(META_EXTENSION);
+    PathBuf::from(meta_path)
+}
+
+/// The contents of the meta file of an asset, which can select its loader and carry settings for
+/// this loader.
+///
+/// Meta files are written in RON next to their asset, at the [`meta_path`] of the asset.
+/// For instance, `textures/grass.png.meta` could contain:
+///
+/// ```ron
+/// (
+///     loader: Some("ImageTextureLoader"),
+///     settings: Some((
+///         is_srgb: false,
+///     )),
+/// )
+/// ```
+///
+/// The [`loader`](Self::loader) is matched against the [names](crate::AssetLoader::name) of the
+/// registered loaders, or their short names without module path. The settings are read by the
+/// loader with [`LoadContext::settings`](crate::LoadContext::settings).
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoaderMeta<S> {
+    /// The name of the loader of the asset, instead of the loader of its extension.
+    #[serde(default)]
+    pub loader: Option<String>,
+    /// The settings of the loader.
+    #[serde(default)]
+    pub settings: Option<S>,
+}
+
+/// Errors that occur while reading the settings of an [`AssetLoader`](crate::AssetLoader).
+#[derive(Error, Debug)]
+pub enum LoaderSettingsError {
+    /// The meta file of the asset couldn't be deserialized with the settings of the loader.
+    #[error("invalid meta file `{path}`: {error}")]
+    InvalidMeta {
+        /// The path of the meta file.
+        path: PathBuf,
+        /// The deserialization error.
+        error: ron::error::SpannedError,
+    },
+
+    /// The settings given to [`AssetServer::load_with_settings`](crate::AssetServer::load_with_settings)
+    /// aren't the settings of the loader.
+    #[error("the settings given to `load_with_settings` are not `{0}`")]
+    MismatchedSettings(&'static str),
+}
+
+/// Settings given in code for an asset, applied after the settings of its meta file.
+///
+/// Returns `false` if the settings aren't of the expected type.
+pub(crate) type SettingsOverride = Arc<dyn Fn(&mut dyn Any) -> bool + Send + Sync>;
+
+/// Reads the settings of type `S` from the meta file at `path`, then applies `settings_override`.
+pub(crate) fn read_settings<S>(
+    path: &Path,
+    meta: Option<&[u8]>,
+    settings_override: Option<&SettingsOverride>,
+) -> Result<S, LoaderSettingsError>
+where
+    S: DeserializeOwned + Default + 'static,
+{
+    let mut settings = match meta {
+        Some(meta) => ron::de::from_bytes::<LoaderMeta<S>>(meta)
+            .map_err(|error| LoaderSettingsError::InvalidMeta {
+                path: meta_path(path),
+                error,
+            })?
+            .settings
+            .unwrap_or_default(),
+        None => S::default(),
+    };
+    if let Some(settings_override) = settings_override {
+        if !settings_override(&mut settings) {
+            return Err(LoaderSettingsError::MismatchedSettings(
+                std::any::type_name::<S>(),
+            ));
+        }
+    }
+    Ok(settings)
+}
@@ -8,6 +8,10 @@ use std::{
 };
 
 /// Represents a path to an asset in the file system.
+///
+/// The path can start with the name of an [asset source](crate::AssetSources) followed by `://`,
+/// such as `mods://weapons/sword.gltf`, to load the asset from this source instead of the default
+/// sources.
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Serialize, Deserialize, Reflect)]
 #[reflect(Debug, PartialEq, Hash, Serialize, Deserialize)]
 pub struct AssetPath<'a> {
@@ -52,6 +56,21 @@ impl<'a> AssetPath<'a> {
         &self.path
     }
 
+    /// Gets the name of the [asset source](crate::AssetSources) of the asset, given by the
+    /// `name://` prefix of its path.
+    ///
+    /// Returns `None` if the asset is loaded from the default sources.
+    #[inline]
+    pub fn source(&self) -> Option<&str> {
+        split_source(&self.path).0
+    }
+
+    /// Gets the path to the asset within its [source](Self::source), without the `name://` prefix.
+    #[inline]
+    pub fn path_in_source(&self) -> &Path {
+        split_source(&self.path).1
+    }
+
     /// Converts the borrowed path data to owned.
     #[inline]
     pub fn to_owned(&self) -> AssetPath<'static> {
@@ -126,6 +145,25 @@ impl AssetPathId {
     }
 }
 
+/// Splits the `name://` prefix of an [`AssetPath`] from the path within the named source.
+///
+/// The prefix is parsed from the string form of the path rather than from its components, which
+/// would read a one-letter source as a Windows drive and accept a single slash after the colon.
+pub(crate) fn split_source(path: &Path) -> (Option<&str>, &Path) {
+    let Some((source, path_in_source)) = path.to_str().and_then(|path| path.split_once("://"))
+    else {
+        return (None, path);
+    };
+    if source.is_empty() || source.contains([':', '/', '\\']) {
+        return (None, path);
+    }
+    // `name:///path` is read as `name://path`, not as an absolute path
+    (
+        Some(source),
+        Path::new(path_in_source.trim_start_matches('/')),
+    )
+}
+
 /// this hasher provides consistent results across runs
 pub(crate) fn get_hasher() -> AHasher {
     RandomState::with_seeds(42, 23, 13, 8).build_hasher()
@@ -0,0 +1,489 @@
+use crate::{AssetIo, AssetIoError};
+use anyhow::Error;
+use bevy_utils::{BoxedFuture, HashMap};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use thiserror::Error;
+
+/// The extension of the records written next to processed artifacts.
+const RECORD_EXTENSION: &str = "record";
+/// How long ago a source must have been modified for its [`SourceStamp`] to be kept. The
+/// modification time of a file is coarse, so a source modified again shortly after it was read
+/// may keep the same stamp.
+const STAMP_MIN_AGE: Duration = Duration::from_secs(2);
+
+/// A processor for an asset source.
+///
+/// Processors transform source assets into artifacts which are faster to load, for example by
+/// compressing textures or baking meshes. When [`ImportedAssets`] are set on the
+/// [`AssetServer`](crate::AssetServer), sources with a processor are processed once, and the
+/// artifact cached in the imported folder is loaded instead of the source until the source, one of
+/// its [dependencies](ProcessContext::read_asset_bytes) or the processor [version](Self::version)
+/// changes.
+///
+/// Artifacts are loaded by the [`AssetLoader`](crate::AssetLoader) of their extension, see
+/// [`artifact_extension`](Self::artifact_extension).
+pub trait AssetProcessor: Send + Sync + 'static {
+    /// Processes the bytes of the source asset into the bytes of its artifact.
+    fn process<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        process_context: &'a mut ProcessContext,
+    ) -> BoxedFuture<'a, Result<Vec<u8>, Error>>;
+
+    /// Returns a list of extensions of the source assets processed by this processor, without the
+    /// preceding dot.
+    fn extensions(&self) -> &[&str];
+
+    /// Returns the extension appended to the source path to get the path of the artifact, without
+    /// the preceding dot.
+    ///
+    /// For instance, a processor compressing `textures/grass.png` into a KTX2 texture would return
+    /// `Some("ktx2")`, and the artifact `textures/grass.png.ktx2` would be loaded by the loader of
+    /// the `png.ktx2` or `ktx2` extension.
+    /// Returns `None` by default, keeping the path of the source.
+    fn artifact_extension(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the version of this processor.
+    ///
+    /// Artifacts produced by another version of the processor are processed again, so the version
+    /// should be increased when the output of the processor changes.
+    fn version(&self) -> u32 {
+        0
+    }
+
+    /// Returns the name of this processor, used to detect artifacts produced by another processor.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// A hash of the bytes of an asset, see [`hash_asset_bytes`].
+pub type AssetHash = u64;
+
+/// Hashes the bytes of an asset.
+///
+/// This uses 64 bit FNV-1a, which is stable across platforms and Rust versions so that
+/// [`ProcessedRecord`]s stay valid.
+pub fn hash_asset_bytes(bytes: &[u8]) -> AssetHash {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// The length and modification time of a source when it was read.
+///
+/// A source is only read and hashed again to check whether an artifact is up to date when its
+/// stamp changed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceStamp {
+    /// The length of the source, in bytes.
+    pub len: u64,
+    /// The last modification time of the source.
+    pub modified: SystemTime,
+}
+
+impl SourceStamp {
+    /// Returns the stamp of the source at `path`, or `None` if `asset_io` doesn't report its length
+    /// and modification time, or if it was modified too recently to be trusted.
+    fn read(path: &Path, asset_io: &dyn AssetIo) -> Option<Self> {
+        let metadata = asset_io.get_metadata(path).ok()?;
+        let modified = metadata.modified()?;
+        let age = SystemTime::now().duration_since(modified).ok()?;
+        (age >= STAMP_MIN_AGE).then_some(Self {
+            len: metadata.size()?,
+            modified,
+        })
+    }
+}
+
+/// A source read by an [`AssetProcessor`] in addition to the processed source.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessDependency {
+    /// The path of the dependency.
+    pub path: PathBuf,
+    /// The hash of the bytes of the dependency when it was read.
+    pub hash: AssetHash,
+    /// The stamp of the dependency when it was read.
+    pub stamp: Option<SourceStamp>,
+}
+
+/// The record written next to a processed artifact, to detect when it's out of date.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessedRecord {
+    /// The [name](AssetProcessor::name) of the processor which produced the artifact.
+    pub processor: String,
+    /// The [version](AssetProcessor::version) of the processor which produced the artifact.
+    pub processor_version: u32,
+    /// The hash of the bytes of the source.
+    pub source_hash: AssetHash,
+    /// The stamp of the source when it was read.
+    pub source_stamp: Option<SourceStamp>,
+    /// The other sources read by the processor.
+    pub dependencies: Vec<ProcessDependency>,
+    /// The path of the artifact, relative to the imported folder.
+    pub artifact: PathBuf,
+}
+
+/// Errors that occur while processing assets.
+#[derive(Error, Debug)]
+pub enum ProcessError {
+    /// Encountered an I/O error while reading a source asset.
+    #[error("encountered an error while reading a source asset: {0}")]
+    AssetIoError(#[from] AssetIoError),
+
+    /// Encountered an I/O error while accessing the imported folder.
+    #[error("encountered an error while accessing the imported folder: {0}")]
+    ImportedIoError(#[from] io::Error),
+
+    /// Encountered an error while writing a processed record.
+    #[error("encountered an error while writing a processed record: {0}")]
+    RecordError(#[from] ron::Error),
+
+    /// The processor failed.
+    #[error("encountered an error while processing an asset: {0}")]
+    ProcessorError(Error),
+
+    /// The artifact of a source would be written outside of the imported folder.
+    #[error("the artifact path {0:?} is outside of the imported folder")]
+    InvalidArtifactPath(PathBuf),
+}
+
+/// An asynchronous context where a source asset is processed.
+///
+/// The process context is created by the [`AssetServer`](crate::AssetServer) and passed to the
+/// [`AssetProcessor`] of the source.
+pub struct ProcessContext<'a> {
+    pub(crate) asset_io: &'a dyn AssetIo,
+    pub(crate) path: &'a Path,
+    pub(crate) dependencies: Vec<ProcessDependency>,
+}
+
+impl<'a> ProcessContext<'a> {
+    pub(crate) fn new(path: &'a Path, asset_io: &'a dyn AssetIo) -> Self {
+        Self {
+            asset_io,
+            path,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Gets the path of the processed source.
+    pub fn path(&self) -> &Path {
+        self.path
+    }
+
+    /// Reads the contents of the source at the specified path through the [`AssetIo`] associated
+    /// with this context.
+    ///
+    /// The source is recorded as a dependency of the artifact, which is processed again when the
+    /// source changes.
+    pub async fn read_asset_bytes<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<Vec<u8>, AssetIoError> {
+        let path = path.as_ref();
+        self.asset_io
+            .watch_path_for_changes(path, Some(self.path.to_owned()))?;
+        let (stamp, bytes) = read_source(path, self.asset_io).await?;
+        self.dependencies.push(ProcessDependency {
+            path: path.to_owned(),
+            hash: hash_asset_bytes(&bytes),
+            stamp,
+        });
+        Ok(bytes)
+    }
+
+    /// Gets the asset I/O associated with this process context.
+    pub fn asset_io(&self) -> &dyn AssetIo {
+        self.asset_io
+    }
+}
+
+/// Whether a [`ProcessedAsset`] was processed or loaded from the imported folder.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProcessStatus {
+    /// The cached artifact was up to date.
+    UpToDate,
+    /// The source was processed, and the artifact written to the imported folder.
+    Processed,
+}
+
+/// The artifact of a processed source asset.
+#[derive(Debug)]
+pub struct ProcessedAsset {
+    /// The path of the artifact, relative to the imported folder.
+    pub artifact_path: PathBuf,
+    /// The bytes of the artifact.
+    pub bytes: Vec<u8>,
+    /// Whether the source was processed or the cached artifact was up to date.
+    pub status: ProcessStatus,
+}
+
+/// A folder on the local filesystem where processed artifacts are cached.
+///
+/// The artifact of a source is written at the same path relative to this folder, with the
+/// [artifact extension](AssetProcessor::artifact_extension) of its processor, next to a
+/// [`ProcessedRecord`] with the `.record` extension. The sources of a cached artifact are only read
+/// to check whether it's up to date when their [`SourceStamp`] changed.
+///
+/// The artifact is loaded without its source if the source doesn't exist, so the imported folder
+/// can be shipped without the sources. A source is only processed by one task at a time.
+///
+/// The imported folder is accessed with blocking calls, like the files read by
+/// [`FileAssetIo`](crate::FileAssetIo), so they block the thread of the task processing the source.
+///
+/// Processing isn't supported on the `wasm32` and `android` targets, which can't write to the
+/// filesystem.
+#[derive(Debug, Clone)]
+pub struct ImportedAssets {
+    root_path: PathBuf,
+    /// The locks of the sources being processed.
+    processing: Arc<Mutex<HashMap<PathBuf, Arc<async_lock::Mutex<()>>>>>,
+}
+
+impl ImportedAssets {
+    /// Creates imported assets cached in the folder at `root_path`.
+    pub fn new<P: Into<PathBuf>>(root_path: P) -> Self {
+        Self {
+            root_path: root_path.into(),
+            processing: Default::default(),
+        }
+    }
+
+    /// Returns the folder where artifacts are cached.
+    pub fn root_path(&self) -> &Path {
+        &self.root_path
+    }
+
+    /// Reads the record of the artifact of the source at `path`, if it has been processed.
+    pub async fn read_record(&self, path: &Path) -> Option<ProcessedRecord> {
+        let record_path = self.imported_path(&record_path(path))?;
+        let record = fs::read_to_string(record_path).ok()?;
+        ron::from_str(&record).ok()
+    }
+
+    /// Returns the artifact of the source at `path`, processing it with `processor` unless the
+    /// cached artifact is up to date.
+    pub(crate) async fn process(
+        &self,
+        path: &Path,
+        processor: &dyn AssetProcessor,
+        asset_io: &dyn AssetIo,
+    ) -> Result<ProcessedAsset, ProcessError> {
+        let lock = self
+            .processing
+            .lock()
+            .entry(path.to_owned())
+            .or_default()
+            .clone();
+        let result = {
+            let _guard = lock.lock().await;
+            self.process_exclusive(path, processor, asset_io).await
+        };
+
+        // forget the lock once no other task waits for it
+        let mut processing = self.processing.lock();
+        drop(lock);
+        if processing
+            .get(path)
+            .is_some_and(|lock| Arc::strong_count(lock) == 1)
+        {
+            processing.remove(path);
+        }
+        result
+    }
+
+    async fn process_exclusive(
+        &self,
+        path: &Path,
+        processor: &dyn AssetProcessor,
+        asset_io: &dyn AssetIo,
+    ) -> Result<ProcessedAsset, ProcessError> {
+        let source_exists = match asset_io.get_metadata(path) {
+            Ok(_) => true,
+            Err(AssetIoError::NotFound(_)) => false,
+            Err(err) => return Err(err.into()),
+        };
+
+        // the source is only read if its stamp changed since it was processed
+        let mut source = None;
+        if let Some(mut record) = self.read_record(path).await {
+            let previous_record = record.clone();
+            if self
+                .is_up_to_date(
+                    &mut record,
+                    path,
+                    source_exists,
+                    &mut source,
+                    processor,
+                    asset_io,
+                )
+                .await
+            {
+                if let Some(artifact_path) = self.imported_path(&record.artifact) {
+                    if let Ok(bytes) = fs::read(artifact_path) {
+                        if source_exists {
+                            for dependency in &record.dependencies {
+                                asset_io.watch_path_for_changes(
+                                    &dependency.path,
+                                    Some(path.to_owned()),
+                                )?;
+                            }
+                        }
+                        // sources touched without being changed aren't hashed again next time
+                        if record != previous_record {
+                            self.write_record(path, &record).await?;
+                        }
+                        return Ok(ProcessedAsset {
+                            artifact_path: record.artifact,
+                            bytes,
+                            status: ProcessStatus::UpToDate,
+                        });
+                    }
+                }
+            }
+        }
+
+        let (source_stamp, source) = match source {
+            Some(source) => source,
+            None => read_source(path, asset_io).await?,
+        };
+        let mut process_context = ProcessContext::new(path, asset_io);
+        let bytes = processor
+            .process(&source, &mut process_context)
+            .await
+            .map_err(ProcessError::ProcessorError)?;
+
+        let artifact_path = match processor.artifact_extension() {
+            Some(extension) => {
+                let mut artifact_path = path.as_os_str().to_owned();
+                artifact_path.push(".");
+                artifact_path.push(extension);
+                PathBuf::from(artifact_path)
+            }
+            None => path.to_owned(),
+        };
+        let record = ProcessedRecord {
+            processor: processor.name().to_string(),
+            processor_version: processor.version(),
+            source_hash: hash_asset_bytes(&source),
+            source_stamp,
+            dependencies: process_context.dependencies,
+            artifact: artifact_path.clone(),
+        };
+
+        let Some(full_artifact_path) = self.imported_path(&artifact_path) else {
+            return Err(ProcessError::InvalidArtifactPath(artifact_path));
+        };
+        if let Some(parent) = full_artifact_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&full_artifact_path, &bytes)?;
+        self.write_record(path, &record).await?;
+
+        Ok(ProcessedAsset {
+            artifact_path,
+            bytes,
+            status: ProcessStatus::Processed,
+        })
+    }
+
+    /// Returns `true` if the artifact of `record` was produced by `processor` from the source at
+    /// `path` and its dependencies as they are now.
+    ///
+    /// Sources are only read and hashed when their stamp changed. The read source is kept in
+    /// `source`, and the stamps of the sources which are unchanged are updated in `record`.
+    /// A missing source and its dependencies aren't checked, only the processor is.
+    async fn is_up_to_date(
+        &self,
+        record: &mut ProcessedRecord,
+        path: &Path,
+        source_exists: bool,
+        source: &mut Option<(Option<SourceStamp>, Vec<u8>)>,
+        processor: &dyn AssetProcessor,
+        asset_io: &dyn AssetIo,
+    ) -> bool {
+        if record.processor != processor.name() || record.processor_version != processor.version() {
+            return false;
+        }
+        if !source_exists {
+            return true;
+        }
+        if !is_unchanged(path, record.source_stamp, asset_io) {
+            let Ok((stamp, bytes)) = read_source(path, asset_io).await else {
+                return false;
+            };
+            let unchanged = hash_asset_bytes(&bytes) == record.source_hash;
+            *source = Some((stamp, bytes));
+            if !unchanged {
+                return false;
+            }
+            record.source_stamp = stamp;
+        }
+        for dependency in &mut record.dependencies {
+            if is_unchanged(&dependency.path, dependency.stamp, asset_io) {
+                continue;
+            }
+            match read_source(&dependency.path, asset_io).await {
+                Ok((stamp, bytes)) if hash_asset_bytes(&bytes) == dependency.hash => {
+                    dependency.stamp = stamp;
+                }
+                _ => return false,
+            }
+        }
+        true
+    }
+
+    async fn write_record(
+        &self,
+        path: &Path,
+        record: &ProcessedRecord,
+    ) -> Result<(), ProcessError> {
+        let Some(full_record_path) = self.imported_path(&record_path(path)) else {
+            return Err(ProcessError::InvalidArtifactPath(record.artifact.clone()));
+        };
+        let record = ron::ser::to_string_pretty(record, Default::default())?;
+        fs::write(full_record_path, record)?;
+        Ok(())
+    }
+
+    /// Returns the path of `path` within the imported folder, or `None` if it would be outside of
+    /// the imported folder.
+    fn imported_path(&self, path: &Path) -> Option<PathBuf> {
+        path.components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+            .then(|| self.root_path.join(path))
+    }
+}
+
+/// Reads the source at `path` along with its stamp, which is read first so that a source changed
+/// while it's read is read again later.
+async fn read_source(
+    path: &Path,
+    asset_io: &dyn AssetIo,
+) -> Result<(Option<SourceStamp>, Vec<u8>), AssetIoError> {
+    let stamp = SourceStamp::read(path, asset_io);
+    let bytes = asset_io.load_path(path).await?;
+    Ok((stamp, bytes))
+}
+
+/// Returns `true` if the source at `path` still has the known `stamp`.
+fn is_unchanged(path: &Path, stamp: Option<SourceStamp>, asset_io: &dyn AssetIo) -> bool {
+    stamp.is_some() && SourceStamp::read(path, asset_io) == stamp
+}
+
+fn record_path(path: &Path) -> PathBuf {
+    let mut record_path = path.as_os_str().to_owned();
+    record_path.push(".");
+    record_path.push(RECORD_EXTENSION);
+    PathBuf::from(record_path)
+}
@@ -332,6 +332,10 @@ pub fn derive_world_query_impl(input: TokenStream) -> TokenStream {
                     true #(&& <#field_types>::filter_fetch(&mut _fetch.#named_field_idents, _entity, _table_row))*
                 }
 
+                fn set_access(_state: &mut Self::State, _access: &#path::query::FilteredAccess<#path::component::ComponentId>) {
+                    #( <#field_types>::set_access(&mut _state.#named_field_idents, _access); )*
+                }
+
                 fn update_component_access(state: &Self::State, _access: &mut #path::query::FilteredAccess<#path::component::ComponentId>) {
                     #( <#field_types>::update_component_access(&state.#named_field_idents, _access); )*
                 }
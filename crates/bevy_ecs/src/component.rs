@@ -3,6 +3,7 @@
 use crate::{
     self as bevy_ecs,
     change_detection::MAX_CHANGE_AGE,
+    entity::Entity,
     storage::{SparseSetIndex, Storages},
     system::{Local, Resource, SystemParam},
     world::{FromWorld, World},
@@ -144,12 +145,42 @@ use std::{
 /// }
 /// ```
 ///
+/// # Lifecycle hooks
+///
+/// Components can run a [`ComponentHook`] each time they are added to, inserted into, or removed
+/// from an entity. Hooks are configured through the derive attribute, by implementing
+/// [`Component::register_component_hooks`], or at runtime with
+/// [`World::register_component_hooks`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::component::ComponentId;
+/// #[derive(Component)]
+/// #[component(on_add = count_spawn)]
+/// struct Enemy;
+///
+/// #[derive(Resource, Default)]
+/// struct EnemiesSpawned(u32);
+///
+/// fn count_spawn(world: &mut World, _entity: Entity, _id: ComponentId) {
+///     world.resource_mut::<EnemiesSpawned>().0 += 1;
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<EnemiesSpawned>();
+/// world.spawn(Enemy);
+/// assert_eq!(world.resource::<EnemiesSpawned>().0, 1);
+/// ```
+///
 /// [`SyncCell`]: bevy_utils::synccell::SyncCell
 /// [`Exclusive`]: https://doc.rust-lang.org/nightly/std/sync/struct.Exclusive.html
 pub trait Component: Send + Sync + 'static {
     /// A marker type indicating the storage type used for this component.
     /// This must be either [`TableStorage`] or [`SparseStorage`].
     type Storage: ComponentStorage;
+
+    /// Called when registering this component, allowing it to register its [`ComponentHooks`].
+    fn register_component_hooks(_hooks: &mut ComponentHooks) {}
 }
 
 /// Marker type for components stored in a [`Table`](crate::storage::Table).
@@ -201,6 +232,91 @@ pub enum StorageType {
     SparseSet,
 }
 
+/// A function run in response to a component lifecycle event on an entity.
+///
+/// Hooks are given exclusive access to the [`World`]. Hooks must not despawn the entity they are
+/// triggered for, except from an `on_remove` hook run while that entity is being despawned.
+pub type ComponentHook = fn(&mut World, Entity, ComponentId);
+
+/// The lifecycle hooks of a component, stored in its [`ComponentDescriptor`].
+///
+/// Each hook can be set at most once: hooks are meant to be owned by the component that defines
+/// them, rather than layered on by unrelated code. See [`World::register_component_hooks`].
+#[derive(Debug, Clone, Default)]
+pub struct ComponentHooks {
+    pub(crate) on_add: Option<ComponentHook>,
+    pub(crate) on_insert: Option<ComponentHook>,
+    pub(crate) on_remove: Option<ComponentHook>,
+}
+
+impl ComponentHooks {
+    /// Register a [`ComponentHook`] that will be run when this component is added to an entity.
+    /// An `on_add` hook will always run before `on_insert` hooks. Spawning an entity counts as
+    /// adding all of its components.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_add` hook
+    pub fn on_add(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_add(hook)
+            .expect("Component already has an on_add hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when this component is added (with `.insert`)
+    /// or replaced. The hook won't run if the component is already present and is only mutated.
+    /// An `on_insert` hook always runs after any `on_add` hooks.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_insert` hook
+    pub fn on_insert(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_insert(hook)
+            .expect("Component already has an on_insert hook")
+    }
+
+    /// Register a [`ComponentHook`] that will be run when this component is removed from an entity.
+    /// The hook runs before the component is removed, so its value can still be read.
+    /// Despawning an entity counts as removing all of its components.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the component already has an `on_remove` hook
+    pub fn on_remove(&mut self, hook: ComponentHook) -> &mut Self {
+        self.try_on_remove(hook)
+            .expect("Component already has an on_remove hook")
+    }
+
+    /// Fallible version of [`Self::on_add`].
+    /// Returns `None` if the component already has an `on_add` hook.
+    pub fn try_on_add(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_add.is_some() {
+            return None;
+        }
+        self.on_add = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_insert`].
+    /// Returns `None` if the component already has an `on_insert` hook.
+    pub fn try_on_insert(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_insert.is_some() {
+            return None;
+        }
+        self.on_insert = Some(hook);
+        Some(self)
+    }
+
+    /// Fallible version of [`Self::on_remove`].
+    /// Returns `None` if the component already has an `on_remove` hook.
+    pub fn try_on_remove(&mut self, hook: ComponentHook) -> Option<&mut Self> {
+        if self.on_remove.is_some() {
+            return None;
+        }
+        self.on_remove = Some(hook);
+        Some(self)
+    }
+}
+
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
 #[derive(Debug)]
 pub struct ComponentInfo {
@@ -259,6 +375,12 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    /// Returns the lifecycle hooks of the current component.
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.descriptor.hooks
+    }
+
     /// Create a new [`ComponentInfo`].
     pub(crate) fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
         ComponentInfo { id, descriptor }
@@ -329,6 +451,7 @@ pub struct ComponentDescriptor {
     // this descriptor describes.
     // None if the underlying type doesn't need to be dropped
     drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
+    hooks: ComponentHooks,
 }
 
 // We need to ignore the `drop` field in our `Debug` impl
@@ -340,6 +463,7 @@ impl std::fmt::Debug for ComponentDescriptor {
             .field("is_send_and_sync", &self.is_send_and_sync)
             .field("type_id", &self.type_id)
             .field("layout", &self.layout)
+            .field("hooks", &self.hooks)
             .finish()
     }
 }
@@ -352,6 +476,8 @@ impl ComponentDescriptor {
 
     /// Create a new `ComponentDescriptor` for the type `T`.
     pub fn new<T: Component>() -> Self {
+        let mut hooks = ComponentHooks::default();
+        T::register_component_hooks(&mut hooks);
         Self {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type: T::Storage::STORAGE_TYPE,
@@ -359,6 +485,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            hooks,
         }
     }
 
@@ -380,6 +507,7 @@ impl ComponentDescriptor {
             type_id: None,
             layout,
             drop,
+            hooks: ComponentHooks::default(),
         }
     }
 
@@ -396,6 +524,7 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            hooks: ComponentHooks::default(),
         }
     }
 
@@ -407,9 +536,25 @@ impl ComponentDescriptor {
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
+            hooks: ComponentHooks::default(),
         }
     }
 
+    /// Sets the lifecycle hooks of this component.
+    ///
+    /// This is mostly useful for components that are not Rust types. For Rust types, prefer
+    /// implementing [`Component::register_component_hooks`].
+    pub fn with_hooks(mut self, hooks: ComponentHooks) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Returns the lifecycle hooks of this component.
+    #[inline]
+    pub fn hooks(&self) -> &ComponentHooks {
+        &self.hooks
+    }
+
     /// Returns a value indicating the storage strategy for the current component.
     #[inline]
     pub fn storage_type(&self) -> StorageType {
@@ -525,6 +670,28 @@ impl Components {
         self.components.get_unchecked(id.0)
     }
 
+    /// Collects the hooks chosen by `select` for each of the given components, in order.
+    pub(crate) fn collect_hooks(
+        &self,
+        ids: impl IntoIterator<Item = ComponentId>,
+        select: impl Fn(&ComponentHooks) -> Option<ComponentHook>,
+    ) -> Vec<(ComponentHook, ComponentId)> {
+        ids.into_iter()
+            .filter_map(|id| {
+                let hook = select(&self.components[id.0].descriptor.hooks)?;
+                Some((hook, id))
+            })
+            .collect()
+    }
+
+    /// Gets mutable access to the [`ComponentHooks`] of the component with the given [`ComponentId`].
+    #[inline]
+    pub(crate) fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
+        self.components
+            .get_mut(id.0)
+            .map(|info| &mut info.descriptor.hooks)
+    }
+
     /// Type-erased equivalent of [`Components::component_id`].
     #[inline]
     pub fn get_id(&self, type_id: TypeId) -> Option<ComponentId> {
@@ -766,6 +766,83 @@ impl Entities {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// Captures the generations and the freelist, which determine the IDs handed out by
+    /// future allocations.
+    pub(crate) fn allocator_snapshot(&mut self) -> EntitiesAllocatorSnapshot {
+        self.verify_flushed();
+        EntitiesAllocatorSnapshot {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            pending: self.pending.clone(),
+        }
+    }
+
+    /// Restores the generations and the freelist captured by [`Entities::allocator_snapshot`],
+    /// so that the same IDs are allocated again.
+    ///
+    /// Entities allocated after the snapshot was taken that are still allocated keep their IDs,
+    /// which are skipped by the following allocations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entities that were allocated when the snapshot was taken are not allocated
+    /// with the same generations.
+    pub(crate) fn restore_allocator(&mut self, snapshot: &EntitiesAllocatorSnapshot) {
+        self.verify_flushed();
+        let is_allocated = |meta: &EntityMeta| meta.location.archetype_id != ArchetypeId::INVALID;
+        let snapshot_len = snapshot.generations.len();
+        let len = self
+            .meta
+            .iter()
+            .rposition(is_allocated)
+            .map_or(0, |index| index + 1)
+            .max(snapshot_len);
+        let mut was_free = vec![false; snapshot_len];
+        for &index in &snapshot.pending {
+            was_free[index as usize] = true;
+        }
+
+        self.meta.resize(len, EntityMeta::EMPTY);
+        for (index, meta) in self.meta.iter_mut().enumerate() {
+            let Some(&generation) = snapshot.generations.get(index) else {
+                // Never allocated when the snapshot was taken.
+                if !is_allocated(meta) {
+                    meta.generation = 0;
+                }
+                continue;
+            };
+            if !is_allocated(meta) {
+                assert!(
+                    was_free[index],
+                    "entities allocated when the snapshot was taken must be allocated when restoring it"
+                );
+                meta.generation = generation;
+            } else if !was_free[index] {
+                assert_eq!(
+                    meta.generation, generation,
+                    "entities allocated when restoring a snapshot must match the snapshot"
+                );
+            }
+        }
+
+        // The IDs past the snapshot come after its freelist, in the order they were allocated.
+        let meta = &self.meta;
+        let pending: Vec<u32> = (snapshot_len as u32..len as u32)
+            .rev()
+            .chain(snapshot.pending.iter().copied())
+            .filter(|&index| !is_allocated(&meta[index as usize]))
+            .collect();
+        self.pending = pending;
+        *self.free_cursor.get_mut() = self.pending.len() as IdCursor;
+        self.len = (len - self.pending.len()) as u32;
+    }
+}
+
+/// The state of the [`Entities`] allocator, captured by [`Entities::allocator_snapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EntitiesAllocatorSnapshot {
+    generations: Vec<u32>,
+    pending: Vec<u32>,
 }
 
 // This type is repr(C) to ensure that the layout and values within it can be safe to fully fill
@@ -12,6 +12,10 @@ use std::{
     iter::Chain,
     marker::PhantomData,
     slice::Iter,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc, Mutex, Weak,
+    },
 };
 /// A type that can be stored in an [`Events<E>`] resource
 /// You can conveniently access events using the [`EventReader`] and [`EventWriter`] system parameter.
@@ -151,6 +155,9 @@ struct EventInstance<E: Event> {
 ///
 /// The buffers in [`Events`] will grow indefinitely if [`update`](Events::update) is never called.
 ///
+/// How long events are kept around by [`update`](Events::update) can be changed per event type
+/// with an [`EventRetention`] policy, see [`Events::with_retention`].
+///
 /// An alternative call pattern would be to call [`update`](Events::update)
 /// manually across frames to control when events are cleared.
 /// This complicates consumption and risks ever-expanding memory usage if not cleaned up,
@@ -168,6 +175,10 @@ pub struct Events<E: Event> {
     /// Holds the newer events.
     events_b: EventSequence<E>,
     event_count: usize,
+    retention: EventRetention,
+    /// The cursors of the [`ManualEventReader`]s reading from this collection.
+    /// Only consulted by [`EventRetention::UntilConsumed`].
+    readers: Mutex<Vec<Weak<AtomicUsize>>>,
 }
 
 // Derived Default impl would incorrectly require E: Default
@@ -177,10 +188,33 @@ impl<E: Event> Default for Events<E> {
             events_a: Default::default(),
             events_b: Default::default(),
             event_count: Default::default(),
+            retention: Default::default(),
+            readers: Default::default(),
         }
     }
 }
 
+/// Controls which events are dropped when [`Events::update`] is called.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EventRetention {
+    /// Events are kept for two updates, after which they are dropped whether they were read or not.
+    #[default]
+    DoubleBuffer,
+    /// At most the given number of the most recent events are kept across updates.
+    ///
+    /// Events sent since the last update are all kept until the next update, even if there are
+    /// more of them than the capacity.
+    RingBuffer(usize),
+    /// Events are kept until every registered [`ManualEventReader`] (including the one backing
+    /// each [`EventReader`]) has read them.
+    ///
+    /// Readers created with [`Events::get_reader`] are registered immediately, while the reader
+    /// of an [`EventReader`] is registered the first time its system reads. Readers are
+    /// unregistered when dropped. If no reader is registered, events are kept for two updates like
+    /// [`EventRetention::DoubleBuffer`].
+    UntilConsumed,
+}
+
 impl<E: Event> Events<E> {
     /// Returns the index of the oldest event stored in the event buffer.
     pub fn oldest_event_count(&self) -> usize {
@@ -279,6 +313,14 @@ impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     pub fn clear(&mut self) {
         self.reader.clear(&self.events);
     }
+
+    /// Returns the number of events that were dropped before this [`EventReader`] could read them.
+    ///
+    /// Reading moves the reader past any dropped events, so this must be called before
+    /// [`EventReader::iter()`] or [`EventReader::clear()`] to observe them.
+    pub fn missed_events(&self) -> usize {
+        self.reader.missed_events(&self.events)
+    }
 }
 
 impl<'a, 'w, 's, E: Event> IntoIterator for &'a mut EventReader<'w, 's, E> {
@@ -367,6 +409,8 @@ impl<'w, E: Event> EventWriter<'w, E> {
 #[derive(Debug)]
 pub struct ManualEventReader<E: Event> {
     last_event_count: usize,
+    /// Mirror of `last_event_count` shared with the [`Events`] this reader is registered with.
+    cursor: Option<Arc<AtomicUsize>>,
     _marker: PhantomData<E>,
 }
 
@@ -374,6 +418,7 @@ impl<E: Event> Default for ManualEventReader<E> {
     fn default() -> Self {
         ManualEventReader {
             last_event_count: 0,
+            cursor: None,
             _marker: Default::default(),
         }
     }
@@ -420,7 +465,25 @@ impl<E: Event> ManualEventReader<E> {
 
     /// See [`EventReader::clear()`]
     pub fn clear(&mut self, events: &Events<E>) {
-        self.last_event_count = events.event_count;
+        self.register(events);
+        self.set_last_event_count(events.event_count);
+    }
+
+    /// Registers this reader with `events`, so that [`EventRetention::UntilConsumed`] keeps
+    /// the events it has not read yet.
+    fn register(&mut self, events: &Events<E>) {
+        if self.cursor.is_none() {
+            let cursor = Arc::new(AtomicUsize::new(self.last_event_count));
+            events.readers.lock().unwrap().push(Arc::downgrade(&cursor));
+            self.cursor = Some(cursor);
+        }
+    }
+
+    fn set_last_event_count(&mut self, last_event_count: usize) {
+        self.last_event_count = last_event_count;
+        if let Some(cursor) = &self.cursor {
+            cursor.store(last_event_count, AtomicOrdering::Relaxed);
+        }
     }
 }
 
@@ -481,7 +544,8 @@ impl<'a, E: Event> ManualEventIteratorWithId<'a, E> {
         let unread_count = a.len() + b.len();
         // Ensure `len` is implemented correctly
         debug_assert_eq!(unread_count, reader.len(events));
-        reader.last_event_count = events.event_count - unread_count;
+        reader.register(events);
+        reader.set_last_event_count(events.event_count - unread_count);
         // Iterate the oldest first, then the newer events
         let chain = a.iter().chain(b.iter());
 
@@ -508,7 +572,8 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
         {
             Some(item) => {
                 detailed_trace!("EventReader::iter() -> {}", item.1);
-                self.reader.last_event_count += 1;
+                self.reader
+                    .set_last_event_count(self.reader.last_event_count + 1);
                 self.unread -= 1;
                 Some(item)
             }
@@ -518,11 +583,13 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
 
     fn nth(&mut self, n: usize) -> Option<Self::Item> {
         if let Some(EventInstance { event_id, event }) = self.chain.nth(n) {
-            self.reader.last_event_count += n + 1;
+            self.reader
+                .set_last_event_count(self.reader.last_event_count + n + 1);
             self.unread -= n + 1;
             Some((event, *event_id))
         } else {
-            self.reader.last_event_count += self.unread;
+            self.reader
+                .set_last_event_count(self.reader.last_event_count + self.unread);
             self.unread = 0;
             None
         }
@@ -533,12 +600,14 @@ impl<'a, E: Event> Iterator for ManualEventIteratorWithId<'a, E> {
         Self: Sized,
     {
         let EventInstance { event_id, event } = self.chain.last()?;
-        self.reader.last_event_count += self.unread;
+        self.reader
+            .set_last_event_count(self.reader.last_event_count + self.unread);
         Some((event, *event_id))
     }
 
     fn count(self) -> usize {
-        self.reader.last_event_count += self.unread;
+        self.reader
+            .set_last_event_count(self.reader.last_event_count + self.unread);
         self.unread
     }
 
@@ -579,30 +648,98 @@ impl<E: Event> Events<E> {
 
     /// Gets a new [`ManualEventReader`]. This will include all events already in the event buffers.
     pub fn get_reader(&self) -> ManualEventReader<E> {
-        ManualEventReader::default()
+        let mut reader = ManualEventReader::default();
+        reader.register(self);
+        reader
     }
 
     /// Gets a new [`ManualEventReader`]. This will ignore all events already in the event buffers.
     /// It will read all future events.
     pub fn get_reader_current(&self) -> ManualEventReader<E> {
-        ManualEventReader {
+        let mut reader = ManualEventReader {
             last_event_count: self.event_count,
             ..Default::default()
+        };
+        reader.register(self);
+        reader
+    }
+
+    /// Creates an empty event collection using the given [`EventRetention`] policy.
+    pub fn with_retention(retention: EventRetention) -> Self {
+        Self {
+            retention,
+            ..Default::default()
         }
     }
 
-    /// Swaps the event buffers and clears the oldest event buffer. In general, this should be
-    /// called once per frame/update.
+    /// Returns the [`EventRetention`] policy used by [`Events::update`].
+    pub fn retention(&self) -> EventRetention {
+        self.retention
+    }
+
+    /// Changes the [`EventRetention`] policy used by [`Events::update`].
+    /// Events already stored are only dropped by the next update.
+    pub fn set_retention(&mut self, retention: EventRetention) {
+        self.retention = retention;
+    }
+
+    /// Drops old events according to this collection's [`EventRetention`] policy. With the
+    /// default policy this swaps the event buffers and clears the oldest event buffer.
+    /// In general, this should be called once per frame/update.
     pub fn update(&mut self) {
-        std::mem::swap(&mut self.events_a, &mut self.events_b);
-        self.events_b.clear();
-        self.events_b.start_event_count = self.event_count;
+        // Readers register under every policy, so dropped ones are forgotten under every policy.
+        let readers = self.readers.get_mut().unwrap();
+        readers.retain(|reader| reader.strong_count() > 0);
+        match self.retention {
+            EventRetention::DoubleBuffer => self.swap_buffers(),
+            EventRetention::RingBuffer(capacity) => {
+                self.merge_buffers();
+                let excess = self.events_a.len().saturating_sub(capacity);
+                self.drop_oldest(excess);
+            }
+            EventRetention::UntilConsumed => {
+                let oldest_unread = readers
+                    .iter()
+                    .filter_map(Weak::upgrade)
+                    .map(|cursor| cursor.load(AtomicOrdering::Relaxed))
+                    .min();
+                match oldest_unread {
+                    Some(oldest_unread) => {
+                        self.merge_buffers();
+                        let consumed =
+                            oldest_unread.saturating_sub(self.events_a.start_event_count);
+                        self.drop_oldest(consumed);
+                    }
+                    None => self.swap_buffers(),
+                }
+            }
+        }
         debug_assert_eq!(
             self.events_a.start_event_count + self.events_a.len(),
             self.events_b.start_event_count
         );
     }
 
+    fn swap_buffers(&mut self) {
+        std::mem::swap(&mut self.events_a, &mut self.events_b);
+        self.events_b.clear();
+        self.events_b.start_event_count = self.event_count;
+    }
+
+    /// Moves all events into the older buffer, leaving the newer buffer empty.
+    fn merge_buffers(&mut self) {
+        let newer = std::mem::take(&mut self.events_b.events);
+        self.events_a.extend(newer);
+        self.events_b.start_event_count = self.event_count;
+    }
+
+    /// Drops up to `count` of the oldest events from the older buffer.
+    fn drop_oldest(&mut self, count: usize) {
+        let count = count.min(self.events_a.len());
+        self.events_a.drain(..count);
+        self.events_a.start_event_count += count;
+    }
+
     /// A system that calls [`Events::update`] once per frame.
     pub fn update_system(mut events: ResMut<Self>) {
         events.update();
@@ -973,6 +1110,123 @@ mod tests {
         assert!(is_empty, "EventReader should be empty");
     }
 
+    #[test]
+    fn ring_buffer_retention() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::RingBuffer(3));
+        let mut reader = events.get_reader();
+        events.extend((0..5).map(|i| TestEvent { i }));
+        events.update();
+        events.update();
+        events.update();
+
+        assert_eq!(reader.missed_events(&events), 2);
+        assert_eq!(
+            get_events(&events, &mut reader),
+            vec![TestEvent { i: 2 }, TestEvent { i: 3 }, TestEvent { i: 4 }]
+        );
+        assert_eq!(reader.missed_events(&events), 0);
+
+        events.send(TestEvent { i: 5 });
+        events.update();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events.oldest_id(), 3);
+        assert_eq!(get_events(&events, &mut reader), vec![TestEvent { i: 5 }]);
+    }
+
+    #[test]
+    fn until_consumed_retention() {
+        let mut events = Events::<TestEvent>::with_retention(EventRetention::UntilConsumed);
+        let mut fast = events.get_reader();
+        let mut slow = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.send(TestEvent { i: 1 });
+        assert_eq!(fast.iter(&events).nth(0), Some(&TestEvent { i: 0 }));
+        for _ in 0..5 {
+            events.update();
+        }
+        assert_eq!(events.len(), 2, "unread events are kept across updates");
+
+        assert_eq!(
+            get_events(&events, &mut slow),
+            vec![TestEvent { i: 0 }, TestEvent { i: 1 }]
+        );
+        events.update();
+        assert_eq!(events.len(), 1, "events read by every reader are dropped");
+
+        drop(fast);
+        events.update();
+        assert!(events.is_empty(), "dropped readers are unregistered");
+        assert_eq!(slow.missed_events(&events), 0);
+
+        drop(slow);
+        events.send(TestEvent { i: 2 });
+        events.update();
+        events.update();
+        assert!(
+            events.is_empty(),
+            "without readers events are kept for two updates"
+        );
+    }
+
+    #[test]
+    fn dropped_readers_are_unregistered_under_every_retention() {
+        let mut events = Events::<TestEvent>::default();
+        for _ in 0..3 {
+            events.get_reader().clear(&events);
+            events.update();
+        }
+        assert_eq!(events.readers.get_mut().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn until_consumed_retention_event_reader() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        world.insert_resource(Events::<TestEvent>::with_retention(
+            EventRetention::UntilConsumed,
+        ));
+
+        let mut reader =
+            IntoSystem::into_system(|mut events: EventReader<TestEvent>| -> (usize, usize) {
+                (events.missed_events(), events.iter().count())
+            });
+        reader.initialize(&mut world);
+        assert_eq!(reader.run((), &mut world), (0, 0));
+
+        world.send_event(TestEvent { i: 0 });
+        for _ in 0..3 {
+            world.resource_mut::<Events<TestEvent>>().update();
+        }
+        assert_eq!(reader.run((), &mut world), (0, 1));
+    }
+
+    #[test]
+    fn event_reader_missed_events() {
+        use bevy_ecs::prelude::*;
+
+        let mut world = World::new();
+        world.init_resource::<Events<TestEvent>>();
+
+        let mut reader =
+            IntoSystem::into_system(|mut events: EventReader<TestEvent>| -> (usize, usize) {
+                (events.missed_events(), events.iter().count())
+            });
+        reader.initialize(&mut world);
+
+        world.send_event(TestEvent { i: 0 });
+        world.send_event(TestEvent { i: 1 });
+        world.resource_mut::<Events<TestEvent>>().update();
+        world.send_event(TestEvent { i: 2 });
+        world.resource_mut::<Events<TestEvent>>().update();
+        world.resource_mut::<Events<TestEvent>>().update();
+        world.send_event(TestEvent { i: 3 });
+
+        assert_eq!(reader.run((), &mut world), (3, 1));
+        assert_eq!(reader.run((), &mut world), (0, 0));
+    }
+
     #[allow(clippy::iter_nth_zero)]
     #[test]
     fn test_event_iter_nth() {
@@ -0,0 +1,357 @@
+//! Observers: systems that run immediately when an [`Event`] is triggered.
+//!
+//! Unlike [`EventReader`](crate::event::EventReader)s, which poll for events once per frame,
+//! observers run as soon as [`World::trigger`] or [`World::trigger_targets`] is called.
+//! Observers can either watch every trigger of an event type, or be attached to a specific entity
+//! with [`EntityMut::observe`], in which case they only run for events targeting that entity.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::observer::Trigger;
+//!
+//! #[derive(Event)]
+//! struct Damage(u32);
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! let player = world
+//!     .spawn(Health(10))
+//!     .observe(|In(trigger): In<Trigger<Damage>>, mut query: Query<&mut Health>| {
+//!         let mut health = query.get_mut(trigger.entity()).unwrap();
+//!         health.0 = health.0.saturating_sub(trigger.event().0);
+//!     })
+//!     .id();
+//!
+//! world.trigger_targets(Damage(3), player);
+//! assert_eq!(world.get::<Health>(player).unwrap().0, 7);
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    change_detection::DetectChangesMut,
+    component::{Component, TableStorage},
+    entity::Entity,
+    event::Event,
+    relation::{DespawnPolicy, Relation, RelationSources},
+    system::{BoxedSystem, Command, IntoSystem, Resource},
+    world::{EntityMut, World},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+/// The input of an observer system, describing the event that triggered it.
+///
+/// Observer systems take it as their [`In`](crate::system::In) parameter.
+pub struct Trigger<E: Event> {
+    event: Arc<E>,
+    entity: Entity,
+}
+
+impl<E: Event> Trigger<E> {
+    /// Returns the event that triggered the observer.
+    pub fn event(&self) -> &E {
+        &self.event
+    }
+
+    /// Returns the entity targeted by the event.
+    ///
+    /// This is [`Entity::PLACEHOLDER`] if the event was triggered without a target.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+/// The [`Relation`] from an observer entity to the entity it watches.
+///
+/// Observers are despawned along with the entity they watch.
+pub struct Observes;
+
+impl Relation for Observes {
+    const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::DespawnSources;
+    const EXCLUSIVE: bool = true;
+}
+
+/// Stores the system of an observer on the observer entity.
+struct ObserverSystem<E: Event> {
+    initialized: bool,
+    system: BoxedSystem<Trigger<E>>,
+}
+
+impl<E: Event> Component for ObserverSystem<E> {
+    type Storage = TableStorage;
+}
+
+/// The observers of `E` that are not attached to an entity.
+#[derive(Resource)]
+struct GlobalObservers<E: Event> {
+    observers: Vec<Entity>,
+    marker: PhantomData<E>,
+}
+
+impl<E: Event> Default for GlobalObservers<E> {
+    fn default() -> Self {
+        Self {
+            observers: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl World {
+    /// Spawns an observer that runs `system` every time the event `E` is triggered, whatever its
+    /// target. Returns the observer entity: despawning it removes the observer.
+    pub fn observe<E: Event, M>(
+        &mut self,
+        system: impl IntoSystem<Trigger<E>, (), M>,
+    ) -> EntityMut {
+        let observer = self.spawn(ObserverSystem {
+            initialized: false,
+            system: Box::new(IntoSystem::into_system(system)),
+        });
+        let id = observer.id();
+        let world = observer.into_world_mut();
+        world
+            .get_resource_or_insert_with(GlobalObservers::<E>::default)
+            .observers
+            .push(id);
+        world.entity_mut(id)
+    }
+
+    /// Triggers the event `E` without a target, immediately running all the observers
+    /// registered with [`World::observe`].
+    pub fn trigger<E: Event>(&mut self, event: E) {
+        self.trigger_internal(event, None);
+    }
+
+    /// Triggers the event `E` for the `target` entity, immediately running the observers attached
+    /// to it with [`EntityMut::observe`], followed by those registered with [`World::observe`].
+    pub fn trigger_targets<E: Event>(&mut self, event: E, target: Entity) {
+        self.trigger_internal(event, Some(target));
+    }
+
+    fn trigger_internal<E: Event>(&mut self, event: E, target: Option<Entity>) {
+        let mut observers: Vec<Entity> = target
+            .and_then(|target| self.get::<RelationSources<Observes>>(target))
+            .map(|sources| sources.to_vec())
+            .unwrap_or_default();
+        if let Some(global) = self.get_resource::<GlobalObservers<E>>() {
+            let targeted = observers.len();
+            observers.extend(
+                global
+                    .observers
+                    .iter()
+                    .copied()
+                    .filter(|observer| self.entities.contains(*observer)),
+            );
+            // Forget about the global observers that were despawned.
+            if observers.len() - targeted != global.observers.len() {
+                let alive = observers[targeted..].to_vec();
+                self.resource_mut::<GlobalObservers<E>>()
+                    .bypass_change_detection()
+                    .observers = alive;
+            }
+        }
+
+        let event = Arc::new(event);
+        let entity = target.unwrap_or(Entity::PLACEHOLDER);
+        for observer in observers {
+            self.run_observer(
+                observer,
+                Trigger {
+                    event: event.clone(),
+                    entity,
+                },
+            );
+        }
+    }
+
+    fn run_observer<E: Event>(&mut self, observer: Entity, trigger: Trigger<E>) {
+        // Observers for other event types, or observers that are already running, are skipped.
+        let Some(ObserverSystem {
+            mut initialized,
+            mut system,
+        }) = self
+            .get_entity_mut(observer)
+            .and_then(|mut entity| entity.take::<ObserverSystem<E>>())
+        else {
+            return;
+        };
+
+        if !initialized {
+            system.initialize(self);
+            initialized = true;
+        }
+        system.run(trigger, self);
+        system.apply_deferred(self);
+
+        if let Some(mut entity) = self.get_entity_mut(observer) {
+            entity.insert(ObserverSystem {
+                initialized,
+                system,
+            });
+        }
+    }
+}
+
+impl<'w> EntityMut<'w> {
+    /// Attaches an observer to this entity, which runs `system` every time the event `E` is
+    /// triggered with this entity as its target.
+    ///
+    /// The observer is stored on its own entity, which is despawned along with this one.
+    pub fn observe<E: Event, M>(
+        &mut self,
+        system: impl IntoSystem<Trigger<E>, (), M>,
+    ) -> &mut Self {
+        let target = self.id();
+        self.world_scope(|world| {
+            world
+                .spawn(ObserverSystem {
+                    initialized: false,
+                    system: Box::new(IntoSystem::into_system(system)),
+                })
+                .relate::<Observes>(target);
+        });
+        self
+    }
+}
+
+/// A [`Command`] that triggers an event, optionally targeting an entity.
+///
+/// See [`World::trigger`] and [`World::trigger_targets`].
+pub struct TriggerEvent<E: Event> {
+    /// The event to trigger.
+    pub event: E,
+    /// The entity targeted by the event, if any.
+    pub target: Option<Entity>,
+}
+
+impl<E: Event> Command for TriggerEvent<E> {
+    fn apply(self, world: &mut World) {
+        world.trigger_internal(self.event, self.target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trigger;
+    use crate as bevy_ecs;
+    use crate::{prelude::*, system::CommandQueue};
+
+    #[derive(Event)]
+    struct Ping(u32);
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<(u32, Entity)>);
+
+    fn record(In(trigger): In<Trigger<Ping>>, mut received: ResMut<Received>) {
+        received.0.push((trigger.event().0, trigger.entity()));
+    }
+
+    #[test]
+    fn global_observer() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        world.observe(record);
+
+        world.trigger(Ping(1));
+        let target = world.spawn_empty().id();
+        world.trigger_targets(Ping(2), target);
+
+        assert_eq!(
+            world.resource::<Received>().0,
+            vec![(1, Entity::PLACEHOLDER), (2, target)]
+        );
+    }
+
+    #[test]
+    fn entity_observer() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let watched = world.spawn_empty().observe(record).id();
+        let other = world.spawn_empty().id();
+
+        world.trigger_targets(Ping(1), other);
+        world.trigger(Ping(2));
+        world.trigger_targets(Ping(3), watched);
+
+        assert_eq!(world.resource::<Received>().0, vec![(3, watched)]);
+    }
+
+    #[test]
+    fn observers_run_in_order_and_immediately() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let watched = world
+            .spawn_empty()
+            .observe(
+                |In(trigger): In<Trigger<Ping>>, mut received: ResMut<Received>| {
+                    received.0.push((trigger.event().0 * 10, trigger.entity()));
+                },
+            )
+            .id();
+        world.observe(record);
+
+        world.trigger_targets(Ping(1), watched);
+        assert_eq!(
+            world.resource::<Received>().0,
+            vec![(10, watched), (1, watched)]
+        );
+    }
+
+    #[test]
+    fn observer_despawned_with_target() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let watched = world.spawn_empty().observe(record).id();
+        let entities = world.entities().len();
+
+        world.despawn(watched);
+        assert_eq!(world.entities().len(), entities - 2);
+    }
+
+    #[test]
+    fn despawned_global_observer() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let observer = world.observe(record).id();
+        world.despawn(observer);
+
+        world.trigger(Ping(1));
+        assert!(world.resource::<Received>().0.is_empty());
+    }
+
+    #[test]
+    fn trigger_from_commands() {
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        let watched = world.spawn_empty().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(watched).observe(record);
+        commands.trigger_targets(Ping(1), watched);
+        queue.apply(&mut world);
+
+        assert_eq!(world.resource::<Received>().0, vec![(1, watched)]);
+    }
+
+    #[test]
+    fn observer_triggering_events() {
+        #[derive(Event)]
+        struct Pong;
+
+        let mut world = World::new();
+        world.init_resource::<Received>();
+        world.observe(|_: In<Trigger<Pong>>, mut commands: Commands| {
+            commands.trigger(Ping(7));
+        });
+        world.observe(record);
+
+        world.trigger(Pong);
+        assert_eq!(
+            world.resource::<Received>().0,
+            vec![(7, Entity::PLACEHOLDER)]
+        );
+    }
+}
@@ -143,6 +143,11 @@ impl<T: SparseSetIndex> Access<T> {
         self.writes.clear();
     }
 
+    /// Removes all writes, turning them into reads.
+    pub fn clear_writes(&mut self) {
+        self.writes.clear();
+    }
+
     /// Adds all access from `other`.
     pub fn extend(&mut self, other: &Access<T>) {
         self.reads_all = self.reads_all || other.reads_all;
@@ -377,6 +382,29 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     pub fn read_all(&mut self) {
         self.access.read_all();
     }
+
+    /// Returns `true` if a set of elements, described by `set_contains_index`, satisfies the
+    /// `With` and `Without` filters of at least one of the filter sets.
+    pub fn matches_set(&self, set_contains_index: impl Fn(T) -> bool) -> bool {
+        self.filter_sets.iter().any(|filter| {
+            filter
+                .with
+                .ones()
+                .all(|index| set_contains_index(T::get_sparse_set_index(index)))
+                && filter
+                    .without
+                    .ones()
+                    .all(|index| !set_contains_index(T::get_sparse_set_index(index)))
+        })
+    }
+
+    /// Returns an iterator over the elements used in the `With` and `Without` filters.
+    pub fn filtered_indices(&self) -> impl Iterator<Item = T> + '_ {
+        self.filter_sets
+            .iter()
+            .flat_map(|filter| filter.with.ones().chain(filter.without.ones()))
+            .map(T::get_sparse_set_index)
+    }
 }
 
 #[derive(Clone, Eq, PartialEq)]
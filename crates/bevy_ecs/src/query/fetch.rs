@@ -5,7 +5,10 @@ use crate::{
     entity::Entity,
     query::{Access, DebugCheckedUnwrap, FilteredAccess},
     storage::{ComponentSparseSet, Table, TableRow},
-    world::{unsafe_world_cell::UnsafeWorldCell, EntityRef, Mut, Ref, World},
+    world::{
+        unsafe_world_cell::UnsafeWorldCell, EntityRef, FilteredEntityMut, FilteredEntityRef, Mut,
+        Ref, World,
+    },
 };
 pub use bevy_ecs_macros::WorldQuery;
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
@@ -420,6 +423,14 @@ pub unsafe trait WorldQuery {
         true
     }
 
+    /// Sets the access this [`WorldQuery`] will use, for queries whose access is only known at
+    /// runtime, such as [`FilteredEntityRef`]. This is called with the full access of the query
+    /// when it is built by a [`QueryBuilder`](crate::query::QueryBuilder), before
+    /// [`WorldQuery::update_component_access`].
+    #[allow(unused_variables)]
+    #[inline]
+    fn set_access(state: &mut Self::State, access: &FilteredAccess<ComponentId>) {}
+
     /// Adds any component accesses used by this [`WorldQuery`] to `access`.
     // This does not have a default body of `{}` because 99% of cases need to add accesses
     // and forgetting to do so would be unsound.
@@ -602,6 +613,185 @@ unsafe impl<'a> WorldQuery for EntityRef<'a> {
 /// SAFETY: access is read only
 unsafe impl<'a> ReadOnlyWorldQuery for EntityRef<'a> {}
 
+/// SAFETY: `Self` is the same as `Self::ReadOnly`
+unsafe impl<'a> WorldQuery for FilteredEntityRef<'a> {
+    type Fetch<'w> = (UnsafeWorldCell<'w>, Access<ComponentId>);
+    type Item<'w> = FilteredEntityRef<'w>;
+    type ReadOnly = Self;
+    type State = FilteredAccess<ComponentId>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    const IS_DENSE: bool = true;
+
+    const IS_ARCHETYPAL: bool = true;
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        (world, state.access().clone())
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _state: &Self::State,
+        _archetype: &'w Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &Self::State, _table: &'w Table) {
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        (world, access): &mut Self::Fetch<'w>,
+        entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // SAFETY: `fetch` must be called with an entity that exists in the world
+        let cell = world.get_entity(entity).debug_checked_unwrap();
+        // SAFETY: read access to every component in `access` has been registered.
+        FilteredEntityRef::new(cell, access.clone())
+    }
+
+    fn set_access(state: &mut Self::State, access: &FilteredAccess<ComponentId>) {
+        *state = access.clone();
+        state.access_mut().clear_writes();
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        assert!(
+            access.access().is_compatible(state.access()),
+            "FilteredEntityRef conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+        );
+        access.access_mut().extend(state.access());
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        for component_id in archetype.components() {
+            if state.access().has_read(component_id) {
+                access.add_read(archetype.get_archetype_component_id(component_id).unwrap());
+            }
+        }
+    }
+
+    fn init_state(_world: &mut World) -> Self::State {
+        FilteredAccess::default()
+    }
+
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+/// SAFETY: access is read only
+unsafe impl<'a> ReadOnlyWorldQuery for FilteredEntityRef<'a> {}
+
+/// SAFETY: `FilteredEntityRef` accesses the same components as `FilteredEntityMut`, read-only
+unsafe impl<'a> WorldQuery for FilteredEntityMut<'a> {
+    type Fetch<'w> = (UnsafeWorldCell<'w>, Access<ComponentId>);
+    type Item<'w> = FilteredEntityMut<'w>;
+    type ReadOnly = FilteredEntityRef<'a>;
+    type State = FilteredAccess<ComponentId>;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    const IS_DENSE: bool = true;
+
+    const IS_ARCHETYPAL: bool = true;
+
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &Self::State,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        (world, state.access().clone())
+    }
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _state: &Self::State,
+        _archetype: &'w Archetype,
+        _table: &Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &Self::State, _table: &'w Table) {
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        (world, access): &mut Self::Fetch<'w>,
+        entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // SAFETY: `fetch` must be called with an entity that exists in the world
+        let cell = world.get_entity(entity).debug_checked_unwrap();
+        // SAFETY: mutable access to every component has been registered, and `fetch` is only
+        // called once per entity.
+        FilteredEntityMut::new(cell, access.clone())
+    }
+
+    fn set_access(state: &mut Self::State, access: &FilteredAccess<ComponentId>) {
+        *state = access.clone();
+    }
+
+    fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>) {
+        assert!(
+            access.access().is_compatible(state.access()),
+            "FilteredEntityMut conflicts with a previous access in this query. Exclusive access cannot coincide with any other accesses.",
+        );
+        access.access_mut().extend(state.access());
+    }
+
+    fn update_archetype_component_access(
+        state: &Self::State,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        for component_id in archetype.components() {
+            let archetype_component_id =
+                archetype.get_archetype_component_id(component_id).unwrap();
+            if state.access().has_write(component_id) {
+                access.add_write(archetype_component_id);
+            } else if state.access().has_read(component_id) {
+                access.add_read(archetype_component_id);
+            }
+        }
+    }
+
+    fn init_state(_world: &mut World) -> Self::State {
+        FilteredAccess::default()
+    }
+
+    fn matches_component_set(
+        _state: &Self::State,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
 #[doc(hidden)]
 pub struct ReadFetch<'w, T> {
     // T::Storage = TableStorage
@@ -1139,6 +1329,10 @@ unsafe impl<T: WorldQuery> WorldQuery for Option<T> {
             .then(|| T::fetch(&mut fetch.fetch, entity, table_row))
     }
 
+    fn set_access(state: &mut T::State, access: &FilteredAccess<ComponentId>) {
+        T::set_access(state, access);
+    }
+
     fn update_component_access(state: &T::State, access: &mut FilteredAccess<ComponentId>) {
         // We don't want to add the `with`/`without` of `T` as `Option<T>` will match things regardless of
         // `T`'s filters. for example `Query<(Option<&U>, &mut V)>` will match every entity with a `V` component
@@ -1379,6 +1573,11 @@ macro_rules! impl_tuple_fetch {
                 true $(&& $name::filter_fetch($name, _entity, _table_row))*
             }
 
+            fn set_access(state: &mut Self::State, _access: &FilteredAccess<ComponentId>) {
+                let ($($name,)*) = state;
+                $($name::set_access($name, _access);)*
+            }
+
             fn update_component_access(state: &Self::State, _access: &mut FilteredAccess<ComponentId>) {
                 let ($($name,)*) = state;
                 $($name::update_component_access($name, _access);)*
@@ -1484,6 +1683,11 @@ macro_rules! impl_anytuple_fetch {
                 )*)
             }
 
+            fn set_access(state: &mut Self::State, _access: &FilteredAccess<ComponentId>) {
+                let ($($name,)*) = state;
+                $($name::set_access($name, _access);)*
+            }
+
             fn update_component_access(state: &Self::State, _access: &mut FilteredAccess<ComponentId>) {
                 let ($($name,)*) = state;
 
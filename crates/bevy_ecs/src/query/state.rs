@@ -4,8 +4,8 @@ use crate::{
     entity::Entity,
     prelude::FromWorld,
     query::{
-        Access, BatchingStrategy, DebugCheckedUnwrap, FilteredAccess, QueryCombinationIter,
-        QueryIter, QueryParIter, WorldQuery,
+        Access, BatchingStrategy, DebugCheckedUnwrap, FilteredAccess, QueryBuilder,
+        QueryCombinationIter, QueryIter, QueryParIter, WorldQuery,
     },
     storage::{TableId, TableRow},
     world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
@@ -29,6 +29,9 @@ pub struct QueryState<Q: WorldQuery, F: ReadOnlyWorldQuery = ()> {
     pub(crate) matched_archetypes: FixedBitSet,
     pub(crate) archetype_component_access: Access<ArchetypeComponentId>,
     pub(crate) component_access: FilteredAccess<ComponentId>,
+    // Whether the query only accesses and filters on table components, in which case it iterates
+    // over tables instead of archetypes.
+    pub(crate) is_dense: bool,
     // NOTE: we maintain both a TableId bitset and a vec because iterating the vec is faster
     pub(crate) matched_table_ids: Vec<TableId>,
     // NOTE: we maintain both a ArchetypeId bitset and a vec because iterating the vec is faster
@@ -119,6 +122,43 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
             fetch_state,
             filter_state,
             component_access,
+            is_dense: Q::IS_DENSE && F::IS_DENSE,
+            matched_tables: Default::default(),
+            matched_archetypes: Default::default(),
+            archetype_component_access: Default::default(),
+        };
+        state.update_archetypes(world);
+        state
+    }
+
+    /// Creates a new [`QueryState`] from a given [`QueryBuilder`] and inherits its [`FilteredAccess`].
+    ///
+    /// # Panics
+    ///
+    /// If the access requested by `Q` conflicts with itself, for example a
+    /// [`FilteredEntityRef`](crate::world::FilteredEntityRef) reading a component that `Q`
+    /// also accesses mutably.
+    pub fn from_builder(builder: &mut QueryBuilder<Q, F>) -> Self {
+        let mut fetch_state = Q::init_state(builder.world_mut());
+        let filter_state = F::init_state(builder.world_mut());
+        Q::set_access(&mut fetch_state, builder.access());
+
+        // Recompute the access of `Q` now that its dynamic parts know their access: this checks
+        // that they do not conflict with the static parts of the query.
+        let mut component_access = FilteredAccess::default();
+        Q::update_component_access(&fetch_state, &mut component_access);
+        component_access.extend(builder.access());
+
+        let world = builder.world();
+        let mut state = Self {
+            world_id: world.id(),
+            archetype_generation: ArchetypeGeneration::initial(),
+            matched_table_ids: Vec::new(),
+            matched_archetype_ids: Vec::new(),
+            fetch_state,
+            filter_state,
+            component_access,
+            is_dense: builder.is_dense(),
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
@@ -202,6 +242,9 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
     pub fn new_archetype(&mut self, archetype: &Archetype) {
         if Q::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
+            && self
+                .component_access
+                .matches_set(|id| archetype.contains(id))
         {
             Q::update_archetype_component_access(
                 &self.fetch_state,
@@ -972,7 +1015,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         let mut filter = F::init_fetch(world, &self.filter_state, last_run, this_run);
 
         let tables = &world.storages().tables;
-        if Q::IS_DENSE && F::IS_DENSE {
+        if self.is_dense {
             for table_id in &self.matched_table_ids {
                 let table = tables.get(*table_id).debug_checked_unwrap();
                 Q::set_table(&mut fetch, &self.fetch_state, table);
@@ -1047,7 +1090,7 @@ impl<Q: WorldQuery, F: ReadOnlyWorldQuery> QueryState<Q, F> {
         // NOTE: If you are changing query iteration code, remember to update the following places, where relevant:
         // QueryIter, QueryIterationCursor, QueryManyIter, QueryCombinationIter, QueryState::for_each_unchecked_manual, QueryState::par_for_each_unchecked_manual
         bevy_tasks::ComputeTaskPool::get().scope(|scope| {
-            if Q::IS_DENSE && F::IS_DENSE {
+            if self.is_dense {
                 // SAFETY: We only access table data that has been registered in `self.archetype_component_access`.
                 let tables = &world.storages().tables;
                 for table_id in &self.matched_table_ids {
@@ -9,10 +9,12 @@ use bevy_reflect::{impl_reflect_value, ReflectDeserialize, ReflectSerialize, Typ
 mod component;
 mod map_entities;
 mod resource;
+mod snapshot;
 
 pub use component::{ReflectComponent, ReflectComponentFns};
 pub use map_entities::ReflectMapEntities;
 pub use resource::{ReflectResource, ReflectResourceFns};
+pub use snapshot::{SnapshotError, SnapshotFilter, WorldSnapshot};
 
 /// A [`Resource`] storing [`TypeRegistry`](bevy_reflect::TypeRegistry) for
 /// type registrations relevant to a whole app.
@@ -0,0 +1,441 @@
+use std::any::{type_name, TypeId};
+
+use bevy_reflect::{Reflect, TypeRegistry};
+use bevy_utils::HashSet;
+use thiserror::Error;
+
+use crate::{
+    component::Component,
+    entity::{EntitiesAllocatorSnapshot, Entity},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    system::Resource,
+    world::World,
+};
+
+/// Selects the components and resources captured by a [`WorldSnapshot`].
+///
+/// Every selected type must be registered in the [`AppTypeRegistry`] with
+/// `#[reflect(Component)]` or `#[reflect(Resource)]`.
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotFilter {
+    components: Vec<(TypeId, &'static str)>,
+    resources: Vec<(TypeId, &'static str)>,
+}
+
+impl SnapshotFilter {
+    /// Creates a filter that selects nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects the component `C`.
+    pub fn with_component<C: Component>(mut self) -> Self {
+        self.components.push((TypeId::of::<C>(), type_name::<C>()));
+        self
+    }
+
+    /// Selects the resource `R`.
+    pub fn with_resource<R: Resource>(mut self) -> Self {
+        self.resources.push((TypeId::of::<R>(), type_name::<R>()));
+        self
+    }
+
+    fn reflect_components(
+        &self,
+        registry: &TypeRegistry,
+    ) -> Result<Vec<(TypeId, ReflectComponent)>, SnapshotError> {
+        self.components
+            .iter()
+            .map(|&(type_id, name)| {
+                registry
+                    .get_type_data::<ReflectComponent>(type_id)
+                    .map(|reflect| (type_id, reflect.clone()))
+                    .ok_or(SnapshotError::UnregisteredComponent(name))
+            })
+            .collect()
+    }
+
+    fn reflect_resources(
+        &self,
+        registry: &TypeRegistry,
+    ) -> Result<Vec<(TypeId, ReflectResource)>, SnapshotError> {
+        self.resources
+            .iter()
+            .map(|&(type_id, name)| {
+                registry
+                    .get_type_data::<ReflectResource>(type_id)
+                    .map(|reflect| (type_id, reflect.clone()))
+                    .ok_or(SnapshotError::UnregisteredResource(name))
+            })
+            .collect()
+    }
+}
+
+/// An error that occurs when capturing or restoring a [`WorldSnapshot`].
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    /// A component selected by the [`SnapshotFilter`] is not registered with `ReflectComponent`.
+    #[error("The component `{0}` is not registered with `#[reflect(Component)]`.")]
+    UnregisteredComponent(&'static str),
+    /// A resource selected by the [`SnapshotFilter`] is not registered with `ReflectResource`.
+    #[error("The resource `{0}` is not registered with `#[reflect(Resource)]`.")]
+    UnregisteredResource(&'static str),
+}
+
+/// The captured components of one entity in a [`WorldSnapshot`].
+#[derive(Debug)]
+struct EntitySnapshot {
+    entity: Entity,
+    components: Vec<(TypeId, Box<dyn Reflect>)>,
+}
+
+/// A copy of part of a [`World`] at one point in time, which the world can be rolled back to.
+///
+/// A snapshot records every entity alive in the world, the state of the entity allocator, and
+/// the values of the components and resources selected by a [`SnapshotFilter`].
+/// [Restoring](WorldSnapshot::restore) it:
+/// - despawns the entities spawned since that have a selected component, and respawns the
+///   despawned ones with the same [`Entity`],
+/// - sets the selected components and resources back to their captured values, inserting and
+///   removing them as needed,
+/// - makes the entity allocator hand out the same [`Entity`] IDs as it did after the capture,
+///   so that resimulating the same frames spawns the same entities.
+///
+/// Components and resources that are not selected are left as they are on entities that
+/// survive the rollback. Entities spawned since without any selected component, such as
+/// observers, are kept, and their IDs are skipped by the allocator, unless they reuse the ID of a
+/// captured entity that needs to be respawned.
+///
+/// Only values that differ from the captured ones are written, so change detection reports the
+/// restored values as changed on the tick they were restored, while the rest keep their ticks.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent, SnapshotFilter, WorldSnapshot};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, Default)]
+/// #[reflect(Component)]
+/// struct Position(f32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Position>();
+///
+/// let player = world.spawn(Position(0.0)).id();
+/// let filter = SnapshotFilter::new().with_component::<Position>();
+/// let snapshot = WorldSnapshot::capture(&mut world, &filter).unwrap();
+///
+/// world.get_mut::<Position>(player).unwrap().0 = 10.0;
+/// let bullet = world.spawn(Position(5.0)).id();
+///
+/// snapshot.restore(&mut world).unwrap();
+/// assert_eq!(world.get::<Position>(player).unwrap().0, 0.0);
+/// assert!(world.get_entity(bullet).is_none());
+/// assert_eq!(world.spawn_empty().id(), bullet);
+/// ```
+#[derive(Debug)]
+pub struct WorldSnapshot {
+    filter: SnapshotFilter,
+    allocator: EntitiesAllocatorSnapshot,
+    entities: Vec<EntitySnapshot>,
+    resources: Vec<(TypeId, Box<dyn Reflect>)>,
+}
+
+impl WorldSnapshot {
+    /// Captures the entities of `world` and the components and resources selected by `filter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world does not contain an [`AppTypeRegistry`].
+    pub fn capture(world: &mut World, filter: &SnapshotFilter) -> Result<Self, SnapshotError> {
+        world.flush();
+        let allocator = world.entities.allocator_snapshot();
+
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let reflect_components = filter.reflect_components(&registry)?;
+        let reflect_resources = filter.reflect_resources(&registry)?;
+
+        let entities = world
+            .iter_entities()
+            .map(|entity| EntitySnapshot {
+                entity: entity.id(),
+                components: reflect_components
+                    .iter()
+                    .filter_map(|(type_id, reflect_component)| {
+                        let component = reflect_component.reflect(entity)?;
+                        Some((*type_id, component.clone_value()))
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let resources = reflect_resources
+            .iter()
+            .filter_map(|(type_id, reflect_resource)| {
+                let resource = reflect_resource.reflect(world)?;
+                Some((*type_id, resource.clone_value()))
+            })
+            .collect();
+
+        Ok(Self {
+            filter: filter.clone(),
+            allocator,
+            entities,
+            resources,
+        })
+    }
+
+    /// Returns the entities that were alive when the snapshot was captured.
+    pub fn entities(&self) -> impl ExactSizeIterator<Item = Entity> + '_ {
+        self.entities.iter().map(|snapshot| snapshot.entity)
+    }
+
+    /// Rolls `world` back to the captured state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world does not contain an [`AppTypeRegistry`].
+    pub fn restore(&self, world: &mut World) -> Result<(), SnapshotError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let reflect_components = self.filter.reflect_components(&registry)?;
+        let reflect_resources = self.filter.reflect_resources(&registry)?;
+
+        world.flush();
+        let alive = self.entities().collect::<HashSet<_>>();
+        let alive_indices = alive
+            .iter()
+            .map(|entity| entity.index())
+            .collect::<HashSet<_>>();
+        let spawned = world
+            .iter_entities()
+            .filter(|entity| {
+                !alive.contains(&entity.id())
+                    && (alive_indices.contains(&entity.id().index())
+                        || reflect_components
+                            .iter()
+                            .any(|(type_id, _)| entity.contains_type_id(*type_id)))
+            })
+            .map(|entity| entity.id())
+            .collect::<Vec<_>>();
+        for entity in spawned {
+            world.despawn(entity);
+        }
+
+        for snapshot in &self.entities {
+            let mut entity_mut = world
+                .get_or_spawn(snapshot.entity)
+                .expect("entities spawned after the snapshot should have been despawned");
+            for (type_id, reflect_component) in &reflect_components {
+                let captured = snapshot
+                    .components
+                    .iter()
+                    .find(|(id, _)| id == type_id)
+                    .map(|(_, component)| &**component);
+                let Some(captured) = captured else {
+                    reflect_component.remove(&mut entity_mut);
+                    continue;
+                };
+                if let Some(mut component) = reflect_component.reflect_mut(&mut entity_mut) {
+                    if component.reflect_partial_eq(captured) != Some(true) {
+                        component.apply(captured);
+                    }
+                } else {
+                    reflect_component.insert(&mut entity_mut, captured);
+                }
+            }
+        }
+
+        for (type_id, reflect_resource) in &reflect_resources {
+            let captured = self
+                .resources
+                .iter()
+                .find(|(id, _)| id == type_id)
+                .map(|(_, resource)| &**resource);
+            let Some(captured) = captured else {
+                reflect_resource.remove(world);
+                continue;
+            };
+            if let Some(mut resource) = reflect_resource.reflect_mut(world) {
+                if resource.reflect_partial_eq(captured) != Some(true) {
+                    resource.apply(captured);
+                }
+            } else {
+                reflect_resource.insert(world, captured);
+            }
+        }
+
+        world.flush();
+        world.entities.restore_allocator(&self.allocator);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_reflect::Reflect;
+
+    use super::*;
+    use crate::{
+        self as bevy_ecs,
+        prelude::{DetectChanges, Mut, ResMut},
+    };
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq, Clone, Copy)]
+    #[reflect(Component)]
+    struct Position(i32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Velocity(i32);
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    struct Untracked(i32);
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct FrameCount(u32);
+
+    fn setup() -> (World, SnapshotFilter) {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Position>();
+            registry.register::<Velocity>();
+            registry.register::<FrameCount>();
+        }
+        let filter = SnapshotFilter::new()
+            .with_component::<Position>()
+            .with_component::<Velocity>()
+            .with_resource::<FrameCount>();
+        (world, filter)
+    }
+
+    #[test]
+    fn restore_components_and_resources() {
+        let (mut world, filter) = setup();
+        world.insert_resource(FrameCount(1));
+        let a = world.spawn((Position(1), Untracked(1))).id();
+        let b = world.spawn((Position(2), Velocity(2))).id();
+
+        let snapshot = WorldSnapshot::capture(&mut world, &filter).unwrap();
+
+        world.resource_mut::<FrameCount>().0 = 2;
+        world.get_mut::<Position>(a).unwrap().0 = 10;
+        world.get_mut::<Untracked>(a).unwrap().0 = 10;
+        world.entity_mut(a).insert(Velocity(10));
+        world.entity_mut(b).remove::<Velocity>();
+
+        snapshot.restore(&mut world).unwrap();
+
+        assert_eq!(world.resource::<FrameCount>(), &FrameCount(1));
+        assert_eq!(world.get::<Position>(a), Some(&Position(1)));
+        assert_eq!(world.get::<Velocity>(a), None);
+        assert_eq!(world.get::<Untracked>(a), Some(&Untracked(10)));
+        assert_eq!(world.get::<Velocity>(b), Some(&Velocity(2)));
+
+        world.remove_resource::<FrameCount>();
+        snapshot.restore(&mut world).unwrap();
+        assert_eq!(world.resource::<FrameCount>(), &FrameCount(1));
+    }
+
+    #[test]
+    fn restore_entity_ids() {
+        let (mut world, filter) = setup();
+        let kept = world.spawn(Position(0)).id();
+        let despawned = world.spawn(Position(1)).id();
+        let freed = world.spawn_empty().id();
+        world.despawn(freed);
+
+        let snapshot = WorldSnapshot::capture(&mut world, &filter).unwrap();
+        let resimulate = |world: &mut World| {
+            world.despawn(despawned);
+            [world.spawn(Position(2)).id(), world.spawn(Position(3)).id()]
+        };
+        let spawned = resimulate(&mut world);
+
+        snapshot.restore(&mut world).unwrap();
+        assert!(spawned
+            .iter()
+            .all(|&entity| world.get_entity(entity).is_none()));
+        assert_eq!(world.get::<Position>(kept), Some(&Position(0)));
+        assert_eq!(world.get::<Position>(despawned), Some(&Position(1)));
+        assert_eq!(world.entities().len(), 2);
+
+        assert_eq!(resimulate(&mut world), spawned);
+    }
+
+    #[test]
+    fn restore_keeps_unselected_entities() {
+        use crate::{event::Event, observer::Trigger, system::In};
+
+        #[derive(Event)]
+        struct Ping;
+
+        #[derive(Resource, Default)]
+        struct Pings(u32);
+
+        let (mut world, filter) = setup();
+        world.init_resource::<Pings>();
+        let player = world.spawn(Position(0)).id();
+        let snapshot = WorldSnapshot::capture(&mut world, &filter).unwrap();
+
+        let observer = world
+            .observe(|_: In<Trigger<Ping>>, mut pings: ResMut<Pings>| pings.0 += 1)
+            .id();
+        let bullet = world.spawn(Position(1)).id();
+        let marker = world.spawn(Untracked(1)).id();
+        world.despawn(player);
+        // Reuses the index of `player`, which must be respawned.
+        let reused = world.spawn(Untracked(2)).id();
+        assert_eq!(reused.index(), player.index());
+
+        snapshot.restore(&mut world).unwrap();
+        assert!(world.get_entity(bullet).is_none());
+        assert!(world.get_entity(reused).is_none());
+        assert_eq!(world.get::<Position>(player), Some(&Position(0)));
+        assert_eq!(world.get::<Untracked>(marker), Some(&Untracked(1)));
+        assert!(world.get_entity(observer).is_some());
+
+        world.trigger(Ping);
+        assert_eq!(world.resource::<Pings>().0, 1);
+
+        // New entities don't reuse the IDs of the kept ones.
+        let spawned = [world.spawn_empty().id(), world.spawn_empty().id()];
+        assert_eq!(spawned[0], bullet);
+        assert!(!spawned.contains(&observer) && !spawned.contains(&marker));
+        assert_eq!(world.entities().len(), 5);
+    }
+
+    #[test]
+    fn restore_only_changes_modified_values() {
+        let (mut world, filter) = setup();
+        let a = world.spawn(Position(1)).id();
+        let b = world.spawn(Position(2)).id();
+        let snapshot = WorldSnapshot::capture(&mut world, &filter).unwrap();
+
+        world.get_mut::<Position>(b).unwrap().0 = 20;
+        world.clear_trackers();
+        snapshot.restore(&mut world).unwrap();
+
+        let changed = |world: &mut World, entity| {
+            let position: Mut<Position> = world.get_mut(entity).unwrap();
+            position.is_changed()
+        };
+        assert!(!changed(&mut world, a));
+        assert!(changed(&mut world, b));
+    }
+
+    #[test]
+    fn unregistered_types() {
+        let (mut world, _) = setup();
+        let filter = SnapshotFilter::new().with_component::<Untracked>();
+        assert!(matches!(
+            WorldSnapshot::capture(&mut world, &filter),
+            Err(SnapshotError::UnregisteredComponent(_))
+        ));
+    }
+}
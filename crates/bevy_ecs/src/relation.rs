@@ -0,0 +1,808 @@
+//! Generic relationships between entities.
+//!
+//! A relation links a *source* entity to one or more *target* entities. The links are stored in a
+//! [`RelationTargets<R>`] component on the source, and mirrored in a [`RelationSources<R>`]
+//! component on each target so that both directions can be looked up cheaply.
+//!
+//! Relations are created and removed through [`EntityMut::relate`], [`EntityMut::unrelate`] and
+//! their [`EntityCommands`](crate::system::EntityCommands) counterparts. Both components keep
+//! each other in sync through their [hooks](crate::component::ComponentHooks), so removing either
+//! of them by hand also removes the other side of the links. When an entity is despawned, it is
+//! removed from every relation it participates in, and its sources are handled according to
+//! [`Relation::DESPAWN_POLICY`].
+//!
+//! The entities related to a target can be queried with the [`Related`] system parameter.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! use bevy_ecs::relation::{Relation, RelationSources, RelationTargets};
+//!
+//! /// The source entity is being targeted by the target entity.
+//! struct TargetedBy;
+//!
+//! impl Relation for TargetedBy {}
+//!
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! let turret = world.spawn_empty().id();
+//! let player = world.spawn(Health(100)).id();
+//! world.entity_mut(player).relate::<TargetedBy>(turret);
+//!
+//! // Look up all the entities related to the turret.
+//! let sources = world.get::<RelationSources<TargetedBy>>(turret).unwrap();
+//! assert_eq!(sources.iter().collect::<Vec<_>>(), vec![player]);
+//!
+//! // The relation is cleaned up when the turret is despawned.
+//! world.despawn(turret);
+//! assert!(world.get::<RelationTargets<TargetedBy>>(player).is_none());
+//! ```
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentHooks, ComponentId, TableStorage},
+    entity::Entity,
+    query::{QueryManyIter, ROQueryItem, ReadOnlyWorldQuery, WorldQuery},
+    system::{Command, Query, SystemParam},
+    world::{EntityMut, World},
+};
+use std::{any::TypeId, marker::PhantomData, ops::Deref};
+
+/// A kind of relationship between a source [`Entity`] and its target entities.
+///
+/// Types implementing this trait are only used as markers: the relation data itself is stored in
+/// the [`RelationTargets`] and [`RelationSources`] components.
+pub trait Relation: Send + Sync + 'static {
+    /// What happens to the sources of this relation when one of their targets is despawned.
+    const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::Orphan;
+
+    /// If `true`, a source can only be related to a single target at a time.
+    /// Relating it to a new target removes the link to the previous one.
+    const EXCLUSIVE: bool = false;
+}
+
+/// Describes how the sources of a [`Relation`] are treated when their target is despawned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DespawnPolicy {
+    /// The link to the despawned target is removed, and the sources are kept alive.
+    Orphan,
+    /// The sources are despawned as well, recursively following their own relations.
+    DespawnSources,
+}
+
+/// The targets of a [`Relation`] `R`, stored on the source entity.
+///
+/// This component is maintained by [`EntityMut::relate`] and [`EntityMut::unrelate`] and cannot
+/// be constructed directly.
+pub struct RelationTargets<R: Relation> {
+    targets: Vec<Entity>,
+    marker: PhantomData<R>,
+}
+
+/// The sources of a [`Relation`] `R`, stored on the target entity.
+///
+/// This is the reverse index of [`RelationTargets`], and is kept in sync with it automatically.
+pub struct RelationSources<R: Relation> {
+    sources: Vec<Entity>,
+    marker: PhantomData<R>,
+}
+
+impl<R: Relation> Component for RelationTargets<R> {
+    type Storage = TableStorage;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks
+            .on_insert(link_targets::<R>)
+            .on_remove(unlink_targets::<R>);
+    }
+}
+
+impl<R: Relation> Component for RelationSources<R> {
+    type Storage = TableStorage;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks
+            .on_insert(link_sources::<R>)
+            .on_remove(unlink_sources::<R>);
+    }
+}
+
+impl<R: Relation> RelationTargets<R> {
+    fn new(target: Entity) -> Self {
+        Self {
+            targets: vec![target],
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the target entities.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entity> + ExactSizeIterator + '_ {
+        self.targets.iter().copied()
+    }
+}
+
+impl<R: Relation> RelationSources<R> {
+    fn new(source: Entity) -> Self {
+        Self {
+            sources: vec![source],
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the source entities.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = Entity> + ExactSizeIterator + '_ {
+        self.sources.iter().copied()
+    }
+}
+
+impl<R: Relation> Deref for RelationTargets<R> {
+    type Target = [Entity];
+
+    fn deref(&self) -> &Self::Target {
+        &self.targets
+    }
+}
+
+impl<R: Relation> Deref for RelationSources<R> {
+    type Target = [Entity];
+
+    fn deref(&self) -> &Self::Target {
+        &self.sources
+    }
+}
+
+impl<R: Relation> std::fmt::Debug for RelationTargets<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RelationTargets")
+            .field(&self.targets)
+            .finish()
+    }
+}
+
+impl<R: Relation> std::fmt::Debug for RelationSources<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RelationSources")
+            .field(&self.sources)
+            .finish()
+    }
+}
+
+/// Type-erased information about a [`Relation`] used when despawning entities.
+#[derive(Clone, Copy)]
+struct RelationInfo {
+    type_id: TypeId,
+    targets_id: ComponentId,
+    sources_id: ComponentId,
+    detach: fn(&mut World, Entity) -> Vec<Entity>,
+}
+
+/// Stores every [`Relation`] kind that has been used in a [`World`].
+#[derive(Default)]
+pub(crate) struct Relations {
+    infos: Vec<RelationInfo>,
+}
+
+impl World {
+    /// Registers the [`Relation`] `R` with this [`World`], so that it is cleaned up on despawn.
+    ///
+    /// This is done automatically by [`EntityMut::relate`].
+    pub fn init_relation<R: Relation>(&mut self) {
+        let type_id = TypeId::of::<R>();
+        if self
+            .relations
+            .infos
+            .iter()
+            .any(|info| info.type_id == type_id)
+        {
+            return;
+        }
+        let targets_id = self.init_component::<RelationTargets<R>>();
+        let sources_id = self.init_component::<RelationSources<R>>();
+        self.relations.infos.push(RelationInfo {
+            type_id,
+            targets_id,
+            sources_id,
+            detach: detach::<R>,
+        });
+    }
+
+    /// Removes `entity` from every relation it is a part of, applying each relation's
+    /// [`DespawnPolicy`] to its sources.
+    ///
+    /// Called before `entity` is despawned.
+    pub(crate) fn despawn_relations(&mut self, entity: Entity) {
+        if self.relations.infos.is_empty() {
+            return;
+        }
+        // Detach the entity from all of its relations before despawning anything, so that
+        // cascading despawns can never reach the entity being despawned.
+        let mut to_despawn = Vec::new();
+        for index in 0..self.relations.infos.len() {
+            let info = self.relations.infos[index];
+            let Some(location) = self.entities.get(entity) else {
+                return;
+            };
+            let archetype = &self.archetypes[location.archetype_id];
+            if archetype.contains(info.targets_id) || archetype.contains(info.sources_id) {
+                to_despawn.extend((info.detach)(self, entity));
+            }
+        }
+        for source in to_despawn {
+            if let Some(source) = self.get_entity_mut(source) {
+                source.despawn();
+            }
+        }
+    }
+}
+
+/// Removes `entity` from both sides of the relation `R`.
+///
+/// Returns the sources that should be despawned according to [`Relation::DESPAWN_POLICY`].
+fn detach<R: Relation>(world: &mut World, entity: Entity) -> Vec<Entity> {
+    // The hooks of both components remove the other side of the links.
+    let mut entity_mut = world.entity_mut(entity);
+    entity_mut.remove::<RelationTargets<R>>();
+    let sources = entity_mut
+        .take::<RelationSources<R>>()
+        .map(|sources| sources.sources)
+        .unwrap_or_default();
+
+    match R::DESPAWN_POLICY {
+        DespawnPolicy::Orphan => Vec::new(),
+        DespawnPolicy::DespawnSources => sources
+            .into_iter()
+            .filter(|source| *source != entity)
+            .collect(),
+    }
+}
+
+/// Links the source `entity` to the targets of its newly inserted [`RelationTargets<R>`].
+fn link_targets<R: Relation>(world: &mut World, entity: Entity, _: ComponentId) {
+    for target in targets_of::<R>(world, entity) {
+        if world.entities.contains(target) {
+            relate::<R>(world, entity, target);
+        } else {
+            remove_target::<R>(world, entity, target);
+        }
+    }
+}
+
+/// Unlinks the source `entity` from the targets of its [`RelationTargets<R>`] being removed.
+fn unlink_targets<R: Relation>(world: &mut World, entity: Entity, _: ComponentId) {
+    for target in targets_of::<R>(world, entity) {
+        remove_source::<R>(world, target, entity);
+    }
+}
+
+/// Links the target `entity` to the sources of its newly inserted [`RelationSources<R>`].
+fn link_sources<R: Relation>(world: &mut World, entity: Entity, _: ComponentId) {
+    for source in sources_of::<R>(world, entity) {
+        if world.entities.contains(source) {
+            relate::<R>(world, source, entity);
+        } else {
+            remove_source::<R>(world, entity, source);
+        }
+    }
+}
+
+/// Unlinks the target `entity` from the sources of its [`RelationSources<R>`] being removed.
+fn unlink_sources<R: Relation>(world: &mut World, entity: Entity, _: ComponentId) {
+    for source in sources_of::<R>(world, entity) {
+        remove_target::<R>(world, source, entity);
+    }
+}
+
+fn targets_of<R: Relation>(world: &World, source: Entity) -> Vec<Entity> {
+    world
+        .get::<RelationTargets<R>>(source)
+        .map(|targets| targets.targets.clone())
+        .unwrap_or_default()
+}
+
+fn sources_of<R: Relation>(world: &World, target: Entity) -> Vec<Entity> {
+    world
+        .get::<RelationSources<R>>(target)
+        .map(|sources| sources.sources.clone())
+        .unwrap_or_default()
+}
+
+fn remove_source<R: Relation>(world: &mut World, target: Entity, source: Entity) {
+    let Some(mut target) = world.get_entity_mut(target) else {
+        return;
+    };
+    let Some(mut sources) = target.get_mut::<RelationSources<R>>() else {
+        return;
+    };
+    sources.sources.retain(|entity| *entity != source);
+    if sources.sources.is_empty() {
+        target.remove::<RelationSources<R>>();
+    }
+}
+
+fn remove_target<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    let Some(mut source) = world.get_entity_mut(source) else {
+        return;
+    };
+    let Some(mut targets) = source.get_mut::<RelationTargets<R>>() else {
+        return;
+    };
+    targets.targets.retain(|entity| *entity != target);
+    if targets.targets.is_empty() {
+        source.remove::<RelationTargets<R>>();
+    }
+}
+
+fn relate<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    world.init_relation::<R>();
+    assert!(
+        world.entities.contains(target),
+        "Could not relate entity {source:?} to {target:?} because the target doesn't exist in this World."
+    );
+
+    // This is also called by the insertion hooks, so every step must be idempotent.
+    let mut source_mut = world.entity_mut(source);
+    let mut previous = Vec::new();
+    if let Some(mut targets) = source_mut.get_mut::<RelationTargets<R>>() {
+        if !targets.targets.contains(&target) {
+            if R::EXCLUSIVE {
+                previous = std::mem::replace(&mut targets.targets, vec![target]);
+            } else {
+                targets.targets.push(target);
+            }
+        }
+    } else {
+        source_mut.insert(RelationTargets::<R>::new(target));
+    }
+    for previous in previous {
+        remove_source::<R>(world, previous, source);
+    }
+
+    let mut target_mut = world.entity_mut(target);
+    if let Some(mut sources) = target_mut.get_mut::<RelationSources<R>>() {
+        if !sources.sources.contains(&source) {
+            sources.sources.push(source);
+        }
+    } else {
+        target_mut.insert(RelationSources::<R>::new(source));
+    }
+}
+
+fn unrelate<R: Relation>(world: &mut World, source: Entity, target: Entity) {
+    remove_target::<R>(world, source, target);
+    remove_source::<R>(world, target, source);
+}
+
+impl<'w> EntityMut<'w> {
+    /// Relates this entity to `target` through the [`Relation`] `R`.
+    ///
+    /// If `R` is [exclusive](Relation::EXCLUSIVE), any previous target of this entity is replaced.
+    /// Relating an entity to a target it is already related to does nothing.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `target` does not exist.
+    pub fn relate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| relate::<R>(world, source, target));
+        self
+    }
+
+    /// Removes the [`Relation`] `R` between this entity and `target`, if it exists.
+    pub fn unrelate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| unrelate::<R>(world, source, target));
+        self
+    }
+
+    /// Removes all the targets of the [`Relation`] `R` from this entity.
+    pub fn unrelate_all<R: Relation>(&mut self) -> &mut Self {
+        let source = self.id();
+        self.world_scope(|world| {
+            world.entity_mut(source).remove::<RelationTargets<R>>();
+        });
+        self
+    }
+}
+
+/// A [`SystemParam`] that queries the sources of a [`Relation`] `R`, i.e. the entities related
+/// to a given target.
+///
+/// `Q` and `F` are the data and filter of the query applied to the sources.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::relation::{Related, Relation};
+///
+/// struct MemberOf;
+///
+/// impl Relation for MemberOf {}
+///
+/// #[derive(Component)]
+/// struct Name(&'static str);
+///
+/// #[derive(Component)]
+/// struct Guild;
+///
+/// fn list_members(guilds: Query<Entity, With<Guild>>, members: Related<MemberOf, &Name>) {
+///     for guild in &guilds {
+///         for name in members.iter(guild) {
+///             println!("{}", name.0);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(list_members);
+/// ```
+#[derive(SystemParam)]
+pub struct Related<'w, 's, R, Q, F = ()>
+where
+    R: Relation,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    sources: Query<'w, 's, &'static RelationSources<R>>,
+    query: Query<'w, 's, Q, F>,
+}
+
+impl<'w, 's, R, Q, F> Related<'w, 's, R, Q, F>
+where
+    R: Relation,
+    Q: WorldQuery + 'static,
+    F: ReadOnlyWorldQuery + 'static,
+{
+    /// Returns an iterator over the read-only query items of the sources related to `target`.
+    ///
+    /// Sources that don't match the query are skipped.
+    pub fn iter(
+        &self,
+        target: Entity,
+    ) -> QueryManyIter<'_, 's, Q::ReadOnly, F::ReadOnly, std::slice::Iter<'_, Entity>> {
+        self.query.iter_many(self.sources_of(target))
+    }
+
+    /// Returns an iterator over the query items of the sources related to `target`.
+    ///
+    /// Sources that don't match the query are skipped. Use
+    /// [`QueryManyIter::fetch_next`] to get the items.
+    pub fn iter_mut(
+        &mut self,
+        target: Entity,
+    ) -> QueryManyIter<'_, 's, Q, F, std::slice::Iter<'_, Entity>> {
+        let sources = self
+            .sources
+            .get(target)
+            .map_or(&[][..], |sources| &sources.sources);
+        self.query.iter_many_mut(sources)
+    }
+
+    /// Returns the query item of `source` if it is related to `target` and matches the query.
+    pub fn get(&self, target: Entity, source: Entity) -> Option<ROQueryItem<'_, Q>> {
+        if self.sources_of(target).contains(&source) {
+            self.query.get(source).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if `source` is related to `target`, even if it doesn't match the query.
+    pub fn contains(&self, target: Entity, source: Entity) -> bool {
+        self.sources_of(target).contains(&source)
+    }
+
+    fn sources_of(&self, target: Entity) -> &[Entity] {
+        self.sources
+            .get(target)
+            .map_or(&[], |sources| &sources.sources)
+    }
+}
+
+/// A [`Command`] that relates an entity to a target through the [`Relation`] `R`.
+#[derive(Debug)]
+pub struct Relate<R: Relation> {
+    /// The source entity of the relation.
+    pub source: Entity,
+    /// The target entity of the relation.
+    pub target: Entity,
+    marker: PhantomData<R>,
+}
+
+impl<R: Relation> Relate<R> {
+    /// Creates a [`Command`] which will relate `source` to `target` when applied.
+    pub const fn new(source: Entity, target: Entity) -> Self {
+        Self {
+            source,
+            target,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relation> Command for Relate<R> {
+    fn apply(self, world: &mut World) {
+        if world.entities.contains(self.source) {
+            relate::<R>(world, self.source, self.target);
+        } else {
+            panic!("error[B0003]: Could not relate entity {:?} because it doesn't exist in this World.", self.source);
+        }
+    }
+}
+
+/// A [`Command`] that removes the [`Relation`] `R` between two entities.
+#[derive(Debug)]
+pub struct Unrelate<R: Relation> {
+    /// The source entity of the relation.
+    pub source: Entity,
+    /// The target entity of the relation.
+    pub target: Entity,
+    marker: PhantomData<R>,
+}
+
+impl<R: Relation> Unrelate<R> {
+    /// Creates a [`Command`] which will remove the relation between `source` and `target` when applied.
+    pub const fn new(source: Entity, target: Entity) -> Self {
+        Self {
+            source,
+            target,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<R: Relation> Command for Unrelate<R> {
+    fn apply(self, world: &mut World) {
+        unrelate::<R>(world, self.source, self.target);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DespawnPolicy, Related, Relation, RelationSources, RelationTargets};
+    use crate as bevy_ecs;
+    use crate::{prelude::*, system::CommandQueue};
+
+    struct Likes;
+
+    impl Relation for Likes {}
+
+    struct OwnedBy;
+
+    impl Relation for OwnedBy {
+        const DESPAWN_POLICY: DespawnPolicy = DespawnPolicy::DespawnSources;
+        const EXCLUSIVE: bool = true;
+    }
+
+    fn targets<R: Relation>(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<RelationTargets<R>>(entity)
+            .map(|targets| targets.to_vec())
+            .unwrap_or_default()
+    }
+
+    fn sources<R: Relation>(world: &World, entity: Entity) -> Vec<Entity> {
+        world
+            .get::<RelationSources<R>>(entity)
+            .map(|sources| sources.to_vec())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn relate_and_unrelate() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+
+        world.entity_mut(a).relate::<Likes>(b).relate::<Likes>(c);
+        world.entity_mut(b).relate::<Likes>(c);
+        // Relating twice is a no-op.
+        world.entity_mut(a).relate::<Likes>(b);
+
+        assert_eq!(targets::<Likes>(&world, a), vec![b, c]);
+        assert_eq!(sources::<Likes>(&world, c), vec![a, b]);
+
+        world.entity_mut(a).unrelate::<Likes>(c);
+        assert_eq!(targets::<Likes>(&world, a), vec![b]);
+        assert_eq!(sources::<Likes>(&world, c), vec![b]);
+
+        world.entity_mut(a).unrelate_all::<Likes>();
+        assert!(!world.entity(a).contains::<RelationTargets<Likes>>());
+        assert!(!world.entity(b).contains::<RelationSources<Likes>>());
+    }
+
+    #[test]
+    fn removing_either_side_by_hand_unlinks() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+
+        world.entity_mut(a).relate::<Likes>(b).relate::<Likes>(c);
+        world.entity_mut(b).relate::<Likes>(c);
+
+        world.entity_mut(a).remove::<RelationTargets<Likes>>();
+        assert!(sources::<Likes>(&world, b).is_empty());
+        assert_eq!(sources::<Likes>(&world, c), vec![b]);
+
+        world.entity_mut(c).remove::<RelationSources<Likes>>();
+        assert!(targets::<Likes>(&world, b).is_empty());
+    }
+
+    #[test]
+    fn moving_targets_relinks() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+        let target = world.spawn_empty().id();
+
+        world
+            .entity_mut(a)
+            .relate::<Likes>(target)
+            .relate::<Likes>(c);
+        world.despawn(c);
+        let relation = world
+            .entity_mut(a)
+            .take::<RelationTargets<Likes>>()
+            .unwrap();
+        assert!(sources::<Likes>(&world, target).is_empty());
+
+        // Despawned targets are dropped when the component is inserted again.
+        world.entity_mut(b).insert(relation);
+        assert_eq!(targets::<Likes>(&world, b), vec![target]);
+        assert_eq!(sources::<Likes>(&world, target), vec![b]);
+    }
+
+    #[test]
+    fn clear_entities_despawns_sources() {
+        #[derive(Component)]
+        struct Marker;
+
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        world.spawn(Marker).relate::<OwnedBy>(owner);
+        world.spawn(Marker).relate::<Likes>(owner);
+
+        world.clear_entities();
+        assert_eq!(world.entities().len(), 0);
+        assert_eq!(world.query::<&Marker>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn exclusive_relation_replaces_target() {
+        let mut world = World::new();
+        let item = world.spawn_empty().id();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+
+        world.entity_mut(item).relate::<OwnedBy>(first);
+        world.entity_mut(item).relate::<OwnedBy>(second);
+
+        assert_eq!(targets::<OwnedBy>(&world, item), vec![second]);
+        assert!(sources::<OwnedBy>(&world, first).is_empty());
+        assert_eq!(sources::<OwnedBy>(&world, second), vec![item]);
+    }
+
+    #[test]
+    fn despawn_orphans_sources() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn_empty().id();
+
+        world.entity_mut(a).relate::<Likes>(b);
+        world.entity_mut(b).relate::<Likes>(c);
+
+        world.despawn(b);
+        assert!(world.get_entity(a).is_some());
+        assert!(targets::<Likes>(&world, a).is_empty());
+        assert!(sources::<Likes>(&world, c).is_empty());
+    }
+
+    #[test]
+    fn despawn_sources_recursively() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let item = world.spawn_empty().id();
+        let gem = world.spawn_empty().id();
+        let bystander = world.spawn_empty().id();
+
+        world.entity_mut(item).relate::<OwnedBy>(owner);
+        world.entity_mut(gem).relate::<OwnedBy>(item);
+        world.entity_mut(bystander).relate::<Likes>(item);
+
+        world.despawn(owner);
+        assert!(world.get_entity(item).is_none());
+        assert!(world.get_entity(gem).is_none());
+        assert!(world.get_entity(bystander).is_some());
+        assert!(targets::<Likes>(&world, bystander).is_empty());
+    }
+
+    #[test]
+    fn despawn_cycle() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+
+        world.entity_mut(a).relate::<OwnedBy>(b);
+        world.entity_mut(b).relate::<OwnedBy>(a);
+        world.entity_mut(a).relate::<Likes>(a);
+
+        world.despawn(a);
+        assert!(world.get_entity(a).is_none());
+        assert!(world.get_entity(b).is_none());
+    }
+
+    #[test]
+    fn relate_commands() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(a).relate::<Likes>(b);
+        queue.apply(&mut world);
+        assert_eq!(targets::<Likes>(&world, a), vec![b]);
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.entity(a).unrelate::<Likes>(b);
+        queue.apply(&mut world);
+        assert!(targets::<Likes>(&world, a).is_empty());
+        assert!(sources::<Likes>(&world, b).is_empty());
+    }
+
+    #[test]
+    fn query_related_entities() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Value(u32);
+
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        world.spawn(Value(1)).relate::<Likes>(target);
+        world.spawn(Value(2));
+        world.spawn(Value(3)).relate::<Likes>(target);
+
+        let mut query = world.query::<&Value>();
+        let sources = world.get::<RelationSources<Likes>>(target).unwrap();
+        let values: Vec<_> = query.iter_many(&world, sources.iter()).collect();
+        assert_eq!(values, vec![&Value(1), &Value(3)]);
+    }
+
+    #[test]
+    fn related_system_param() {
+        #[derive(Component, PartialEq, Debug)]
+        struct Value(u32);
+
+        #[derive(Resource)]
+        struct Target(Entity);
+
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        world.insert_resource(Target(target));
+        let first = world.spawn(Value(1)).relate::<Likes>(target).id();
+        world.spawn(Value(2)).relate::<Likes>(other);
+        world.spawn_empty().relate::<Likes>(target);
+        world.spawn(Value(3)).relate::<Likes>(target);
+
+        fn double(target: Res<Target>, mut related: Related<Likes, &mut Value>) {
+            let mut values = related.iter_mut(target.0);
+            while let Some(mut value) = values.fetch_next() {
+                value.0 *= 2;
+            }
+        }
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(double);
+        schedule.run(&mut world);
+
+        let mut state = bevy_ecs::system::SystemState::<Related<Likes, &Value>>::new(&mut world);
+        let related = state.get(&world);
+        let values: Vec<_> = related.iter(target).collect();
+        assert_eq!(values, vec![&Value(2), &Value(6)]);
+        assert_eq!(related.get(target, first), Some(&Value(2)));
+        assert!(related.get(other, first).is_none());
+        assert!(related.contains(target, first));
+        assert!(related.iter(first).next().is_none());
+    }
+}
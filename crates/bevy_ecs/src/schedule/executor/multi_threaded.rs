@@ -102,6 +102,8 @@ pub struct MultiThreadedExecutor {
     running_systems: FixedBitSet,
     /// Systems that got skipped.
     skipped_systems: FixedBitSet,
+    /// Systems that are skipped without evaluating their conditions, such as by stepping.
+    skip_systems: FixedBitSet,
     /// Systems whose conditions have been evaluated and were run or skipped.
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
@@ -144,6 +146,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.running_systems = FixedBitSet::with_capacity(sys_count);
         self.completed_systems = FixedBitSet::with_capacity(sys_count);
         self.skipped_systems = FixedBitSet::with_capacity(sys_count);
+        self.skip_systems = FixedBitSet::with_capacity(sys_count);
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
 
         self.system_task_metadata = Vec::with_capacity(sys_count);
@@ -159,7 +162,12 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.num_dependencies_remaining = Vec::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
         // reset counts
         self.num_systems = schedule.systems.len();
         if self.num_systems == 0 {
@@ -177,6 +185,12 @@ impl SystemExecutor for MultiThreadedExecutor {
             }
         }
 
+        if let Some(skip_systems) = skip_systems {
+            // skipped systems complete without running or evaluating their conditions
+            // once their dependencies have completed
+            self.skip_systems.union_with(skip_systems);
+        }
+
         let thread_executor = world
             .get_resource::<MainThreadExecutor>()
             .map(|e| e.0.clone());
@@ -251,6 +265,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         self.active_access.clear();
         self.evaluated_sets.clear();
         self.skipped_systems.clear();
+        self.skip_systems.clear();
         self.completed_systems.clear();
     }
 }
@@ -277,6 +292,7 @@ impl MultiThreadedExecutor {
             ready_systems_copy: FixedBitSet::new(),
             running_systems: FixedBitSet::new(),
             skipped_systems: FixedBitSet::new(),
+            skip_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
             apply_final_deferred: true,
@@ -308,6 +324,12 @@ impl MultiThreadedExecutor {
 
         for system_index in ready_systems.ones() {
             assert!(!self.running_systems.contains(system_index));
+            if self.skip_systems.contains(system_index) {
+                self.ready_systems.set(system_index, false);
+                self.skip_system_and_signal_dependents(system_index);
+                continue;
+            }
+
             // SAFETY: Caller assured that these systems are not running.
             // Therefore, no other reference to this system exists and there is no aliasing.
             let system = unsafe { &mut *systems[system_index].get() };
@@ -42,8 +42,19 @@ impl SystemExecutor for SingleThreadedExecutor {
         self.unapplied_systems = FixedBitSet::with_capacity(sys_count);
     }
 
-    fn run(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        skip_systems: Option<&FixedBitSet>,
+        world: &mut World,
+    ) {
         for system_index in 0..schedule.systems.len() {
+            // skipped systems complete without running or evaluating their conditions
+            if skip_systems.is_some_and(|skip_systems| skip_systems.contains(system_index)) {
+                self.completed_systems.insert(system_index);
+                continue;
+            }
+
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
             #[cfg(feature = "trace")]
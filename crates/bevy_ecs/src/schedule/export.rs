@@ -0,0 +1,348 @@
+use std::fmt::Write;
+
+use bevy_utils::get_short_name;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    component::Components,
+    schedule::{Dag, NodeId, ScheduleGraph, ScheduleLabel, SystemSchedule},
+};
+
+/// A serializable description of a built [`Schedule`](super::Schedule): its systems, sets,
+/// ordering edges, run conditions, ambiguities and the order its systems run in.
+///
+/// Nodes are referred to by string ids, `"system{index}"` for systems and `"set{index}"` for
+/// sets, which stay the same as long as systems and sets are added in the same order.
+///
+/// Created with [`Schedule::export`](super::Schedule::export). It can be serialized with any
+/// [`serde`] format, such as JSON, or rendered as Graphviz DOT with [`ScheduleExport::to_dot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleExport {
+    /// The label of the schedule, if it has been inserted into [`Schedules`](super::Schedules).
+    pub label: Option<String>,
+    /// The systems of the schedule, in the order they were added.
+    pub systems: Vec<SystemExport>,
+    /// The system sets of the schedule, in the order they were added.
+    ///
+    /// This includes the sets created for each system type, which `before` and `after`
+    /// constraints on systems are attached to, and the anonymous sets of tuples of systems.
+    pub sets: Vec<SystemSetExport>,
+    /// Edges from a set to the systems and sets it contains.
+    pub hierarchy: Vec<EdgeExport>,
+    /// Edges from a system or set to a system or set that has to run after it.
+    pub dependencies: Vec<EdgeExport>,
+    /// Pairs of systems with conflicting access and no ordering between them.
+    pub ambiguities: Vec<AmbiguityExport>,
+    /// The ids of the systems, in the order they run in when run one at a time.
+    pub topological_order: Vec<String>,
+}
+
+/// A system in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemExport {
+    /// The id of the system in the exported graph.
+    pub id: String,
+    /// The name of the system.
+    pub name: String,
+    /// The names of the run conditions of the system.
+    pub conditions: Vec<String>,
+    /// Is `true` if the system requires exclusive [`World`](crate::world::World) access.
+    pub is_exclusive: bool,
+}
+
+/// A system set in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SystemSetExport {
+    /// The id of the set in the exported graph.
+    pub id: String,
+    /// The name of the set.
+    pub name: String,
+    /// The names of the run conditions of the set.
+    pub conditions: Vec<String>,
+    /// Is `true` if this is the set of all systems of a given type.
+    pub is_system_type: bool,
+    /// Is `true` if this set was created for a tuple of systems.
+    pub is_anonymous: bool,
+}
+
+/// A directed edge in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeExport {
+    /// The id of the node the edge starts at.
+    pub from: String,
+    /// The id of the node the edge points to.
+    pub to: String,
+}
+
+/// An ambiguity between two systems in a [`ScheduleExport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmbiguityExport {
+    /// The id of the first system.
+    pub first: String,
+    /// The id of the second system.
+    pub second: String,
+    /// The names of the components and resources both systems access, at least one of them
+    /// mutably. Empty if the systems conflict on [`World`](crate::world::World) access.
+    pub conflicts: Vec<String>,
+}
+
+impl ScheduleExport {
+    pub(super) fn new(
+        label: Option<&dyn ScheduleLabel>,
+        graph: &ScheduleGraph,
+        schedule: &SystemSchedule,
+        components: &Components,
+    ) -> Self {
+        // the built schedule holds the systems and the conditions that are evaluated,
+        // the graph keeps everything else
+        let mut systems = schedule
+            .system_ids
+            .iter()
+            .zip(&schedule.systems)
+            .zip(&schedule.system_conditions)
+            .map(|((&id, system), conditions)| {
+                let export = SystemExport {
+                    id: node_key(id),
+                    name: system.name().into_owned(),
+                    conditions: conditions.iter().map(|c| c.name().into_owned()).collect(),
+                    is_exclusive: system.is_exclusive(),
+                };
+                (id, export)
+            })
+            .collect::<Vec<_>>();
+        systems.sort_by_key(|(id, _)| *id);
+
+        let mut sets = graph
+            .system_sets()
+            .map(|(id, set, conditions)| {
+                let conditions = match schedule.set_ids.iter().position(|&set_id| set_id == id) {
+                    Some(index) => &schedule.set_conditions[index],
+                    None => conditions,
+                };
+                let export = SystemSetExport {
+                    id: node_key(id),
+                    name: format!("{set:?}"),
+                    conditions: conditions.iter().map(|c| c.name().into_owned()).collect(),
+                    is_system_type: set.system_type().is_some(),
+                    is_anonymous: set.is_anonymous(),
+                };
+                (id, export)
+            })
+            .collect::<Vec<_>>();
+        sets.sort_by_key(|(id, _)| *id);
+
+        let edges = |dag: &Dag| {
+            let mut edges = dag.graph().all_edges().collect::<Vec<_>>();
+            edges.sort();
+            edges
+                .into_iter()
+                .map(|(from, to, _)| EdgeExport {
+                    from: node_key(from),
+                    to: node_key(to),
+                })
+                .collect()
+        };
+
+        let ambiguities = graph
+            .conflicting_systems()
+            .iter()
+            .map(|(first, second, conflicts)| AmbiguityExport {
+                first: node_key(*first),
+                second: node_key(*second),
+                conflicts: conflicts
+                    .iter()
+                    .filter_map(|&id| components.get_name(id))
+                    .map(str::to_string)
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            label: label.map(|label| format!("{label:?}")),
+            systems: systems.into_iter().map(|(_, export)| export).collect(),
+            sets: sets.into_iter().map(|(_, export)| export).collect(),
+            hierarchy: edges(graph.hierarchy()),
+            dependencies: edges(graph.dependency()),
+            ambiguities,
+            topological_order: schedule.system_ids.iter().map(|&id| node_key(id)).collect(),
+        }
+    }
+
+    /// Renders the schedule as a Graphviz DOT digraph.
+    ///
+    /// Systems are drawn as boxes and sets as dashed ellipses, labeled with their short names
+    /// and run conditions. Solid edges are ordering dependencies, dashed edges point from a set
+    /// to its members, and dotted red edges connect ambiguous systems.
+    pub fn to_dot(&self) -> String {
+        let name = self.label.as_deref().unwrap_or("schedule");
+        let mut dot = String::new();
+        // writing to a `String` can't fail
+        let _ = writeln!(dot, "digraph {} {{", quote(name));
+        let _ = writeln!(dot, "\tnode [shape=box];");
+        for system in &self.systems {
+            let label = node_label(&system.name, &system.conditions);
+            let _ = writeln!(dot, "\t{} [label={}];", quote(&system.id), quote(&label));
+        }
+        for set in &self.sets {
+            let label = node_label(&set.name, &set.conditions);
+            let _ = writeln!(
+                dot,
+                "\t{} [label={}, shape=ellipse, style=dashed];",
+                quote(&set.id),
+                quote(&label)
+            );
+        }
+        for edge in &self.hierarchy {
+            let _ = writeln!(
+                dot,
+                "\t{} -> {} [style=dashed, arrowhead=empty];",
+                quote(&edge.from),
+                quote(&edge.to)
+            );
+        }
+        for edge in &self.dependencies {
+            let _ = writeln!(dot, "\t{} -> {};", quote(&edge.from), quote(&edge.to));
+        }
+        for ambiguity in &self.ambiguities {
+            let conflicts = ambiguity
+                .conflicts
+                .iter()
+                .map(|name| get_short_name(name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(
+                dot,
+                "\t{} -> {} [dir=none, style=dotted, color=red, label={}];",
+                quote(&ambiguity.first),
+                quote(&ambiguity.second),
+                quote(&conflicts)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+fn node_key(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system{index}"),
+        NodeId::Set(index) => format!("set{index}"),
+    }
+}
+
+fn node_label(name: &str, conditions: &[String]) -> String {
+    let mut label = get_short_name(name);
+    for condition in conditions {
+        label.push_str("\nif ");
+        label.push_str(&get_short_name(condition));
+    }
+    label
+}
+
+/// Quotes `text` as a DOT string.
+fn quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        prelude::*,
+        schedule::{ScheduleLabel, Schedules},
+    };
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Update;
+
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Physics;
+
+    #[derive(Resource)]
+    struct Counter;
+
+    fn read_counter(_counter: Res<Counter>) {}
+    fn write_counter(_counter: ResMut<Counter>) {}
+    fn also_write_counter(_counter: ResMut<Counter>) {}
+
+    fn export(world: &mut World) -> super::ScheduleExport {
+        world.schedule_scope(Update, |world, schedule| schedule.export(world).unwrap())
+    }
+
+    #[test]
+    fn export_schedule() {
+        let mut world = World::new();
+        let mut schedules = Schedules::new();
+        let mut schedule = Schedule::new();
+        schedule
+            .configure_set(Physics.run_if(|| true))
+            .add_systems((
+                read_counter.before(write_counter),
+                write_counter.in_set(Physics),
+                also_write_counter,
+            ));
+        schedules.insert(Update, schedule);
+        world.insert_resource(schedules);
+        world.insert_resource(Counter);
+
+        let export = export(&mut world);
+        assert_eq!(export.label.as_deref(), Some("Update"));
+
+        let names = export
+            .systems
+            .iter()
+            .map(|system| bevy_utils::get_short_name(&system.name))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["read_counter", "write_counter", "also_write_counter"]
+        );
+        let id_of = |name: &str| {
+            let index = names.iter().position(|n| n == name).unwrap();
+            export.systems[index].id.clone()
+        };
+
+        let physics = export
+            .sets
+            .iter()
+            .find(|set| set.name == "Physics")
+            .unwrap();
+        assert_eq!(physics.conditions.len(), 1);
+        assert!(export
+            .hierarchy
+            .iter()
+            .any(|edge| edge.from == physics.id && edge.to == id_of("write_counter")));
+
+        let order = &export.topological_order;
+        let position = |name: &str| order.iter().position(|id| *id == id_of(name)).unwrap();
+        assert!(position("read_counter") < position("write_counter"));
+
+        // also_write_counter is unordered with both other systems
+        assert_eq!(export.ambiguities.len(), 2);
+        for ambiguity in &export.ambiguities {
+            assert!(
+                ambiguity.first == id_of("also_write_counter")
+                    || ambiguity.second == id_of("also_write_counter")
+            );
+            assert_eq!(ambiguity.conflicts.len(), 1);
+            assert!(ambiguity.conflicts[0].ends_with("Counter"));
+        }
+
+        let dot = export.to_dot();
+        assert!(dot.starts_with("digraph \"Update\" {"));
+        assert!(dot.contains("label=\"write_counter\"]"));
+        assert!(dot.contains("label=\"Physics\\nif "));
+        assert!(dot.contains("[dir=none, style=dotted, color=red, label=\"Counter\"]"));
+    }
+}
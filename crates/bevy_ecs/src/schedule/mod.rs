@@ -3,19 +3,23 @@
 mod condition;
 mod config;
 mod executor;
+mod export;
 mod graph_utils;
 #[allow(clippy::module_inception)]
 mod schedule;
 mod set;
 mod state;
+mod stepping;
 
 pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
+pub use self::export::*;
 use self::graph_utils::*;
 pub use self::schedule::*;
 pub use self::set::*;
 pub use self::state::*;
+pub use self::stepping::*;
 
 pub use self::graph_utils::NodeId;
 
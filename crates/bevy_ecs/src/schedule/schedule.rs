@@ -41,8 +41,13 @@ impl Schedules {
     ///
     /// If the map already had an entry for `label`, `schedule` is inserted,
     /// and the old schedule is returned. Otherwise, `None` is returned.
-    pub fn insert(&mut self, label: impl ScheduleLabel, schedule: Schedule) -> Option<Schedule> {
+    pub fn insert(
+        &mut self,
+        label: impl ScheduleLabel,
+        mut schedule: Schedule,
+    ) -> Option<Schedule> {
         let label = label.dyn_clone();
+        schedule.label = Some(label.dyn_clone());
         self.inner.insert(label, schedule)
     }
 
@@ -151,6 +156,7 @@ fn make_executor(kind: ExecutorKind) -> Box<dyn SystemExecutor> {
 /// }
 /// ```
 pub struct Schedule {
+    label: Option<BoxedScheduleLabel>,
     graph: ScheduleGraph,
     executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
@@ -167,6 +173,7 @@ impl Schedule {
     /// Constructs an empty `Schedule`.
     pub fn new() -> Self {
         Self {
+            label: None,
             graph: ScheduleGraph::new(),
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
@@ -174,6 +181,11 @@ impl Schedule {
         }
     }
 
+    /// Returns the label of this schedule, once it has been inserted into [`Schedules`].
+    pub fn label(&self) -> Option<&dyn ScheduleLabel> {
+        self.label.as_deref()
+    }
+
     /// Add a collection of systems to the schedule.
     pub fn add_systems<M>(&mut self, systems: impl IntoSystemConfigs<M>) -> &mut Self {
         self.graph.add_systems_inner(systems.into_configs(), false);
@@ -222,10 +234,20 @@ impl Schedule {
     }
 
     /// Runs all systems in this schedule on the `world`, using its current execution strategy.
+    ///
+    /// If the schedule is being stepped through by the [`Stepping`] resource, only the systems
+    /// it allows are run.
     pub fn run(&mut self, world: &mut World) {
         world.check_change_ticks();
         self.initialize(world).unwrap_or_else(|e| panic!("{e}"));
-        self.executor.run(&mut self.executable, world);
+
+        let skip_systems = self.label.as_deref().and_then(|label| {
+            world
+                .get_resource_mut::<Stepping>()
+                .and_then(|mut stepping| stepping.skipped_systems(label, &self.executable))
+        });
+        self.executor
+            .run(&mut self.executable, skip_systems.as_ref(), world);
     }
 
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
@@ -249,6 +271,33 @@ impl Schedule {
         Ok(())
     }
 
+    /// Builds the schedule if needed and exports its systems, sets, ordering, run conditions
+    /// and ambiguities as a [`ScheduleExport`], which can be serialized or rendered as
+    /// Graphviz DOT.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// fn hello() {}
+    /// fn goodbye() {}
+    ///
+    /// let mut world = World::new();
+    /// let mut schedule = Schedule::new();
+    /// schedule.add_systems((hello, goodbye).chain());
+    ///
+    /// let export = schedule.export(&mut world).unwrap();
+    /// assert_eq!(export.topological_order, ["system0", "system1"]);
+    /// assert!(export.to_dot().contains("\"system0\" -> \"system1\""));
+    /// ```
+    pub fn export(&mut self, world: &mut World) -> Result<ScheduleExport, ScheduleBuildError> {
+        self.initialize(world)?;
+        Ok(ScheduleExport::new(
+            self.label(),
+            &self.graph,
+            &self.executable,
+            world.components(),
+        ))
+    }
+
     /// Returns the [`ScheduleGraph`].
     pub fn graph(&self) -> &ScheduleGraph {
         &self.graph
@@ -1,13 +1,18 @@
+use std::any::TypeId;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::mem;
 use std::ops::Deref;
 
 use crate as bevy_ecs;
 use crate::change_detection::DetectChangesMut;
-use crate::schedule::ScheduleLabel;
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::schedule::{IntoSystemSetConfig, ScheduleLabel, SystemSet, SystemSetConfig};
 use crate::system::Resource;
 use crate::world::World;
+use bevy_utils::all_tuples;
 
 pub use bevy_ecs_macros::States;
 
@@ -120,18 +125,50 @@ impl<S: States> NextState<S> {
     }
 }
 
+/// The [`SystemSet`] of the [`apply_state_transition::<S>`] system added by `App::add_state` and
+/// `App::add_sub_state`.
+///
+/// The sets of computed and sub-states are ordered after the sets of their source states with
+/// [`ApplyStateTransition::after_states`], so that a sub-state applies its queued transition
+/// after its source states have applied theirs.
+#[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ApplyStateTransition<S: States>(PhantomData<S>);
+
+impl<S: States> Default for ApplyStateTransition<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: States> ApplyStateTransition<S> {
+    /// Returns the configuration of this set, ordered after the sets of the states in `T`.
+    pub fn after_states<T: StateSet>() -> SystemSetConfig {
+        T::after_transitions(Self::default().into_config())
+    }
+}
+
 /// Run the enter schedule (if it exists) for the current state.
+///
+/// This also enters the initial value of the [computed](ComputedStates) and [sub-states](SubStates)
+/// that depend on `S`, after `S` itself.
 pub fn run_enter_schedule<S: States>(world: &mut World) {
-    world
-        .try_run_schedule(OnEnter(world.resource::<State<S>>().0.clone()))
-        .ok();
+    let entered = world.resource::<State<S>>().0.clone();
+    let mut transitions = Transitions::default();
+    transitions.record::<S>(world, None, Some(entered));
+    transitions.apply(world);
 }
 
 /// If a new state is queued in [`NextState<S>`], this system:
 /// - Takes the new state value from [`NextState<S>`] and updates [`State<S>`].
-/// - Runs the [`OnExit(exited_state)`] schedule, if it exists.
-/// - Runs the [`OnTransition { from: exited_state, to: entered_state }`](OnTransition), if it exists.
-/// - Runs the [`OnEnter(entered_state)`] schedule, if it exists.
+/// - Updates the [computed](ComputedStates) and [sub-states](SubStates) that depend on `S`.
+/// - Runs the [`OnExit(exited_state)`] schedules, if they exist, from the deepest dependent
+///   state to `S`.
+/// - Runs the [`OnTransition { from: exited_state, to: entered_state }`](OnTransition) schedules,
+///   if they exist, from `S` to the deepest dependent state.
+/// - Runs the [`OnEnter(entered_state)`] schedules, if they exist, from `S` to the deepest
+///   dependent state.
+///
+/// A queued value is discarded if `S` is a [sub-state](SubStates) that currently doesn't exist.
 pub fn apply_state_transition<S: States>(world: &mut World) {
     // We want to take the `NextState` resource,
     // but only mark it as changed if it wasn't empty.
@@ -139,18 +176,803 @@ pub fn apply_state_transition<S: States>(world: &mut World) {
     if let Some(entered) = next_state_resource.bypass_change_detection().0.take() {
         next_state_resource.set_changed();
 
-        let mut state_resource = world.resource_mut::<State<S>>();
-        if *state_resource != entered {
-            let exited = mem::replace(&mut state_resource.0, entered.clone());
-            // Try to run the schedules if they exist.
+        if world.contains_resource::<State<S>>() {
+            let mut transitions = Transitions::default();
+            transitions.set_state(world, Some(entered));
+            transitions.apply(world);
+        }
+    }
+}
+
+/// The transitions caused by a change of state, collected so that the schedules only run once
+/// every dependent state is updated.
+#[derive(Default)]
+struct Transitions {
+    /// The dependent states left to update.
+    pending: Vec<Dependent>,
+    /// The states that changed, with their depth, in the order they were updated.
+    changed: Vec<(usize, Box<dyn AnyStateChange>)>,
+}
+
+impl Transitions {
+    /// Sets the value of [`State<S>`], removing the resource if `entered` is `None`, and records
+    /// the transition if the value changed.
+    fn set_state<S: States>(&mut self, world: &mut World, entered: Option<S>) {
+        let exited = match entered.clone() {
+            Some(entered) => match world.get_resource_mut::<State<S>>() {
+                Some(mut state_resource) => {
+                    if *state_resource == entered {
+                        return;
+                    }
+                    Some(mem::replace(&mut state_resource.0, entered))
+                }
+                None => {
+                    world.insert_resource(State(entered));
+                    None
+                }
+            },
+            None => match world.remove_resource::<State<S>>() {
+                Some(state_resource) => Some(state_resource.0),
+                None => return,
+            },
+        };
+        self.record(world, exited, entered);
+    }
+
+    /// Records the transition of `S`, and queues the updates of the states that depend on it.
+    fn record<S: States>(&mut self, world: &World, exited: Option<S>, entered: Option<S>) {
+        self.changed
+            .push((S::depth(world), Box::new(StateChange { exited, entered })));
+        if let Some(dependents) = world.get_resource::<DependentStates<S>>() {
+            for dependent in &dependents.updates {
+                if self
+                    .pending
+                    .iter()
+                    .all(|pending| pending.type_id != dependent.type_id)
+                {
+                    self.pending.push(*dependent);
+                }
+            }
+        }
+    }
+
+    /// Updates the pending dependent states, then runs the transition schedules.
+    fn apply(mut self, world: &mut World) {
+        // A state is deeper than all its sources, so updating by increasing depth updates each
+        // state once, after all its sources.
+        while let Some(index) = self
+            .pending
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, dependent)| dependent.depth)
+            .map(|(index, _)| index)
+        {
+            let dependent = self.pending.remove(index);
+            (dependent.update)(world, &mut self);
+        }
+
+        // The sort is stable, so states of the same depth keep their update order.
+        self.changed.sort_by_key(|(depth, _)| *depth);
+        for (_, change) in self.changed.iter().rev() {
+            change.exit(world);
+        }
+        for (_, change) in &self.changed {
+            change.transition(world);
+        }
+        for (_, change) in &self.changed {
+            change.enter(world);
+        }
+    }
+}
+
+/// A recorded transition of [`State<S>`], `None` meaning that the resource doesn't exist.
+struct StateChange<S: States> {
+    exited: Option<S>,
+    entered: Option<S>,
+}
+
+/// Runs the schedules of a [`StateChange`] of any state type.
+trait AnyStateChange {
+    fn exit(&self, world: &mut World);
+    fn transition(&self, world: &mut World);
+    fn enter(&self, world: &mut World);
+}
+
+impl<S: States> AnyStateChange for StateChange<S> {
+    fn exit(&self, world: &mut World) {
+        if let Some(exited) = &self.exited {
             world.try_run_schedule(OnExit(exited.clone())).ok();
-            world
-                .try_run_schedule(OnTransition {
-                    from: exited,
-                    to: entered.clone(),
-                })
-                .ok();
+            clear_state_scoped_entities(world, exited);
+        }
+    }
+
+    fn transition(&self, world: &mut World) {
+        if let (Some(from), Some(to)) = (self.exited.clone(), self.entered.clone()) {
+            world.try_run_schedule(OnTransition { from, to }).ok();
+        }
+    }
+
+    fn enter(&self, world: &mut World) {
+        if let Some(entered) = self.entered.clone() {
+            show_state_scoped_entities(world, &entered);
             world.try_run_schedule(OnEnter(entered)).ok();
         }
     }
 }
+
+/// A set of [`States`] that [computed](ComputedStates) and [sub-states](SubStates) depend on.
+///
+/// This is implemented for any single [`States`] type, and for tuples of them.
+pub trait StateSet: 'static {
+    /// The current values of the states in the set, passed to [`ComputedStates::compute`]
+    /// and [`SubStates::should_exist`].
+    type Values;
+
+    /// Returns the current values of the states, or `None` if any of them doesn't exist.
+    fn get_values(world: &World) -> Option<Self::Values>;
+
+    /// Returns the depth of the deepest state of the set: `0` for a state that doesn't depend on
+    /// other states, and one more than the depth of its source states for the others.
+    fn depth(world: &World) -> usize;
+
+    /// Registers `dependent` to be updated after any state of the set changes.
+    fn register_dependent(world: &mut World, dependent: Dependent);
+
+    /// Orders the configured set after the [`ApplyStateTransition`] sets of the states of the set.
+    fn after_transitions(config: SystemSetConfig) -> SystemSetConfig;
+}
+
+impl<S: States> StateSet for S {
+    type Values = S;
+
+    fn get_values(world: &World) -> Option<S> {
+        world
+            .get_resource::<State<S>>()
+            .map(|state| state.get().clone())
+    }
+
+    fn depth(world: &World) -> usize {
+        world
+            .get_resource::<DependentStates<S>>()
+            .map_or(0, |dependents| dependents.depth)
+    }
+
+    fn register_dependent(world: &mut World, dependent: Dependent) {
+        let mut dependents = world.get_resource_or_insert_with(DependentStates::<S>::default);
+        if dependents
+            .updates
+            .iter()
+            .all(|registered| registered.type_id != dependent.type_id)
+        {
+            dependents.updates.push(dependent);
+        }
+    }
+
+    fn after_transitions(config: SystemSetConfig) -> SystemSetConfig {
+        config.after(ApplyStateTransition::<S>::default())
+    }
+}
+
+macro_rules! impl_state_set {
+    ($($name: ident),*) => {
+        impl<$($name: States),*> StateSet for ($($name,)*) {
+            type Values = ($($name,)*);
+
+            fn get_values(world: &World) -> Option<Self::Values> {
+                Some(($(<$name as StateSet>::get_values(world)?,)*))
+            }
+
+            fn depth(world: &World) -> usize {
+                0$(.max(<$name as StateSet>::depth(world)))*
+            }
+
+            fn register_dependent(world: &mut World, dependent: Dependent) {
+                $(<$name as StateSet>::register_dependent(world, dependent);)*
+            }
+
+            fn after_transitions(config: SystemSetConfig) -> SystemSetConfig {
+                $(let config = <$name as StateSet>::after_transitions(config);)*
+                config
+            }
+        }
+    };
+}
+
+all_tuples!(impl_state_set, 1, 8, S);
+
+/// A state whose value is derived from one or more source states.
+///
+/// The value is recomputed every time one of the [`SourceStates`](Self::SourceStates) changes.
+/// Its [`OnExit`] schedules run before those of its sources, and its [`OnTransition`] and
+/// [`OnEnter`] schedules after those of its sources, see [`apply_state_transition`]. Computed
+/// states can't be
+/// changed through [`NextState`]: their [`State`] resource only exists while
+/// [`compute`](Self::compute) returns `Some`, so use
+/// [`state_exists_and_equals`](super::common_conditions::state_exists_and_equals) rather than
+/// [`in_state`](super::common_conditions::in_state) to check their value.
+///
+/// Call [`setup_computed_state`] (or `App::add_computed_state`) to register a computed state.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AppState {
+///     #[default]
+///     Menu,
+///     Playing,
+///     Paused,
+/// }
+///
+/// /// Exists while a game is running, whether it is paused or not.
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum InGame {
+///     #[default]
+///     Yes,
+/// }
+///
+/// impl ComputedStates for InGame {
+///     type SourceStates = AppState;
+///
+///     fn compute(app_state: AppState) -> Option<Self> {
+///         match app_state {
+///             AppState::Menu => None,
+///             AppState::Playing | AppState::Paused => Some(InGame::Yes),
+///         }
+///     }
+/// }
+/// ```
+pub trait ComputedStates: States {
+    /// The states this state is computed from: a single [`States`] type or a tuple of them.
+    type SourceStates: StateSet;
+
+    /// Computes the value of this state from the current values of the source states.
+    ///
+    /// Returning `None` removes the [`State`] resource. This is only called while all the source
+    /// states exist, otherwise the computed state doesn't exist either.
+    fn compute(sources: <Self::SourceStates as StateSet>::Values) -> Option<Self>;
+}
+
+/// A state that only exists while its source states have given values.
+///
+/// When [`should_exist`](Self::should_exist) starts returning `true`, the [`State`] resource is
+/// inserted with the [`Default`] value, and its [`OnEnter`] schedule runs. While it exists, it
+/// can be changed through [`NextState`] like any other state. When `should_exist` returns
+/// `false`, the [`OnExit`] schedule of its current value runs and the resource is removed.
+///
+/// Call [`setup_sub_state`] (or `App::add_sub_state`) to register a sub-state.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum AppState {
+///     #[default]
+///     Menu,
+///     InGame,
+/// }
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GamePhase {
+///     #[default]
+///     Running,
+///     Paused,
+/// }
+///
+/// impl SubStates for GamePhase {
+///     type SourceStates = AppState;
+///
+///     fn should_exist(app_state: AppState) -> bool {
+///         app_state == AppState::InGame
+///     }
+/// }
+/// ```
+pub trait SubStates: States {
+    /// The states this state depends on: a single [`States`] type or a tuple of them.
+    type SourceStates: StateSet;
+
+    /// Returns `true` if this state should exist for the current values of the source states.
+    ///
+    /// This is only called while all the source states exist, otherwise the sub-state doesn't
+    /// exist either.
+    fn should_exist(sources: <Self::SourceStates as StateSet>::Values) -> bool;
+}
+
+/// A computed or sub-state, registered with the [`StateSet`] it depends on.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct Dependent {
+    type_id: TypeId,
+    depth: usize,
+    update: fn(&mut World, &mut Transitions),
+}
+
+/// The depth of the state `S` and the computed and sub-states that depend on it, in registration
+/// order.
+#[derive(Resource)]
+struct DependentStates<S: States> {
+    depth: usize,
+    updates: Vec<Dependent>,
+    marker: PhantomData<S>,
+}
+
+impl<S: States> Default for DependentStates<S> {
+    fn default() -> Self {
+        Self {
+            depth: 0,
+            updates: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Registers `S` as a dependent of `T`, one level deeper than the deepest state of `T`.
+fn register_dependent<S: States, T: StateSet>(
+    world: &mut World,
+    update: fn(&mut World, &mut Transitions),
+) {
+    let depth = T::depth(world) + 1;
+    world
+        .get_resource_or_insert_with(DependentStates::<S>::default)
+        .depth = depth;
+    T::register_dependent(
+        world,
+        Dependent {
+            type_id: TypeId::of::<S>(),
+            depth,
+            update,
+        },
+    );
+}
+
+/// Registers the computed state `S`, so that it is updated whenever one of its source states
+/// changes.
+///
+/// The source states must be registered first. The initial value is computed when the source
+/// states are entered with [`run_enter_schedule`].
+pub fn setup_computed_state<S: ComputedStates>(world: &mut World) {
+    register_dependent::<S, S::SourceStates>(world, update_computed_state::<S>);
+}
+
+/// Registers the sub-state `S`, so that it is inserted or removed whenever one of its source
+/// states changes, and adds its [`NextState<S>`] resource.
+///
+/// The source states must be registered first. Transitions queued in [`NextState<S>`] are
+/// applied by [`apply_state_transition::<S>`].
+pub fn setup_sub_state<S: SubStates>(world: &mut World) {
+    world.init_resource::<NextState<S>>();
+    register_dependent::<S, S::SourceStates>(world, update_sub_state::<S>);
+}
+
+fn update_computed_state<S: ComputedStates>(world: &mut World, transitions: &mut Transitions) {
+    let computed = S::SourceStates::get_values(world).and_then(S::compute);
+    transitions.set_state(world, computed);
+}
+
+fn update_sub_state<S: SubStates>(world: &mut World, transitions: &mut Transitions) {
+    let should_exist = S::SourceStates::get_values(world).is_some_and(S::should_exist);
+    match (should_exist, world.contains_resource::<State<S>>()) {
+        (true, false) => transitions.set_state(world, Some(S::default())),
+        (false, true) => {
+            // Transitions queued for a state that no longer exists are discarded.
+            world
+                .resource_mut::<NextState<S>>()
+                .bypass_change_detection()
+                .0 = None;
+            transitions.set_state::<S>(world, None);
+        }
+        _ => {}
+    }
+}
+
+/// Ties an entity to a value of the state `S`: when [`apply_state_transition`] exits that value,
+/// the entity is cleaned up according to its [`StateScopedAction`].
+///
+/// The cleanup happens right after the [`OnExit`] schedule of the state has run, so its systems
+/// can still access the entity. Hidden entities are shown again right before the [`OnEnter`]
+/// schedule runs when the state is entered again. By default, despawning is not recursive and
+/// hiding does nothing: the hierarchy and rendering plugins configure the [`StateScopedActions`]
+/// resource to despawn entities along with their children, and to hide and show them with their
+/// `Visibility` component.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ecs::prelude::*;
+/// use bevy_ecs::schedule::StateScoped;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// let mut world = World::new();
+/// world.init_resource::<State<GameState>>();
+/// world.init_resource::<NextState<GameState>>();
+///
+/// let menu = world.spawn(StateScoped::despawn(GameState::MainMenu)).id();
+///
+/// world.resource_mut::<NextState<GameState>>().set(GameState::InGame);
+/// apply_state_transition::<GameState>(&mut world);
+/// assert!(world.get_entity(menu).is_none());
+/// ```
+#[derive(Component, Clone, Debug)]
+pub struct StateScoped<S: States> {
+    /// The state value whose exit cleans up the entity.
+    pub state: S,
+    /// How the entity is cleaned up.
+    pub action: StateScopedAction,
+}
+
+impl<S: States> StateScoped<S> {
+    /// Despawns the entity when `state` is exited.
+    pub fn despawn(state: S) -> Self {
+        Self {
+            state,
+            action: StateScopedAction::Despawn,
+        }
+    }
+
+    /// Hides the entity when `state` is exited.
+    pub fn hide(state: S) -> Self {
+        Self {
+            state,
+            action: StateScopedAction::Hide,
+        }
+    }
+}
+
+/// What happens to an entity with a [`StateScoped`] component when its state is exited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StateScopedAction {
+    /// Despawns the entity (and its descendants, if the hierarchy plugin is used).
+    #[default]
+    Despawn,
+    /// Hides the entity and keeps it alive, and shows it again when the state is entered again.
+    Hide,
+}
+
+/// The functions used to clean up entities with a [`StateScoped`] component.
+///
+/// Plugins can replace them to integrate with their own components, for example to despawn
+/// children along with their parents.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct StateScopedActions {
+    /// Applies [`StateScopedAction::Despawn`]. Defaults to [`World::despawn`].
+    pub despawn: fn(&mut World, Entity),
+    /// Applies [`StateScopedAction::Hide`]. Does nothing by default.
+    pub hide: fn(&mut World, Entity),
+    /// Shows the entities with [`StateScopedAction::Hide`] when their state is entered. Does
+    /// nothing by default.
+    ///
+    /// It's also called when the state is entered for the first time, so it should only undo
+    /// what `hide` did.
+    pub show: fn(&mut World, Entity),
+}
+
+impl Default for StateScopedActions {
+    fn default() -> Self {
+        Self {
+            despawn: |world, entity| {
+                world.despawn(entity);
+            },
+            hide: |_, _| {},
+            show: |_, _| {},
+        }
+    }
+}
+
+/// Returns the entities scoped to `state`, with their [`StateScopedAction`].
+fn state_scoped_entities<S: States>(
+    world: &mut World,
+    state: &S,
+) -> Vec<(Entity, StateScopedAction)> {
+    if world.component_id::<StateScoped<S>>().is_none() {
+        return Vec::new();
+    }
+
+    world
+        .query::<(Entity, &StateScoped<S>)>()
+        .iter(world)
+        .filter(|(_, scoped)| scoped.state == *state)
+        .map(|(entity, scoped)| (entity, scoped.action))
+        .collect()
+}
+
+fn clear_state_scoped_entities<S: States>(world: &mut World, exited: &S) {
+    let entities = state_scoped_entities(world, exited);
+    if entities.is_empty() {
+        return;
+    }
+    let actions = world
+        .get_resource::<StateScopedActions>()
+        .copied()
+        .unwrap_or_default();
+    for (entity, action) in entities {
+        // The entity may have been despawned along with an earlier one.
+        if !world.entities().contains(entity) {
+            continue;
+        }
+        match action {
+            StateScopedAction::Despawn => (actions.despawn)(world, entity),
+            StateScopedAction::Hide => (actions.hide)(world, entity),
+        }
+    }
+}
+
+fn show_state_scoped_entities<S: States>(world: &mut World, entered: &S) {
+    let entities = state_scoped_entities(world, entered);
+    if entities.is_empty() {
+        return;
+    }
+    let actions = world
+        .get_resource::<StateScopedActions>()
+        .copied()
+        .unwrap_or_default();
+    for (entity, action) in entities {
+        if action == StateScopedAction::Hide {
+            (actions.show)(world, entity);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::{Schedule, ScheduleLabel};
+    use crate::system::ResMut;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum AppState {
+        #[default]
+        Menu,
+        InGame,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum GamePhase {
+        #[default]
+        Running,
+        Paused,
+    }
+
+    impl SubStates for GamePhase {
+        type SourceStates = AppState;
+
+        fn should_exist(app_state: AppState) -> bool {
+            app_state == AppState::InGame
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum InGame {
+        #[default]
+        Active,
+    }
+
+    impl ComputedStates for InGame {
+        type SourceStates = AppState;
+
+        fn compute(app_state: AppState) -> Option<Self> {
+            (app_state == AppState::InGame).then_some(InGame::Active)
+        }
+    }
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+    enum Paused {
+        #[default]
+        Frozen,
+    }
+
+    impl ComputedStates for Paused {
+        type SourceStates = (InGame, GamePhase);
+
+        fn compute((_, phase): (InGame, GamePhase)) -> Option<Self> {
+            (phase == GamePhase::Paused).then_some(Paused::Frozen)
+        }
+    }
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<String>);
+
+    fn log_schedule(world: &mut World, label: impl ScheduleLabel) {
+        let name = format!("{label:?}");
+        let mut schedule = Schedule::new();
+        schedule.add_systems(move |mut log: ResMut<Log>| log.0.push(name.clone()));
+        world.add_schedule(schedule, label);
+    }
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        world.init_resource::<State<AppState>>();
+        world.init_resource::<NextState<AppState>>();
+        setup_sub_state::<GamePhase>(&mut world);
+        setup_computed_state::<InGame>(&mut world);
+        setup_computed_state::<Paused>(&mut world);
+
+        for state in AppState::variants() {
+            log_schedule(&mut world, OnEnter(state));
+            log_schedule(&mut world, OnExit(state));
+        }
+        for state in GamePhase::variants() {
+            log_schedule(&mut world, OnEnter(state));
+            log_schedule(&mut world, OnExit(state));
+        }
+        log_schedule(&mut world, OnEnter(InGame::Active));
+        log_schedule(&mut world, OnExit(InGame::Active));
+        log_schedule(&mut world, OnEnter(Paused::Frozen));
+        log_schedule(&mut world, OnExit(Paused::Frozen));
+        log_schedule(
+            &mut world,
+            OnTransition {
+                from: GamePhase::Running,
+                to: GamePhase::Paused,
+            },
+        );
+
+        run_enter_schedule::<AppState>(&mut world);
+        world
+    }
+
+    fn take_log(world: &mut World) -> Vec<String> {
+        mem::take(&mut world.resource_mut::<Log>().0)
+    }
+
+    #[test]
+    fn sub_state_exists_with_parent() {
+        let mut world = setup();
+        assert_eq!(take_log(&mut world), ["OnEnter(Menu)"]);
+        assert!(!world.contains_resource::<State<GamePhase>>());
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        apply_state_transition::<AppState>(&mut world);
+        assert_eq!(world.resource::<State<GamePhase>>(), &GamePhase::Running);
+        assert_eq!(
+            take_log(&mut world),
+            [
+                "OnExit(Menu)",
+                "OnEnter(InGame)",
+                "OnEnter(Running)",
+                "OnEnter(Active)"
+            ]
+        );
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Menu);
+        apply_state_transition::<AppState>(&mut world);
+        assert!(!world.contains_resource::<State<GamePhase>>());
+        assert!(!world.contains_resource::<State<InGame>>());
+        assert_eq!(
+            take_log(&mut world),
+            [
+                "OnExit(Active)",
+                "OnExit(Running)",
+                "OnExit(InGame)",
+                "OnEnter(Menu)"
+            ]
+        );
+    }
+
+    #[test]
+    fn sub_state_transitions_only_while_it_exists() {
+        let mut world = setup();
+        world
+            .resource_mut::<NextState<GamePhase>>()
+            .set(GamePhase::Paused);
+        apply_state_transition::<GamePhase>(&mut world);
+        assert!(!world.contains_resource::<State<GamePhase>>());
+        assert_eq!(world.resource::<NextState<GamePhase>>().0, None);
+
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        apply_state_transition::<AppState>(&mut world);
+        take_log(&mut world);
+
+        world
+            .resource_mut::<NextState<GamePhase>>()
+            .set(GamePhase::Paused);
+        apply_state_transition::<GamePhase>(&mut world);
+        assert_eq!(world.resource::<State<GamePhase>>(), &GamePhase::Paused);
+        assert_eq!(
+            take_log(&mut world),
+            [
+                "OnExit(Running)",
+                "OnTransition { from: Running, to: Paused }",
+                "OnEnter(Paused)",
+                "OnEnter(Frozen)"
+            ]
+        );
+    }
+
+    #[test]
+    fn computed_state_from_several_sources() {
+        let mut world = setup();
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        apply_state_transition::<AppState>(&mut world);
+        world
+            .resource_mut::<NextState<GamePhase>>()
+            .set(GamePhase::Paused);
+        apply_state_transition::<GamePhase>(&mut world);
+        assert!(world.contains_resource::<State<Paused>>());
+        take_log(&mut world);
+
+        // Leaving the game removes `GamePhase` and `InGame`, and therefore `Paused`: all of them
+        // are exited, deepest first, before `Menu` is entered.
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Menu);
+        apply_state_transition::<AppState>(&mut world);
+        assert!(!world.contains_resource::<State<Paused>>());
+        assert_eq!(
+            take_log(&mut world),
+            [
+                "OnExit(Frozen)",
+                "OnExit(Active)",
+                "OnExit(Paused)",
+                "OnExit(InGame)",
+                "OnEnter(Menu)"
+            ]
+        );
+    }
+
+    #[test]
+    fn state_scoped_entities() {
+        let mut world = setup();
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        apply_state_transition::<AppState>(&mut world);
+
+        let level = world.spawn(StateScoped::despawn(AppState::InGame)).id();
+        let pause_menu = world.spawn(StateScoped::despawn(GamePhase::Paused)).id();
+        let hud = world.spawn(StateScoped::hide(AppState::InGame)).id();
+        let menu = world.spawn(StateScoped::despawn(AppState::Menu)).id();
+
+        #[derive(Resource, Default)]
+        struct Hidden(Vec<Entity>);
+        world.insert_resource(StateScopedActions {
+            hide: |world, entity| world.resource_mut::<Hidden>().0.push(entity),
+            show: |world, entity| world.resource_mut::<Hidden>().0.retain(|&e| e != entity),
+            ..Default::default()
+        });
+        world.init_resource::<Hidden>();
+
+        world
+            .resource_mut::<NextState<GamePhase>>()
+            .set(GamePhase::Paused);
+        apply_state_transition::<GamePhase>(&mut world);
+        assert!(world.get_entity(pause_menu).is_some());
+
+        // Leaving `InGame` also exits the `Paused` sub-state.
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Menu);
+        apply_state_transition::<AppState>(&mut world);
+        assert!(world.get_entity(level).is_none());
+        assert!(world.get_entity(pause_menu).is_none());
+        assert!(world.get_entity(hud).is_some());
+        assert_eq!(world.resource::<Hidden>().0, [hud]);
+        assert!(world.get_entity(menu).is_some());
+
+        // Entering `InGame` again shows the hidden entities.
+        world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        apply_state_transition::<AppState>(&mut world);
+        assert!(world.resource::<Hidden>().0.is_empty());
+        assert!(world.get_entity(menu).is_none());
+    }
+}
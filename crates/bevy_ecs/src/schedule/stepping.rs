@@ -0,0 +1,545 @@
+use std::{any::TypeId, borrow::Cow};
+
+use bevy_utils::HashMap;
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    self as bevy_ecs,
+    schedule::{
+        BoxedScheduleLabel, IntoSystemSet, NodeId, ScheduleLabel, SystemSchedule, SystemSet,
+    },
+    system::{ResMut, Resource, System},
+};
+
+/// How a system is treated by [`Stepping`] while it is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemBehavior {
+    /// The system runs every frame, even while stepping is paused.
+    AlwaysRun,
+    /// The system never runs.
+    NeverRun,
+    /// [`Stepping::step_frame`] pauses before running the system.
+    Break,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Action {
+    /// Stepping is disabled and every system runs.
+    #[default]
+    RunAll,
+    /// Only [`SystemBehavior::AlwaysRun`] systems run.
+    Waiting,
+    /// Run the system at the cursor, then wait.
+    Step,
+    /// Run systems from the cursor until a breakpoint or the end of the frame, then wait.
+    Continue,
+}
+
+/// The position of [`Stepping`] in its schedules.
+#[derive(Debug, Default)]
+struct Cursor {
+    /// Index into [`Stepping::schedules`].
+    schedule: usize,
+    /// Index of the next system to run in the schedule's topological order.
+    system: usize,
+    /// Is `true` if the cursor has moved to the start of a schedule without pausing,
+    /// in which case a breakpoint on the first system still pauses.
+    entered: bool,
+    /// The id and name of the system at the cursor, once its schedule has run.
+    current: Option<(NodeId, Cow<'static, str>)>,
+}
+
+/// Resource that lets schedules be paused and advanced one system or one frame at a time,
+/// for debugging.
+///
+/// Only the schedules added with [`Stepping::add_schedule`] are stepped, in the order they were
+/// added, which should match the order they run in. While stepping is enabled, the systems of
+/// those schedules only run when asked to by [`Stepping::step_system`] or
+/// [`Stepping::step_frame`], except for systems set to [`SystemBehavior::AlwaysRun`]. The cursor
+/// waits in a schedule until it runs, so schedules that do not run every frame are stepped
+/// through once they do. Schedules that are not stepped, such as the one driving the
+/// others, keep running normally.
+///
+/// Requested actions take effect when [`Stepping::begin_frame`] next runs, which should happen
+/// once per frame before the stepped schedules run. `bevy_app` does this at the start of `Main`.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{ScheduleLabel, Stepping};
+/// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+/// struct Update;
+///
+/// fn movement() {}
+/// fn collisions() {}
+///
+/// let mut stepping = Stepping::new();
+/// stepping
+///     .add_schedule(Update)
+///     .set_breakpoint(Update, collisions)
+///     .enable();
+///
+/// // later, from a debug UI: run `movement` and pause before `collisions`
+/// stepping.step_frame();
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct Stepping {
+    schedules: Vec<BoxedScheduleLabel>,
+    behaviors: HashMap<BoxedScheduleLabel, HashMap<TypeId, SystemBehavior>>,
+    action: Action,
+    pending_action: Option<Action>,
+    cursor: Cursor,
+}
+
+impl Stepping {
+    /// Creates a disabled [`Stepping`] without any schedules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a schedule to be stepped. Schedules are stepped through in the order they are added.
+    pub fn add_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        if !self.schedules.iter().any(|label| (**label).eq(&schedule)) {
+            self.schedules.push(schedule.dyn_clone());
+        }
+        self
+    }
+
+    /// Stops stepping a schedule, so that it runs normally.
+    pub fn remove_schedule(&mut self, schedule: impl ScheduleLabel) -> &mut Self {
+        self.schedules.retain(|label| !(**label).eq(&schedule));
+        self.cursor = Cursor::default();
+        self
+    }
+
+    /// Returns the stepped schedules, in stepping order.
+    pub fn schedules(&self) -> impl Iterator<Item = &dyn ScheduleLabel> {
+        self.schedules.iter().map(|label| &**label)
+    }
+
+    /// Pauses the stepped schedules before their first system on the next frame.
+    pub fn enable(&mut self) -> &mut Self {
+        self.pending_action = Some(Action::Waiting);
+        self
+    }
+
+    /// Lets every system run normally again from the next frame on.
+    pub fn disable(&mut self) -> &mut Self {
+        self.pending_action = Some(Action::RunAll);
+        self
+    }
+
+    /// Returns `true` if stepping is enabled, or is about to be on the next frame.
+    pub fn is_enabled(&self) -> bool {
+        self.pending_action.unwrap_or(self.action) != Action::RunAll
+    }
+
+    /// Runs the system at the cursor on the next frame, then pauses again.
+    ///
+    /// Has no effect while stepping is disabled.
+    pub fn step_system(&mut self) -> &mut Self {
+        if self.is_enabled() {
+            self.pending_action = Some(Action::Step);
+        }
+        self
+    }
+
+    /// Runs the remaining systems of the stepped schedules on the next frame, pausing before
+    /// the next system with a [breakpoint](Stepping::set_breakpoint).
+    ///
+    /// Has no effect while stepping is disabled.
+    pub fn step_frame(&mut self) -> &mut Self {
+        if self.is_enabled() {
+            self.pending_action = Some(Action::Continue);
+        }
+        self
+    }
+
+    /// Runs `system` every frame in `schedule`, even while stepping is paused.
+    pub fn always_run<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystemSet<M>,
+    ) -> &mut Self {
+        self.set_behavior(schedule, system, Some(SystemBehavior::AlwaysRun))
+    }
+
+    /// Skips `system` in `schedule` while stepping is enabled.
+    pub fn never_run<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystemSet<M>,
+    ) -> &mut Self {
+        self.set_behavior(schedule, system, Some(SystemBehavior::NeverRun))
+    }
+
+    /// Pauses [`Stepping::step_frame`] before `system` runs in `schedule`.
+    pub fn set_breakpoint<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystemSet<M>,
+    ) -> &mut Self {
+        self.set_behavior(schedule, system, Some(SystemBehavior::Break))
+    }
+
+    /// Removes any [`SystemBehavior`] set for `system` in `schedule`.
+    pub fn clear_system<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystemSet<M>,
+    ) -> &mut Self {
+        self.set_behavior(schedule, system, None)
+    }
+
+    /// Returns the [`SystemBehavior`] set for `system` in `schedule`, if any.
+    pub fn system_behavior<M>(
+        &self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystemSet<M>,
+    ) -> Option<SystemBehavior> {
+        let type_id = system_type(system);
+        self.behaviors
+            .get(&schedule as &dyn ScheduleLabel)
+            .and_then(|behaviors| behaviors.get(&type_id).copied())
+    }
+
+    /// Returns the schedule, id and name of the system that will run next while stepping is
+    /// enabled, once the schedule containing it has run.
+    pub fn cursor(&self) -> Option<(&dyn ScheduleLabel, NodeId, &str)> {
+        if self.action == Action::RunAll {
+            return None;
+        }
+        let (id, name) = self.cursor.current.as_ref()?;
+        Some((&*self.schedules[self.cursor.schedule], *id, name))
+    }
+
+    /// A system that applies the requested stepping action. It should run once per frame,
+    /// before any stepped schedule.
+    pub fn begin_frame(stepping: Option<ResMut<Self>>) {
+        let Some(mut stepping) = stepping else {
+            return;
+        };
+        let Some(action) = stepping.pending_action else {
+            return;
+        };
+        stepping.pending_action = None;
+        if action == Action::RunAll || stepping.action == Action::RunAll {
+            stepping.cursor = Cursor::default();
+        }
+        stepping.action = action;
+    }
+
+    fn set_behavior<M>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        system: impl IntoSystemSet<M>,
+        behavior: Option<SystemBehavior>,
+    ) -> &mut Self {
+        let type_id = system_type(system);
+        let behaviors = self.behaviors.entry(schedule.dyn_clone()).or_default();
+        match behavior {
+            Some(behavior) => behaviors.insert(type_id, behavior),
+            None => behaviors.remove(&type_id),
+        };
+        self
+    }
+
+    /// Moves the cursor to the start of the schedule after `index`, finishing the stepping
+    /// frame after the last schedule.
+    fn enter_next_schedule(&mut self, index: usize) {
+        let next = index + 1;
+        if next < self.schedules.len() {
+            self.cursor.schedule = next;
+        } else {
+            self.cursor.schedule = 0;
+            self.action = Action::Waiting;
+        }
+        self.cursor.system = 0;
+        self.cursor.entered = true;
+        self.cursor.current = None;
+    }
+
+    /// Returns the systems of the schedule labeled `label` that should not run this frame,
+    /// advancing the cursor past the ones that will.
+    pub(super) fn skipped_systems(
+        &mut self,
+        label: &dyn ScheduleLabel,
+        schedule: &SystemSchedule,
+    ) -> Option<FixedBitSet> {
+        if self.action == Action::RunAll {
+            return None;
+        }
+        let index = self.schedules.iter().position(|l| **l == *label)?;
+
+        let behaviors = self.behaviors.get(label);
+        let behavior = |system_index: usize| {
+            let type_id = System::type_id(schedule.systems[system_index].as_ref());
+            behaviors.and_then(|behaviors| behaviors.get(&type_id).copied())
+        };
+
+        let len = schedule.systems.len();
+        let mut skipped = FixedBitSet::with_capacity(len);
+        skipped.insert_range(..);
+        for system_index in 0..len {
+            if behavior(system_index) == Some(SystemBehavior::AlwaysRun) {
+                skipped.set(system_index, false);
+            }
+        }
+
+        if index != self.cursor.schedule {
+            return Some(skipped);
+        }
+
+        let is_stepped = |system_index: usize| {
+            !matches!(
+                behavior(system_index),
+                Some(SystemBehavior::AlwaysRun | SystemBehavior::NeverRun)
+            )
+        };
+        let start = self.cursor.system;
+        match self.action {
+            Action::RunAll | Action::Waiting => {}
+            Action::Step => {
+                if let Some(system_index) = (start..len).find(|&i| is_stepped(i)) {
+                    skipped.set(system_index, false);
+                    self.cursor.system = system_index + 1;
+                    self.cursor.entered = false;
+                    self.action = Action::Waiting;
+                } else {
+                    self.cursor.system = len;
+                }
+            }
+            Action::Continue => {
+                let breakpoint = (start..len).find(|&i| {
+                    behavior(i) == Some(SystemBehavior::Break) && (i > start || self.cursor.entered)
+                });
+                let end = breakpoint.unwrap_or(len);
+                for system_index in (start..end).filter(|&i| is_stepped(i)) {
+                    skipped.set(system_index, false);
+                }
+                self.cursor.system = end;
+                self.cursor.entered = false;
+                if breakpoint.is_some() {
+                    self.action = Action::Waiting;
+                }
+            }
+        }
+
+        if self.cursor.system >= len {
+            self.enter_next_schedule(index);
+        } else {
+            let system_index = self.cursor.system;
+            self.cursor.current = Some((
+                schedule.system_ids[system_index],
+                schedule.systems[system_index].name(),
+            ));
+        }
+
+        Some(skipped)
+    }
+}
+
+fn system_type<M>(system: impl IntoSystemSet<M>) -> TypeId {
+    system
+        .into_system_set()
+        .system_type()
+        .expect("Stepping behaviors can only be set for systems, not system sets")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        schedule::{ExecutorKind, ScheduleLabel, Schedules},
+        system::RunSystemOnce,
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct First;
+
+    #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Second;
+
+    #[derive(Resource, Default)]
+    struct Ran(Vec<&'static str>);
+
+    fn a(mut ran: ResMut<Ran>) {
+        ran.0.push("a");
+    }
+
+    fn b(mut ran: ResMut<Ran>) {
+        ran.0.push("b");
+    }
+
+    fn c(mut ran: ResMut<Ran>) {
+        ran.0.push("c");
+    }
+
+    fn d(mut ran: ResMut<Ran>) {
+        ran.0.push("d");
+    }
+
+    fn setup(kind: ExecutorKind) -> World {
+        let mut world = World::new();
+        world.init_resource::<Ran>();
+        let mut schedules = Schedules::new();
+        let mut first = Schedule::new();
+        first.set_executor_kind(kind).add_systems((a, b, c).chain());
+        schedules.insert(First, first);
+        let mut second = Schedule::new();
+        second.set_executor_kind(kind).add_systems(d);
+        schedules.insert(Second, second);
+        world.insert_resource(schedules);
+
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(First).add_schedule(Second).enable();
+        world.insert_resource(stepping);
+        world
+    }
+
+    fn frame(world: &mut World) -> Vec<&'static str> {
+        world.run_system_once(Stepping::begin_frame);
+        world.run_schedule(First);
+        world.run_schedule(Second);
+        std::mem::take(&mut world.resource_mut::<Ran>().0)
+    }
+
+    fn stepping(world: &mut World) -> Mut<'_, Stepping> {
+        world.resource_mut::<Stepping>()
+    }
+
+    fn step_systems(kind: ExecutorKind) {
+        let mut world = setup(kind);
+        stepping(&mut world).always_run(First, c);
+
+        assert_eq!(frame(&mut world), vec!["c"]);
+        assert_eq!(frame(&mut world), vec!["c"]);
+
+        stepping(&mut world).step_system();
+        assert_eq!(frame(&mut world), vec!["a", "c"]);
+        assert_eq!(frame(&mut world), vec!["c"]);
+        let (_, _, name) = world.resource::<Stepping>().cursor().unwrap();
+        assert!(name.ends_with("::b"));
+
+        stepping(&mut world).step_system();
+        assert_eq!(frame(&mut world), vec!["b", "c"]);
+        stepping(&mut world).step_system();
+        assert_eq!(frame(&mut world), vec!["c", "d"]);
+        stepping(&mut world).step_system();
+        assert_eq!(frame(&mut world), vec!["a", "c"]);
+
+        stepping(&mut world).disable();
+        assert_eq!(frame(&mut world), vec!["a", "b", "c", "d"]);
+        assert!(world.resource::<Stepping>().cursor().is_none());
+    }
+
+    fn step_frames(kind: ExecutorKind) {
+        let mut world = setup(kind);
+        stepping(&mut world)
+            .set_breakpoint(First, b)
+            .never_run(Second, d);
+
+        stepping(&mut world).step_frame();
+        assert_eq!(frame(&mut world), vec!["a"]);
+        assert_eq!(frame(&mut world), Vec::<&str>::new());
+
+        stepping(&mut world).step_frame();
+        assert_eq!(frame(&mut world), vec!["b", "c"]);
+
+        stepping(&mut world).clear_system(Second, d).step_frame();
+        assert_eq!(frame(&mut world), vec!["a"]);
+        stepping(&mut world).step_frame();
+        assert_eq!(frame(&mut world), vec!["b", "c", "d"]);
+        assert_eq!(
+            world.resource::<Stepping>().system_behavior(First, b),
+            Some(SystemBehavior::Break)
+        );
+    }
+
+    #[derive(Resource, Default, Clone)]
+    struct Order(Arc<Mutex<Vec<&'static str>>>);
+
+    fn skip_before_always_run(kind: ExecutorKind) {
+        // the systems only read `Order`, so nothing but their ordering keeps them apart
+        fn x(order: Res<Order>) {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            order.0.lock().unwrap().push("x");
+        }
+        fn y(order: Res<Order>) {
+            order.0.lock().unwrap().push("y");
+        }
+        fn y_condition(order: Res<Order>) -> bool {
+            order.0.lock().unwrap().push("y_condition");
+            true
+        }
+        fn z_condition(order: Res<Order>) -> bool {
+            order.0.lock().unwrap().push("z_condition");
+            true
+        }
+        fn z(order: Res<Order>) {
+            order.0.lock().unwrap().push("z");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Order>();
+        let mut schedules = Schedules::new();
+        let mut first = Schedule::new();
+        first
+            .set_executor_kind(kind)
+            .add_systems((x, y.run_if(y_condition), z.run_if(z_condition)).chain());
+        schedules.insert(First, first);
+        world.insert_resource(schedules);
+        let mut stepping = Stepping::new();
+        stepping.add_schedule(First).enable().always_run(First, x);
+        stepping.always_run(First, z);
+        world.insert_resource(stepping);
+
+        world.run_system_once(Stepping::begin_frame);
+        world.run_schedule(First);
+        let order = world.resource::<Order>().0.lock().unwrap().clone();
+        assert_eq!(order, vec!["x", "z_condition", "z"]);
+    }
+
+    #[test]
+    fn skip_before_always_run_single_threaded() {
+        skip_before_always_run(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn skip_before_always_run_multi_threaded() {
+        skip_before_always_run(ExecutorKind::MultiThreaded);
+    }
+
+    #[test]
+    fn skip_before_always_run_simple() {
+        skip_before_always_run(ExecutorKind::Simple);
+    }
+
+    #[test]
+    fn step_systems_single_threaded() {
+        step_systems(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn step_systems_multi_threaded() {
+        step_systems(ExecutorKind::MultiThreaded);
+    }
+
+    #[test]
+    fn step_frames_single_threaded() {
+        step_frames(ExecutorKind::SingleThreaded);
+    }
+
+    #[test]
+    fn step_frames_multi_threaded() {
+        step_frames(ExecutorKind::MultiThreaded);
+    }
+
+    #[test]
+    fn step_frames_simple() {
+        step_frames(ExecutorKind::Simple);
+    }
+}
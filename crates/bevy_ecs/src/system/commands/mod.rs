@@ -5,6 +5,9 @@ use crate::{
     self as bevy_ecs,
     bundle::Bundle,
     entity::{Entities, Entity},
+    event::Event,
+    observer::{Trigger, TriggerEvent},
+    relation::{Relate, Relation, Unrelate},
     world::{FromWorld, World},
 };
 use bevy_ecs_macros::SystemParam;
@@ -13,7 +16,7 @@ pub use command_queue::CommandQueue;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
-use super::{Deferred, Resource, SystemBuffer, SystemMeta};
+use super::{Deferred, IntoSystem, Resource, RunSystem, SystemBuffer, SystemId, SystemMeta};
 
 /// A [`World`] mutation.
 ///
@@ -556,6 +559,37 @@ impl<'w, 's> Commands<'w, 's> {
     pub fn add<C: Command>(&mut self, command: C) {
         self.queue.push(command);
     }
+
+    /// Triggers the event `E` without a target once the command is applied,
+    /// running the observers registered with [`World::observe`].
+    ///
+    /// See [`World::trigger`] for more details.
+    pub fn trigger<E: Event>(&mut self, event: E) {
+        self.queue.push(TriggerEvent {
+            event,
+            target: None,
+        });
+    }
+
+    /// Triggers the event `E` for the `target` entity once the command is applied,
+    /// running its observers.
+    ///
+    /// See [`World::trigger_targets`] for more details.
+    pub fn trigger_targets<E: Event>(&mut self, event: E, target: Entity) {
+        self.queue.push(TriggerEvent {
+            event,
+            target: Some(target),
+        });
+    }
+
+    /// Runs the system corresponding to the given [`SystemId`].
+    /// Systems are ran in an exclusive and single threaded way.
+    /// Running slow systems can become a bottleneck.
+    ///
+    /// Calls [`World::run_system`](crate::world::World::run_system).
+    pub fn run_system(&mut self, id: SystemId) {
+        self.queue.push(RunSystem::new(id));
+    }
 }
 
 /// A [`Command`] which gets executed for a given [`Entity`].
@@ -799,6 +833,39 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         });
     }
 
+    /// Relates the entity to `target` through the [`Relation`] `R`.
+    ///
+    /// See [`EntityMut::relate`](crate::world::EntityMut::relate) for more details.
+    ///
+    /// # Panics
+    ///
+    /// The command will panic when applied if either entity does not exist.
+    pub fn relate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        self.commands.add(Relate::<R>::new(self.entity, target));
+        self
+    }
+
+    /// Removes the [`Relation`] `R` between the entity and `target`, if it exists.
+    ///
+    /// See [`EntityMut::unrelate`](crate::world::EntityMut::unrelate) for more details.
+    pub fn unrelate<R: Relation>(&mut self, target: Entity) -> &mut Self {
+        self.commands.add(Unrelate::<R>::new(self.entity, target));
+        self
+    }
+
+    /// Attaches an observer to the entity, which runs `system` every time the event `E` is
+    /// triggered with this entity as its target.
+    ///
+    /// See [`EntityMut::observe`](crate::world::EntityMut::observe) for more details.
+    pub fn observe<E: Event, M>(
+        &mut self,
+        system: impl IntoSystem<Trigger<E>, (), M> + Send + 'static,
+    ) -> &mut Self {
+        self.add(move |entity: Entity, world: &mut World| {
+            world.entity_mut(entity).observe(system);
+        })
+    }
+
     /// Pushes an [`EntityCommand`] to the queue, which will get executed for the current [`Entity`].
     ///
     /// # Examples
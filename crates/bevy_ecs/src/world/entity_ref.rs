@@ -1,9 +1,10 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleInfo, BundleInserter, DynamicBundle},
+    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
     change_detection::MutUntyped,
     component::{Component, ComponentId, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    query::Access,
     removal_detection::RemovedComponentEvents,
     storage::Storages,
     world::{Mut, World},
@@ -338,10 +339,13 @@ impl<'w> EntityMut<'w> {
             self.location.archetype_id,
             change_tick,
         );
+        let old_archetype = self.location.archetype_id;
+        let bundle_id = bundle_info.id();
         // SAFETY: location matches current entity. `T` matches `bundle_info`
         unsafe {
             self.location = bundle_inserter.insert(self.entity, self.location, bundle);
         }
+        self.trigger_insert_hooks(bundle_id, old_archetype);
 
         self
     }
@@ -376,6 +380,8 @@ impl<'w> EntityMut<'w> {
             change_tick,
         );
 
+        let old_archetype = self.location.archetype_id;
+        let bundle_id = bundle_info.id();
         self.location = insert_dynamic_bundle(
             bundle_inserter,
             self.entity,
@@ -383,6 +389,7 @@ impl<'w> EntityMut<'w> {
             Some(component).into_iter(),
             Some(storage_type).into_iter(),
         );
+        self.trigger_insert_hooks(bundle_id, old_archetype);
 
         self
     }
@@ -419,6 +426,8 @@ impl<'w> EntityMut<'w> {
             change_tick,
         );
 
+        let old_archetype = self.location.archetype_id;
+        let bundle_id = bundle_info.id();
         self.location = insert_dynamic_bundle(
             bundle_inserter,
             self.entity,
@@ -426,6 +435,7 @@ impl<'w> EntityMut<'w> {
             iter_components,
             storage_types.iter().cloned(),
         );
+        self.trigger_insert_hooks(bundle_id, old_archetype);
 
         self
     }
@@ -437,6 +447,17 @@ impl<'w> EntityMut<'w> {
     // TODO: BundleRemover?
     #[must_use]
     pub fn take<T: Bundle>(&mut self) -> Option<T> {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let bundle_components = self.world.bundles.get(bundle_id).unwrap().components();
+        if bundle_components.iter().all(|id| archetype.contains(*id)) {
+            self.trigger_remove_hooks(bundle_id);
+        }
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -585,6 +606,13 @@ impl<'w> EntityMut<'w> {
     /// Removes any components in the [`Bundle`] from the entity.
     // TODO: BundleRemover?
     pub fn remove<T: Bundle>(&mut self) -> &mut Self {
+        let bundle_id = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components, &mut self.world.storages)
+            .id();
+        self.trigger_remove_hooks(bundle_id);
+
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -647,11 +675,39 @@ impl<'w> EntityMut<'w> {
         self
     }
 
+    /// Runs the hooks of the bundle `bundle_id` after it was inserted on this entity, and updates
+    /// the entity location in case a hook moved it.
+    fn trigger_insert_hooks(&mut self, bundle_id: BundleId, old_archetype: ArchetypeId) {
+        if self
+            .world
+            .trigger_insert_hooks(self.entity, bundle_id, Some(old_archetype))
+        {
+            self.update_location();
+        }
+    }
+
+    /// Runs the `on_remove` hooks of the components of the bundle `bundle_id` that this entity
+    /// has, and updates the entity location in case a hook moved it.
+    fn trigger_remove_hooks(&mut self, bundle_id: BundleId) {
+        if self
+            .world
+            .trigger_remove_hooks(self.entity, Some(bundle_id))
+        {
+            self.update_location();
+        }
+    }
+
     /// Despawns the current entity.
     pub fn despawn(self) {
         debug!("Despawning entity {:?}", self.entity);
         let world = self.world;
         world.flush();
+        world.despawn_relations(self.entity);
+        world.trigger_remove_hooks(self.entity, None);
+        if !world.entities.contains(self.entity) {
+            // The entity was despawned by one of its `on_remove` hooks.
+            return;
+        }
         let location = world
             .entities
             .free(self.entity)
@@ -825,6 +881,275 @@ impl<'w> EntityMut<'w> {
     }
 }
 
+/// Provides read-only access to a single entity and some of its components defined by the contained [`Access`].
+///
+/// This is the item type of [`FilteredEntityRef`] queries, which are built with a
+/// [`QueryBuilder`](crate::query::QueryBuilder): only the components the builder requested access
+/// to can be read.
+#[derive(Clone)]
+pub struct FilteredEntityRef<'w> {
+    entity: UnsafeEntityCell<'w>,
+    access: Access<ComponentId>,
+}
+
+impl<'w> FilteredEntityRef<'w> {
+    /// # Safety
+    ///
+    /// - No `&mut World` can exist from the underlying `UnsafeWorldCell`
+    /// - If `access` takes read access to a component no mutable reference to that
+    ///   component can exist at the same time as the returned [`FilteredEntityRef`]
+    /// - If `access` takes any access for a component `entity` must have that component.
+    pub(crate) unsafe fn new(entity: UnsafeEntityCell<'w>, access: Access<ComponentId>) -> Self {
+        Self { entity, access }
+    }
+
+    /// Returns the [ID](Entity) of the current entity.
+    #[inline]
+    #[must_use = "Omit the .id() call if you do not need to store the `Entity` identifier."]
+    pub fn id(&self) -> Entity {
+        self.entity.id()
+    }
+
+    /// Gets metadata indicating the location where the current entity is stored.
+    #[inline]
+    pub fn location(&self) -> EntityLocation {
+        self.entity.location()
+    }
+
+    /// Returns the archetype that the current entity belongs to.
+    #[inline]
+    pub fn archetype(&self) -> &Archetype {
+        self.entity.archetype()
+    }
+
+    /// Returns a reference to the underlying [`Access`].
+    #[inline]
+    pub fn access(&self) -> &Access<ComponentId> {
+        &self.access
+    }
+
+    /// Returns `true` if the current entity has a component of type `T`.
+    /// Otherwise, this returns `false`.
+    ///
+    /// This does not depend on the access: an entity can contain a component it cannot read.
+    #[inline]
+    pub fn contains<T: Component>(&self) -> bool {
+        self.contains_type_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the current entity has a component identified by `component_id`.
+    /// Otherwise, this returns false.
+    #[inline]
+    pub fn contains_id(&self, component_id: ComponentId) -> bool {
+        self.entity.contains_id(component_id)
+    }
+
+    /// Returns `true` if the current entity has a component with the type identified by `type_id`.
+    /// Otherwise, this returns false.
+    #[inline]
+    pub fn contains_type_id(&self, type_id: TypeId) -> bool {
+        self.entity.contains_type_id(type_id)
+    }
+
+    /// Gets access to the component of type `T` for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get<T: Component>(&self) -> Option<&'w T> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_read(id)
+            // SAFETY: We have read access
+            .then(|| unsafe { self.entity.get() })
+            .flatten()
+    }
+
+    /// Gets access to the component of type `T` for the current entity,
+    /// including change detection information as a [`Ref`].
+    ///
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get_ref<T: Component>(&self) -> Option<Ref<'w, T>> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_read(id)
+            // SAFETY: We have read access
+            .then(|| unsafe { self.entity.get_ref() })
+            .flatten()
+    }
+
+    /// Gets the component of the given [`ComponentId`] from the entity.
+    ///
+    /// **You should prefer to use the typed API [`FilteredEntityRef::get`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// Unlike [`FilteredEntityRef::get`], this returns a raw pointer to the component,
+    /// which is only valid while the `'w` borrow of the lifetime is active.
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<Ptr<'w>> {
+        self.access
+            .has_read(component_id)
+            // SAFETY: We have read access
+            .then(|| unsafe { self.entity.get_by_id(component_id) })
+            .flatten()
+    }
+}
+
+impl<'w> From<FilteredEntityMut<'w>> for FilteredEntityRef<'w> {
+    fn from(entity_mut: FilteredEntityMut<'w>) -> Self {
+        // SAFETY: `FilteredEntityMut` guarantees exclusive access to all components in the new `FilteredEntityRef`.
+        unsafe { FilteredEntityRef::new(entity_mut.entity, entity_mut.access) }
+    }
+}
+
+/// Provides mutable access to a single entity and some of its components defined by the contained [`Access`].
+///
+/// This is the item type of [`FilteredEntityMut`] queries, which are built with a
+/// [`QueryBuilder`](crate::query::QueryBuilder): only the components the builder requested access
+/// to can be read, and only those it requested write access to can be mutated.
+pub struct FilteredEntityMut<'w> {
+    entity: UnsafeEntityCell<'w>,
+    access: Access<ComponentId>,
+}
+
+impl<'w> FilteredEntityMut<'w> {
+    /// # Safety
+    ///
+    /// - No `&mut World` can exist from the underlying `UnsafeWorldCell`
+    /// - If `access` takes read access to a component no mutable reference to that
+    ///   component can exist at the same time as the returned [`FilteredEntityMut`]
+    /// - If `access` takes write access to a component, no reference to that component
+    ///   may exist at the same time as the returned [`FilteredEntityMut`]
+    /// - If `access` takes any access for a component `entity` must have that component.
+    pub(crate) unsafe fn new(entity: UnsafeEntityCell<'w>, access: Access<ComponentId>) -> Self {
+        Self { entity, access }
+    }
+
+    /// Returns a new instance with a shorter lifetime.
+    /// This is useful if you have `&mut FilteredEntityMut`, but you need `FilteredEntityMut`.
+    pub fn reborrow(&mut self) -> FilteredEntityMut<'_> {
+        // SAFETY: We have exclusive access to the entire entity and its components.
+        unsafe { Self::new(self.entity, self.access.clone()) }
+    }
+
+    /// Gets read-only access to all of the entity's components.
+    pub fn as_readonly(&self) -> FilteredEntityRef<'_> {
+        // SAFETY: `&self` ensures that no mutable references exist to the readable components.
+        unsafe { FilteredEntityRef::new(self.entity, self.access.clone()) }
+    }
+
+    /// Returns the [ID](Entity) of the current entity.
+    #[inline]
+    #[must_use = "Omit the .id() call if you do not need to store the `Entity` identifier."]
+    pub fn id(&self) -> Entity {
+        self.entity.id()
+    }
+
+    /// Gets metadata indicating the location where the current entity is stored.
+    #[inline]
+    pub fn location(&self) -> EntityLocation {
+        self.entity.location()
+    }
+
+    /// Returns the archetype that the current entity belongs to.
+    #[inline]
+    pub fn archetype(&self) -> &Archetype {
+        self.entity.archetype()
+    }
+
+    /// Returns a reference to the underlying [`Access`].
+    #[inline]
+    pub fn access(&self) -> &Access<ComponentId> {
+        &self.access
+    }
+
+    /// Returns `true` if the current entity has a component of type `T`.
+    /// Otherwise, this returns `false`.
+    ///
+    /// This does not depend on the access: an entity can contain a component it cannot read.
+    #[inline]
+    pub fn contains<T: Component>(&self) -> bool {
+        self.contains_type_id(TypeId::of::<T>())
+    }
+
+    /// Returns `true` if the current entity has a component identified by `component_id`.
+    /// Otherwise, this returns false.
+    #[inline]
+    pub fn contains_id(&self, component_id: ComponentId) -> bool {
+        self.entity.contains_id(component_id)
+    }
+
+    /// Returns `true` if the current entity has a component with the type identified by `type_id`.
+    /// Otherwise, this returns false.
+    #[inline]
+    pub fn contains_type_id(&self, type_id: TypeId) -> bool {
+        self.entity.contains_type_id(type_id)
+    }
+
+    /// Gets access to the component of type `T` for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get<T: Component>(&self) -> Option<&'_ T> {
+        self.as_readonly().get()
+    }
+
+    /// Gets access to the component of type `T` for the current entity,
+    /// including change detection information as a [`Ref`].
+    ///
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include it.
+    #[inline]
+    pub fn get_ref<T: Component>(&self) -> Option<Ref<'_, T>> {
+        self.as_readonly().get_ref()
+    }
+
+    /// Gets mutable access to the component of type `T` for the current entity.
+    /// Returns `None` if the entity does not have a component of type `T`,
+    /// or if the access does not include write access to it.
+    #[inline]
+    pub fn get_mut<T: Component>(&mut self) -> Option<Mut<'_, T>> {
+        let id = self.entity.world().components().get_id(TypeId::of::<T>())?;
+        self.access
+            .has_write(id)
+            // SAFETY: We have write access and `&mut self` ensures it is unique
+            .then(|| unsafe { self.entity.get_mut() })
+            .flatten()
+    }
+
+    /// Gets the component of the given [`ComponentId`] from the entity.
+    ///
+    /// **You should prefer to use the typed API [`FilteredEntityMut::get`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// Unlike [`FilteredEntityMut::get`], this returns a raw pointer to the component,
+    /// which is only valid while the [`FilteredEntityMut`] is alive.
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<Ptr<'_>> {
+        self.as_readonly().get_by_id(component_id)
+    }
+
+    /// Gets a [`MutUntyped`] of the component of the given [`ComponentId`] from the entity.
+    ///
+    /// **You should prefer to use the typed API [`FilteredEntityMut::get_mut`] where possible and only
+    /// use this in cases where the actual component types are not known at
+    /// compile time.**
+    ///
+    /// Unlike [`FilteredEntityMut::get_mut`], this returns a raw pointer to the component,
+    /// which is only valid while the [`FilteredEntityMut`] is alive.
+    #[inline]
+    pub fn get_mut_by_id(&mut self, component_id: ComponentId) -> Option<MutUntyped<'_>> {
+        self.access
+            .has_write(component_id)
+            // SAFETY: We have write access and `&mut self` ensures it is unique
+            .then(|| unsafe { self.entity.get_mut_by_id(component_id) })
+            .flatten()
+    }
+}
+
 /// Inserts a dynamic [`Bundle`] into the entity.
 ///
 /// # Safety
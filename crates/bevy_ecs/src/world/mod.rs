@@ -7,18 +7,22 @@ pub mod unsafe_world_cell;
 mod world_cell;
 
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
-pub use entity_ref::{EntityMut, EntityRef};
+pub use entity_ref::{EntityMut, EntityRef, FilteredEntityMut, FilteredEntityRef};
 pub use spawn_batch::*;
 pub use world_cell::*;
 
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeId, ArchetypeRow, Archetypes},
-    bundle::{Bundle, BundleInserter, BundleSpawner, Bundles},
+    bundle::{Bundle, BundleId, BundleInserter, BundleSpawner, Bundles},
     change_detection::{MutUntyped, TicksMut},
-    component::{Component, ComponentDescriptor, ComponentId, ComponentInfo, Components, Tick},
+    component::{
+        Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, Components,
+        Tick,
+    },
     entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
     event::{Event, Events},
     query::{DebugCheckedUnwrap, QueryState, ReadOnlyWorldQuery, WorldQuery},
+    relation::Relations,
     removal_detection::RemovedComponentEvents,
     schedule::{Schedule, ScheduleLabel, Schedules},
     storage::{ResourceData, Storages},
@@ -65,6 +69,7 @@ pub struct World {
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
     pub(crate) removed_components: RemovedComponentEvents,
+    pub(crate) relations: Relations,
     /// Access cache used by [`WorldCell`]. Is only accessed in the `Drop` impl of `WorldCell`.
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
     pub(crate) change_tick: AtomicU32,
@@ -82,6 +87,7 @@ impl Default for World {
             storages: Default::default(),
             bundles: Default::default(),
             removed_components: Default::default(),
+            relations: Default::default(),
             archetype_component_access: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
             // are detected on first system runs and for direct world queries.
@@ -222,6 +228,121 @@ impl World {
         self.components.component_id::<T>()
     }
 
+    /// Returns a mutable reference to the [`ComponentHooks`] of the [`Component`] type `T`,
+    /// initializing the component if needed.
+    ///
+    /// Hooks let a component react synchronously when it is added to, inserted into, or removed
+    /// from an entity, which makes them well suited to keeping indexes up to date.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_utils::HashSet;
+    /// #[derive(Component)]
+    /// struct Tracked;
+    ///
+    /// #[derive(Resource, Default)]
+    /// struct TrackedEntities(HashSet<Entity>);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<TrackedEntities>();
+    /// world
+    ///     .register_component_hooks::<Tracked>()
+    ///     .on_add(|world, entity, _| {
+    ///         world.resource_mut::<TrackedEntities>().0.insert(entity);
+    ///     })
+    ///     .on_remove(|world, entity, _| {
+    ///         world.resource_mut::<TrackedEntities>().0.remove(&entity);
+    ///     });
+    ///
+    /// let entity = world.spawn(Tracked).id();
+    /// assert!(world.resource::<TrackedEntities>().0.contains(&entity));
+    /// world.despawn(entity);
+    /// assert!(world.resource::<TrackedEntities>().0.is_empty());
+    /// ```
+    pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
+        let id = self.init_component::<T>();
+        self.components
+            .get_hooks_mut(id)
+            .expect("component was just initialized")
+    }
+
+    /// Returns a mutable reference to the [`ComponentHooks`] of the component with the given
+    /// [`ComponentId`], or `None` if no such component exists in this [`World`].
+    ///
+    /// See [`World::register_component_hooks`] for the typed version.
+    pub fn register_component_hooks_by_id(
+        &mut self,
+        id: ComponentId,
+    ) -> Option<&mut ComponentHooks> {
+        self.components.get_hooks_mut(id)
+    }
+
+    /// Runs the `on_add` and `on_insert` hooks of the bundle `bundle_id`, which was just inserted
+    /// on `entity`. Components already present in `old_archetype` only run their `on_insert` hook.
+    ///
+    /// Returns `true` if any hook was run.
+    pub(crate) fn trigger_insert_hooks(
+        &mut self,
+        entity: Entity,
+        bundle_id: BundleId,
+        old_archetype: Option<ArchetypeId>,
+    ) -> bool {
+        let bundle_components = self.bundles.get(bundle_id).unwrap().components();
+        let on_add = self.components.collect_hooks(
+            bundle_components.iter().copied().filter(|id| {
+                old_archetype.map_or(true, |archetype| !self.archetypes[archetype].contains(*id))
+            }),
+            |hooks| hooks.on_add,
+        );
+        let on_insert = self
+            .components
+            .collect_hooks(bundle_components.iter().copied(), |hooks| hooks.on_insert);
+        if on_add.is_empty() && on_insert.is_empty() {
+            return false;
+        }
+        for (hook, id) in on_add.into_iter().chain(on_insert) {
+            hook(self, entity, id);
+        }
+        true
+    }
+
+    /// Runs the `on_remove` hooks of the components of `entity` that are part of the bundle
+    /// `bundle_id`, or of all its components if `bundle_id` is `None`.
+    ///
+    /// Returns `true` if any hook was run.
+    pub(crate) fn trigger_remove_hooks(
+        &mut self,
+        entity: Entity,
+        bundle_id: Option<BundleId>,
+    ) -> bool {
+        let Some(location) = self.entities.get(entity) else {
+            return false;
+        };
+        let archetype = &self.archetypes[location.archetype_id];
+        let on_remove = match bundle_id {
+            Some(bundle_id) => self.components.collect_hooks(
+                self.bundles
+                    .get(bundle_id)
+                    .unwrap()
+                    .components()
+                    .iter()
+                    .copied()
+                    .filter(|id| archetype.contains(*id)),
+                |hooks| hooks.on_remove,
+            ),
+            None => self
+                .components
+                .collect_hooks(archetype.components(), |hooks| hooks.on_remove),
+        };
+        if on_remove.is_empty() {
+            return false;
+        }
+        for (hook, id) in on_remove {
+            hook(self, entity, id);
+        }
+        true
+    }
+
     /// Retrieves an [`EntityRef`] that exposes read-only operations for the given `entity`.
     /// This will panic if the `entity` does not exist. Use [`World::get_entity`] if you want
     /// to check for entity existence instead of implicitly panic-ing.
@@ -512,7 +633,7 @@ impl World {
         self.flush();
         let change_tick = self.change_tick();
         let entity = self.entities.alloc();
-        let entity_location = {
+        let (entity_location, bundle_id) = {
             let bundle_info = self
                 .bundles
                 .init_info::<B>(&mut self.components, &mut self.storages);
@@ -525,10 +646,19 @@ impl World {
             );
 
             // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
-            unsafe { spawner.spawn_non_existent(entity, bundle) }
+            let location = unsafe { spawner.spawn_non_existent(entity, bundle) };
+            (location, bundle_info.id())
         };
 
-        // SAFETY: entity and location are valid, as they were just created above
+        let entity_location = if self.trigger_insert_hooks(entity, bundle_id, None) {
+            self.entities
+                .get(entity)
+                .expect("component hooks must not despawn the entity being spawned")
+        } else {
+            entity_location
+        };
+
+        // SAFETY: entity and location are valid, as they were just created or updated above
         unsafe { EntityMut::new(self, entity, entity_location) }
     }
 
@@ -1211,6 +1341,16 @@ impl World {
         let bundle_info = self
             .bundles
             .init_info::<B>(&mut self.components, &mut self.storages);
+        let bundle_id = bundle_info.id();
+        let has_hooks = !self
+            .components
+            .collect_hooks(bundle_info.components().iter().copied(), |hooks| {
+                hooks.on_add.or(hooks.on_insert)
+            })
+            .is_empty();
+        // Hooks need exclusive world access, so they are run once the whole batch is inserted.
+        let mut hooked_entities = Vec::new();
+
         enum SpawnOrInsert<'a, 'b> {
             Spawn(BundleSpawner<'a, 'b>),
             Insert(BundleInserter<'a, 'b>, ArchetypeId),
@@ -1239,6 +1379,9 @@ impl World {
                 .alloc_at_without_replacement(entity)
             {
                 AllocAtWithoutReplacement::Exists(location) => {
+                    if has_hooks {
+                        hooked_entities.push((entity, Some(location.archetype_id)));
+                    }
                     match spawn_or_insert {
                         SpawnOrInsert::Insert(ref mut inserter, archetype)
                             if location.archetype_id == archetype =>
@@ -1263,6 +1406,9 @@ impl World {
                     };
                 }
                 AllocAtWithoutReplacement::DidNotExist => {
+                    if has_hooks {
+                        hooked_entities.push((entity, None));
+                    }
                     if let SpawnOrInsert::Spawn(ref mut spawner) = spawn_or_insert {
                         // SAFETY: `entity` is allocated (but non existent), bundle matches inserter
                         unsafe { spawner.spawn_non_existent(entity, bundle) };
@@ -1285,6 +1431,12 @@ impl World {
             }
         }
 
+        for (entity, old_archetype) in hooked_entities {
+            if self.entities.contains(entity) {
+                self.trigger_insert_hooks(entity, bundle_id, old_archetype);
+            }
+        }
+
         if invalid_entities.is_empty() {
             Ok(())
         } else {
@@ -1577,7 +1729,29 @@ impl World {
     }
 
     /// Despawns all entities in this [`World`].
+    ///
+    /// Entities with a component that has an `on_remove` [hook](ComponentHooks) are despawned one
+    /// by one first, so that their hooks run and their [relations](crate::relation) are cleaned up.
     pub fn clear_entities(&mut self) {
+        let hooked_entities: Vec<Entity> = self
+            .archetypes
+            .iter()
+            .filter(|archetype| {
+                archetype.components().any(|id| {
+                    self.components
+                        .get_info(id)
+                        .is_some_and(|info| info.hooks().on_remove.is_some())
+                })
+            })
+            .flat_map(|archetype| archetype.entities().iter().map(|entity| entity.entity()))
+            .collect();
+        for entity in hooked_entities {
+            // A previous hook or relation may have despawned the entity already.
+            if let Some(entity) = self.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+
         self.storages.tables.clear();
         self.storages.sparse_sets.clear_entities();
         self.archetypes.clear_entities();
@@ -1903,7 +2077,8 @@ mod tests {
     use super::{FromWorld, World};
     use crate::{
         change_detection::DetectChangesMut,
-        component::{ComponentDescriptor, ComponentInfo, StorageType},
+        component::{ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, StorageType},
+        entity::Entity,
         ptr::OwningPtr,
         system::Resource,
     };
@@ -2291,4 +2466,167 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[derive(Resource, Default)]
+    struct HookLog(Vec<&'static str>);
+
+    #[derive(Component)]
+    #[component(on_add = log_add, on_insert = log_insert, on_remove = log_remove)]
+    struct Hooked(u32);
+
+    #[derive(Component)]
+    struct Other;
+
+    fn log_add(world: &mut World, _: Entity, _: ComponentId) {
+        world.resource_mut::<HookLog>().0.push("add");
+    }
+
+    fn log_insert(world: &mut World, _: Entity, _: ComponentId) {
+        world.resource_mut::<HookLog>().0.push("insert");
+    }
+
+    fn log_remove(world: &mut World, entity: Entity, _: ComponentId) {
+        // The component can still be read in `on_remove` hooks.
+        assert!(world.get::<Hooked>(entity).is_some());
+        world.resource_mut::<HookLog>().0.push("remove");
+    }
+
+    fn take_hook_log(world: &mut World) -> Vec<&'static str> {
+        std::mem::take(&mut world.resource_mut::<HookLog>().0)
+    }
+
+    #[test]
+    fn component_hooks() {
+        let mut world = World::new();
+        world.init_resource::<HookLog>();
+
+        let entity = world.spawn(Hooked(0)).id();
+        assert_eq!(take_hook_log(&mut world), vec!["add", "insert"]);
+
+        world.entity_mut(entity).insert(Hooked(1));
+        assert_eq!(take_hook_log(&mut world), vec!["insert"]);
+
+        world.entity_mut(entity).insert(Other);
+        assert!(take_hook_log(&mut world).is_empty());
+
+        world.entity_mut(entity).remove::<Hooked>();
+        assert_eq!(take_hook_log(&mut world), vec!["remove"]);
+
+        // Removing a missing component doesn't run hooks.
+        world.entity_mut(entity).remove::<Hooked>();
+        assert!(take_hook_log(&mut world).is_empty());
+
+        world.entity_mut(entity).insert(Hooked(2));
+        assert_eq!(take_hook_log(&mut world), vec!["add", "insert"]);
+
+        let taken = world.entity_mut(entity).take::<(Hooked, Other)>();
+        assert_eq!(taken.map(|(hooked, _)| hooked.0), Some(2));
+        assert_eq!(take_hook_log(&mut world), vec!["remove"]);
+
+        world.entity_mut(entity).insert(Hooked(3));
+        take_hook_log(&mut world);
+        world.despawn(entity);
+        assert_eq!(take_hook_log(&mut world), vec!["remove"]);
+    }
+
+    #[test]
+    fn clear_entities_runs_remove_hooks() {
+        let mut world = World::new();
+        world.init_resource::<HookLog>();
+        world.spawn(Hooked(0));
+        world.spawn((Hooked(1), Other));
+        world.spawn(Other);
+        take_hook_log(&mut world);
+
+        world.clear_entities();
+        assert_eq!(take_hook_log(&mut world), vec!["remove", "remove"]);
+        assert_eq!(world.entities().len(), 0);
+    }
+
+    #[test]
+    fn component_hooks_batch() {
+        let mut world = World::new();
+        world.init_resource::<HookLog>();
+
+        let entities: Vec<_> = world.spawn_batch((0..2).map(Hooked)).collect();
+        assert_eq!(
+            take_hook_log(&mut world),
+            vec!["add", "insert", "add", "insert"]
+        );
+
+        world
+            .insert_or_spawn_batch(entities.into_iter().map(|entity| (entity, Hooked(5))))
+            .unwrap();
+        assert_eq!(take_hook_log(&mut world), vec!["insert", "insert"]);
+    }
+
+    #[test]
+    fn runtime_component_hooks() {
+        #[derive(Component)]
+        struct Indexed;
+
+        #[derive(Resource, Default)]
+        struct Index(HashSet<Entity>);
+
+        let mut world = World::new();
+        world.init_resource::<Index>();
+        world
+            .register_component_hooks::<Indexed>()
+            .on_add(|world, entity, _| {
+                world.resource_mut::<Index>().0.insert(entity);
+            })
+            .on_remove(|world, entity, _| {
+                world.resource_mut::<Index>().0.remove(&entity);
+            });
+
+        let a = world.spawn(Indexed).id();
+        let b = world.spawn(Indexed).id();
+        assert_eq!(world.resource::<Index>().0.len(), 2);
+
+        world.entity_mut(a).remove::<Indexed>();
+        world.despawn(b);
+        assert!(world.resource::<Index>().0.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn component_hooks_cannot_be_registered_twice() {
+        #[derive(Component)]
+        #[component(on_add = log_add)]
+        struct AlreadyHooked;
+
+        let mut world = World::new();
+        world
+            .register_component_hooks::<AlreadyHooked>()
+            .on_add(log_add);
+    }
+
+    #[test]
+    fn dynamic_component_hooks() {
+        let mut world = World::new();
+        world.init_resource::<HookLog>();
+
+        let mut hooks = ComponentHooks::default();
+        hooks.on_add(log_add);
+        // SAFETY: `u32` has the layout `Layout::new::<u32>()` and doesn't need to be dropped.
+        let descriptor = unsafe {
+            ComponentDescriptor::new_with_layout(
+                "Dynamic",
+                StorageType::Table,
+                std::alloc::Layout::new::<u32>(),
+                None,
+            )
+        }
+        .with_hooks(hooks);
+        let component_id = world.init_component_with_descriptor(descriptor);
+
+        let mut entity = world.spawn_empty();
+        OwningPtr::make(7u32, |ptr| {
+            // SAFETY: `ptr` points to a `u32`, matching the descriptor.
+            unsafe {
+                entity.insert_by_id(component_id, ptr);
+            }
+        });
+        assert_eq!(take_hook_log(&mut world), vec!["add"]);
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{
-    bundle::{Bundle, BundleSpawner},
+    bundle::{Bundle, BundleId, BundleSpawner},
+    component::Tick,
     entity::Entity,
     world::World,
 };
@@ -15,7 +16,18 @@ where
     I::Item: Bundle,
 {
     inner: I,
-    spawner: BundleSpawner<'w, 'w>,
+    state: SpawnBatchState<'w>,
+}
+
+enum SpawnBatchState<'w> {
+    /// None of the bundle's components have hooks, so a single spawner can be reused.
+    Spawner(BundleSpawner<'w, 'w>),
+    /// Some components have hooks, which need exclusive access to the world after each spawn.
+    WithHooks {
+        world: &'w mut World,
+        bundle_id: BundleId,
+        change_tick: Tick,
+    },
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -34,10 +46,32 @@ where
         let (lower, upper) = iter.size_hint();
         let length = upper.unwrap_or(lower);
 
-        let bundle_info = world
-            .bundles
-            .init_info::<I::Item>(&mut world.components, &mut world.storages);
         world.entities.reserve(length as u32);
+
+        let (bundle_id, has_hooks) = {
+            let bundle_info = world
+                .bundles
+                .init_info::<I::Item>(&mut world.components, &mut world.storages);
+            let has_hooks = !world
+                .components
+                .collect_hooks(bundle_info.components().iter().copied(), |hooks| {
+                    hooks.on_add.or(hooks.on_insert)
+                })
+                .is_empty();
+            (bundle_info.id(), has_hooks)
+        };
+        if has_hooks {
+            return Self {
+                inner: iter,
+                state: SpawnBatchState::WithHooks {
+                    world,
+                    bundle_id,
+                    change_tick,
+                },
+            };
+        }
+
+        let bundle_info = world.bundles.get(bundle_id).unwrap();
         let mut spawner = bundle_info.get_bundle_spawner(
             &mut world.entities,
             &mut world.archetypes,
@@ -49,7 +83,7 @@ where
 
         Self {
             inner: iter,
-            spawner,
+            state: SpawnBatchState::Spawner(spawner),
         }
     }
 }
@@ -73,8 +107,28 @@ where
 
     fn next(&mut self) -> Option<Entity> {
         let bundle = self.inner.next()?;
-        // SAFETY: bundle matches spawner type
-        unsafe { Some(self.spawner.spawn(bundle)) }
+        match &mut self.state {
+            // SAFETY: bundle matches spawner type
+            SpawnBatchState::Spawner(spawner) => unsafe { Some(spawner.spawn(bundle)) },
+            SpawnBatchState::WithHooks {
+                world,
+                bundle_id,
+                change_tick,
+            } => {
+                let bundle_info = world.bundles.get(*bundle_id).unwrap();
+                let mut spawner = bundle_info.get_bundle_spawner(
+                    &mut world.entities,
+                    &mut world.archetypes,
+                    &mut world.components,
+                    &mut world.storages,
+                    *change_tick,
+                );
+                // SAFETY: bundle matches spawner type
+                let entity = unsafe { spawner.spawn(bundle) };
+                world.trigger_insert_hooks(entity, *bundle_id, None);
+                Some(entity)
+            }
+        }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -33,6 +33,7 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
+use bevy_ecs::schedule::StateScopedActions;
 
 /// The base plugin for handling [`Parent`] and [`Children`] components
 #[derive(Default)]
@@ -44,5 +45,9 @@ impl Plugin for HierarchyPlugin {
             .register_type::<Parent>()
             .register_type::<smallvec::SmallVec<[bevy_ecs::entity::Entity; 8]>>()
             .add_event::<HierarchyEvent>();
+        // Entities scoped to a state are despawned along with their children.
+        app.world
+            .get_resource_or_insert_with(StateScopedActions::default)
+            .despawn = despawn_with_children_recursive;
     }
 }
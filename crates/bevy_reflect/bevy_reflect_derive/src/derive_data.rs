@@ -1,5 +1,6 @@
 use crate::container_attributes::{FromReflectAttrs, ReflectTraits};
 use crate::field_attributes::{parse_field_attrs, ReflectFieldAttr};
+use crate::registration::InspectedField;
 use crate::type_path::parse_path_no_leading_colon;
 use crate::utility::{members_to_serialization_denylist, StringExpr, WhereClauseOptions};
 use bit_set::BitSet;
@@ -402,7 +403,7 @@ impl<'a> ReflectMeta<'a> {
         &self,
         where_clause_options: &WhereClauseOptions,
     ) -> proc_macro2::TokenStream {
-        crate::registration::impl_get_type_registration(self, where_clause_options, None)
+        crate::registration::impl_get_type_registration(self, where_clause_options, None, &[])
     }
 
     /// The collection of docstrings for this type, if any.
@@ -437,9 +438,24 @@ impl<'a> ReflectStruct<'a> {
             self.meta(),
             where_clause_options,
             Some(&self.serialization_denylist),
+            &self.inspected_fields(),
         )
     }
 
+    /// Get the reflected fields with inspector options, indexed among the reflected fields.
+    fn inspected_fields(&self) -> Vec<InspectedField<'_>> {
+        self.active_fields()
+            .enumerate()
+            .filter_map(|(index, field)| {
+                Some(InspectedField {
+                    variant: None,
+                    index,
+                    attr: field.attrs.inspect.as_ref()?,
+                })
+            })
+            .collect()
+    }
+
     /// Get a collection of types which are exposed to the reflection API
     pub fn active_types(&self) -> Vec<syn::Type> {
         self.active_fields()
@@ -491,6 +507,37 @@ impl<'a> ReflectEnum<'a> {
         &self.variants
     }
 
+    /// Returns the `GetTypeRegistration` impl as a `TokenStream`.
+    ///
+    /// Returns a specific implementation for enums and this method should be preferred over the generic [`get_type_registration`](crate::ReflectMeta) method
+    pub fn get_type_registration(
+        &self,
+        where_clause_options: &WhereClauseOptions,
+    ) -> proc_macro2::TokenStream {
+        let inspected_fields = self
+            .variants()
+            .iter()
+            .flat_map(|variant| {
+                variant
+                    .active_fields()
+                    .enumerate()
+                    .filter_map(|(index, field)| {
+                        Some(InspectedField {
+                            variant: Some(&variant.data.ident),
+                            index,
+                            attr: field.attrs.inspect.as_ref()?,
+                        })
+                    })
+            })
+            .collect::<Vec<_>>();
+        crate::registration::impl_get_type_registration(
+            self.meta(),
+            where_clause_options,
+            None,
+            &inspected_fields,
+        )
+    }
+
     /// Get an iterator of fields which are exposed to the reflection API
     pub fn active_fields(&self) -> impl Iterator<Item = &StructField<'a>> {
         self.variants()
@@ -6,13 +6,20 @@
 
 use crate::REFLECT_ATTRIBUTE_NAME;
 use syn::meta::ParseNestedMeta;
-use syn::{Attribute, LitStr, Token};
+use syn::{Attribute, Expr, LitStr, Token};
 
 pub(crate) static IGNORE_SERIALIZATION_ATTR: &str = "skip_serializing";
 pub(crate) static IGNORE_ALL_ATTR: &str = "ignore";
 
 pub(crate) static DEFAULT_ATTR: &str = "default";
 
+pub(crate) static INSPECT_ATTR: &str = "inspect";
+pub(crate) static DISPLAY_NAME_ATTR: &str = "display_name";
+pub(crate) static MIN_ATTR: &str = "min";
+pub(crate) static MAX_ATTR: &str = "max";
+pub(crate) static STEP_ATTR: &str = "step";
+pub(crate) static READ_ONLY_ATTR: &str = "read_only";
+
 /// Stores data about if the field should be visible via the Reflect and serialization interfaces
 ///
 /// Note the relationship between serialization and reflection is such that a member must be reflected in order to be serialized.
@@ -51,6 +58,18 @@ pub(crate) struct ReflectFieldAttr {
     pub ignore: ReflectIgnoreBehavior,
     /// Sets the default behavior of this field.
     pub default: DefaultBehavior,
+    /// The inspector options of this field, if any.
+    pub inspect: Option<InspectAttr>,
+}
+
+/// The inspector options of a field, given with `#[reflect(inspect(...))]`.
+#[derive(Default, Clone)]
+pub(crate) struct InspectAttr {
+    pub display_name: Option<LitStr>,
+    pub min: Option<Expr>,
+    pub max: Option<Expr>,
+    pub step: Option<Expr>,
+    pub read_only: bool,
 }
 
 /// Controls how the default value is determined for a field.
@@ -136,11 +155,55 @@ fn parse_meta(args: &mut ReflectFieldAttr, meta: ParseNestedMeta) -> Result<(),
 
         args.ignore = ReflectIgnoreBehavior::IgnoreSerialization;
 
+        Ok(())
+    } else if meta.path.is_ident(INSPECT_ATTR) {
+        // Allow:
+        // - `#[reflect(inspect(display_name = "Speed", min = 0.0, max = 10.0, step = 0.5, read_only))]`
+        if args.inspect.is_some() {
+            return Err(meta.error(format!("only one of [{:?}] is allowed", [INSPECT_ATTR])));
+        }
+
+        let mut inspect = InspectAttr::default();
+        meta.parse_nested_meta(|meta| parse_inspect_meta(&mut inspect, meta))?;
+        args.inspect = Some(inspect);
+
         Ok(())
     } else {
         Err(meta.error(format!(
             "unknown attribute, expected {:?}",
-            [DEFAULT_ATTR, IGNORE_ALL_ATTR, IGNORE_SERIALIZATION_ATTR]
+            [
+                DEFAULT_ATTR,
+                IGNORE_ALL_ATTR,
+                IGNORE_SERIALIZATION_ATTR,
+                INSPECT_ATTR
+            ]
         )))
     }
 }
+
+fn parse_inspect_meta(inspect: &mut InspectAttr, meta: ParseNestedMeta) -> Result<(), syn::Error> {
+    if meta.path.is_ident(DISPLAY_NAME_ATTR) {
+        inspect.display_name = Some(meta.value()?.parse()?);
+    } else if meta.path.is_ident(MIN_ATTR) {
+        inspect.min = Some(meta.value()?.parse()?);
+    } else if meta.path.is_ident(MAX_ATTR) {
+        inspect.max = Some(meta.value()?.parse()?);
+    } else if meta.path.is_ident(STEP_ATTR) {
+        inspect.step = Some(meta.value()?.parse()?);
+    } else if meta.path.is_ident(READ_ONLY_ATTR) {
+        inspect.read_only = true;
+    } else {
+        return Err(meta.error(format!(
+            "unknown inspect attribute, expected {:?}",
+            [
+                DISPLAY_NAME_ATTR,
+                MIN_ATTR,
+                MAX_ATTR,
+                STEP_ATTR,
+                READ_ONLY_ATTR
+            ]
+        )));
+    }
+
+    Ok(())
+}
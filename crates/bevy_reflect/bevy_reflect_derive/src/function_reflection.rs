@@ -0,0 +1,288 @@
+use crate::fq_std::{FQBox, FQResult};
+use bevy_macro_utils::BevyManifest;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{
+    parse_macro_input, FnArg, GenericParam, ImplItem, ImplItemFn, ItemImpl, Pat, Path, ReturnType,
+    Type,
+};
+
+const REFLECT_ATTRIBUTE_NAME: &str = "reflect";
+const IGNORE_ATTR: &str = "ignore";
+
+/// How an argument or return value is passed.
+#[derive(Clone, Copy)]
+enum Ownership {
+    Owned,
+    Ref,
+    Mut,
+}
+
+impl Ownership {
+    /// Returns how a value of type `ty` is passed, and its type without the reference.
+    fn of(ty: &Type) -> (Self, &Type) {
+        match ty {
+            Type::Reference(reference) if reference.mutability.is_some() => {
+                (Self::Mut, &reference.elem)
+            }
+            Type::Reference(reference) => (Self::Ref, &reference.elem),
+            ty => (Self::Owned, ty),
+        }
+    }
+
+    /// Returns an error spanning `spanned` if `ty` is passed by reference but is known to be
+    /// unsized, as only references to sized types can be passed as `&dyn Reflect`.
+    fn check_sized(self, ty: &Type, spanned: &impl Spanned) -> syn::Result<()> {
+        if let Self::Owned = self {
+            return Ok(());
+        }
+        let referent = match ty {
+            Type::Slice(_) => "slices",
+            Type::TraitObject(_) => "trait objects",
+            Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => "`str`",
+            _ => return Ok(()),
+        };
+        Err(syn::Error::new(
+            spanned.span(),
+            format_args!(
+                "references to {referent} cannot be reflected, consider passing an owned value \
+                or adding `#[reflect(ignore)]`"
+            ),
+        ))
+    }
+
+    fn to_tokens(self, bevy_reflect_path: &Path) -> TokenStream2 {
+        let variant = match self {
+            Self::Owned => quote!(Owned),
+            Self::Ref => quote!(Ref),
+            Self::Mut => quote!(Mut),
+        };
+        quote!(#bevy_reflect_path::func::Ownership::#variant)
+    }
+}
+
+/// An attribute macro for inherent impl blocks that reflects their functions.
+///
+/// This implements `TypeFunctions` for the type, returning a `DynamicFunction` for each function
+/// of the block that isn't marked with `#[reflect(ignore)]`.
+pub(crate) fn reflect_functions(_args: &TokenStream, input: TokenStream) -> TokenStream {
+    let mut item_impl = parse_macro_input!(input as ItemImpl);
+    let bevy_reflect_path = BevyManifest::default().get_path("bevy_reflect");
+
+    if let Some((_, trait_path, _)) = &item_impl.trait_ {
+        return syn::Error::new(
+            trait_path.span(),
+            "#[reflect_functions] can only be used on inherent impl blocks",
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let mut functions = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    for item in &mut item_impl.items {
+        let ImplItem::Fn(function) = item else {
+            continue;
+        };
+        match take_ignore_attribute(function) {
+            Ok(true) => continue,
+            Ok(false) => {}
+            Err(error) => {
+                combine(&mut errors, error);
+                continue;
+            }
+        }
+        match reflect_function(function, &bevy_reflect_path) {
+            Ok(tokens) => functions.push(tokens),
+            Err(error) => combine(&mut errors, error),
+        }
+    }
+    if let Some(errors) = errors {
+        return errors.into_compile_error().into();
+    }
+
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+
+    TokenStream::from(quote! {
+        #item_impl
+
+        impl #impl_generics #bevy_reflect_path::func::TypeFunctions for #self_ty #where_clause {
+            fn functions() -> ::std::vec::Vec<#bevy_reflect_path::func::DynamicFunction> {
+                ::std::vec![#(#functions),*]
+            }
+        }
+    })
+}
+
+fn combine(errors: &mut Option<syn::Error>, error: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(error),
+        None => *errors = Some(error),
+    }
+}
+
+/// Removes the `#[reflect(ignore)]` attribute from `function`, returning whether it had one.
+fn take_ignore_attribute(function: &mut ImplItemFn) -> syn::Result<bool> {
+    let mut ignored = false;
+    let mut result = Ok(());
+    function.attrs.retain(|attr| {
+        if !attr.path().is_ident(REFLECT_ATTRIBUTE_NAME) {
+            return true;
+        }
+        result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(IGNORE_ATTR) {
+                ignored = true;
+                Ok(())
+            } else {
+                Err(meta.error(format_args!(
+                    "unknown function attribute, expected `{IGNORE_ATTR}`"
+                )))
+            }
+        });
+        false
+    });
+    result.map(|_| ignored)
+}
+
+fn reflect_function(function: &ImplItemFn, bevy_reflect_path: &Path) -> syn::Result<TokenStream2> {
+    let signature = &function.sig;
+    if let Some(param) = signature
+        .generics
+        .params
+        .iter()
+        .find(|param| !matches!(param, GenericParam::Lifetime(_)))
+    {
+        return Err(syn::Error::new(
+            param.span(),
+            "generic functions cannot be reflected, consider adding `#[reflect(ignore)]`",
+        ));
+    }
+    if let Some(asyncness) = &signature.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "async functions cannot be reflected, consider adding `#[reflect(ignore)]`",
+        ));
+    }
+    if let Some(unsafety) = &signature.unsafety {
+        return Err(syn::Error::new(
+            unsafety.span(),
+            "unsafe functions cannot be reflected, consider adding `#[reflect(ignore)]`",
+        ));
+    }
+
+    let self_ty: Type = syn::parse_quote!(Self);
+    let mut arg_infos = Vec::new();
+    let mut arg_takes = Vec::new();
+    let mut arg_idents = Vec::new();
+    for (index, input) in signature.inputs.iter().enumerate() {
+        let (ownership, ty, name) = match input {
+            FnArg::Receiver(receiver) => {
+                if receiver.colon_token.is_some() {
+                    return Err(syn::Error::new(
+                        receiver.span(),
+                        "only `self`, `&self` and `&mut self` receivers can be reflected",
+                    ));
+                }
+                let ownership = match (&receiver.reference, &receiver.mutability) {
+                    (None, _) => Ownership::Owned,
+                    (Some(_), Some(_)) => Ownership::Mut,
+                    (Some(_), None) => Ownership::Ref,
+                };
+                (ownership, &self_ty, Some("self".to_string()))
+            }
+            FnArg::Typed(pat_type) => {
+                if let Type::ImplTrait(impl_trait) = &*pat_type.ty {
+                    return Err(syn::Error::new(
+                        impl_trait.span(),
+                        "`impl Trait` arguments cannot be reflected, consider adding `#[reflect(ignore)]`",
+                    ));
+                }
+                let name = match &*pat_type.pat {
+                    Pat::Ident(pat_ident) => Some(pat_ident.ident.unraw().to_string()),
+                    _ => None,
+                };
+                let (ownership, ty) = Ownership::of(&pat_type.ty);
+                ownership.check_sized(ty, pat_type)?;
+                (ownership, ty, name)
+            }
+        };
+
+        let ownership_tokens = ownership.to_tokens(bevy_reflect_path);
+        let with_name = name.map(|name| quote!(.with_name(#name)));
+        arg_infos.push(quote! {
+            #bevy_reflect_path::func::ArgInfo::new::<#ty>(#index, #ownership_tokens) #with_name
+        });
+
+        let ident = format_ident!("arg{index}");
+        let take = match ownership {
+            Ownership::Owned => quote!(take_owned),
+            Ownership::Ref => quote!(take_ref),
+            Ownership::Mut => quote!(take_mut),
+        };
+        arg_takes.push(quote! {
+            let #ident = args.next().unwrap().#take::<#ty>(#index)?;
+        });
+        arg_idents.push(ident);
+    }
+
+    let function_ident = &signature.ident;
+    let name = function_ident.unraw().to_string();
+    let call = quote!(Self::#function_ident(#(#arg_idents),*));
+    let (return_info, call_and_return) = match &signature.output {
+        ReturnType::Type(_, ty) if !matches!(&**ty, Type::Tuple(tuple) if tuple.elems.is_empty()) =>
+        {
+            let (ownership, return_ty) = Ownership::of(ty);
+            ownership.check_sized(return_ty, ty)?;
+            let return_value = match ownership {
+                Ownership::Owned => {
+                    quote!(#bevy_reflect_path::func::Return::Owned(#FQBox::new(#call)))
+                }
+                Ownership::Ref => quote!(#bevy_reflect_path::func::Return::Ref(#call)),
+                Ownership::Mut => quote!(#bevy_reflect_path::func::Return::Mut(#call)),
+            };
+            let ownership = ownership.to_tokens(bevy_reflect_path);
+            (
+                quote!(#bevy_reflect_path::func::ReturnInfo::new::<#return_ty>(#ownership)),
+                quote!(#FQResult::Ok(#return_value)),
+            )
+        }
+        _ => (
+            quote! {
+                #bevy_reflect_path::func::ReturnInfo::new::<()>(
+                    #bevy_reflect_path::func::Ownership::Owned
+                )
+            },
+            quote! {
+                #call;
+                #FQResult::Ok(#bevy_reflect_path::func::Return::Unit)
+            },
+        ),
+    };
+
+    let take_args = if arg_takes.is_empty() {
+        quote!(let _ = args;)
+    } else {
+        quote! {
+            let mut args = ::core::iter::IntoIterator::into_iter(args);
+            #(#arg_takes)*
+        }
+    };
+
+    Ok(quote! {
+        #bevy_reflect_path::func::DynamicFunction::new(
+            #bevy_reflect_path::func::FunctionInfo::new(
+                #name,
+                ::std::vec![#(#arg_infos),*],
+                #return_info,
+            ),
+            |args| {
+                #take_args
+                #call_and_return
+            },
+        )
+    })
+}
@@ -5,12 +5,13 @@
 //! such as `Struct`, `GetTypeRegistration`, and more— all with a single derive!
 //!
 //! Some other noteworthy exports include the derive macros for [`FromReflect`] and
-//! [`TypeUuid`], as well as the [`reflect_trait`] attribute macro.
+//! [`TypeUuid`], as well as the [`reflect_trait`] and [`reflect_functions`] attribute macros.
 //!
 //! [`Reflect`]: crate::derive_reflect
 //! [`FromReflect`]: crate::derive_from_reflect
 //! [`TypeUuid`]: crate::derive_type_uuid
 //! [`reflect_trait`]: macro@reflect_trait
+//! [`reflect_functions`]: macro@reflect_functions
 
 extern crate proc_macro;
 
@@ -22,6 +23,7 @@ mod enum_utility;
 mod field_attributes;
 mod fq_std;
 mod from_reflect;
+mod function_reflection;
 mod impls;
 mod reflect_value;
 mod registration;
@@ -345,6 +347,60 @@ pub fn reflect_trait(args: TokenStream, input: TokenStream) -> TokenStream {
     trait_reflection::reflect_trait(&args, input)
 }
 
+/// An attribute macro for inherent impl blocks that makes their functions callable through
+/// reflection.
+///
+/// This implements `TypeFunctions` for the type, which returns a `DynamicFunction` for each
+/// function of the block, taking and returning reflected values. The receiver of methods is
+/// their first argument. The functions can then be registered as `ReflectFunctions` type data,
+/// for instance with `#[reflect(Functions)]`.
+///
+/// Generic, async and unsafe functions can't be reflected, nor can functions taking `impl Trait`
+/// arguments or taking or returning references to unsized types such as `&str` or `&[T]`: they
+/// must be marked with `#[reflect(ignore)]` to be skipped. The arguments and return values of the
+/// other functions must implement `Reflect`, and the arguments taken by value `FromReflect`.
+///
+/// As `TypeFunctions` can only be implemented once per type, only one impl block of a type can
+/// use this attribute.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Reflect)]
+/// #[reflect(Functions)] // Registers `ReflectFunctions`
+/// struct Counter {
+///     count: u32,
+/// }
+///
+/// #[reflect_functions]
+/// impl Counter {
+///     fn add(&mut self, amount: u32) -> u32 {
+///         self.count += amount;
+///         self.count
+///     }
+///
+///     #[reflect(ignore)]
+///     fn add_all(&mut self, amounts: impl IntoIterator<Item = u32>) {
+///         self.count += amounts.into_iter().sum::<u32>();
+///     }
+/// }
+///
+/// let mut registry = TypeRegistry::default();
+/// registry.register::<Counter>();
+/// let functions = registry
+///     .get_type_data::<ReflectFunctions>(TypeId::of::<Counter>())
+///     .unwrap();
+///
+/// let mut counter = Counter { count: 1 };
+/// let args = ArgList::new().push_mut(&mut counter).push_owned(2_u32);
+/// let count = functions.call("add", args).unwrap().unwrap_owned();
+/// assert_eq!(count.downcast_ref::<u32>(), Some(&3));
+/// ```
+#[proc_macro_attribute]
+pub fn reflect_functions(args: TokenStream, input: TokenStream) -> TokenStream {
+    function_reflection::reflect_functions(&args, input)
+}
+
 /// A macro used to generate reflection trait implementations for the given type.
 ///
 /// This is functionally the same as [deriving `Reflect`] using the `#[reflect_value]` container attribute.
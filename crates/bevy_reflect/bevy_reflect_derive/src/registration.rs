@@ -1,9 +1,20 @@
 //! Contains code related specifically to Bevy's type registration.
 
 use crate::derive_data::ReflectMeta;
+use crate::field_attributes::InspectAttr;
 use crate::utility::{extend_where_clause, WhereClauseOptions};
 use bit_set::BitSet;
 use quote::quote;
+use syn::Ident;
+
+/// A reflected field with inspector options, see [`InspectAttr`].
+pub(crate) struct InspectedField<'a> {
+    /// The variant containing the field, for enums.
+    pub variant: Option<&'a Ident>,
+    /// The index of the field among the reflected fields of its struct or variant.
+    pub index: usize,
+    pub attr: &'a InspectAttr,
+}
 
 /// Creates the `GetTypeRegistration` impl for the given type data.
 #[allow(clippy::too_many_arguments)]
@@ -11,6 +22,7 @@ pub(crate) fn impl_get_type_registration(
     meta: &ReflectMeta,
     where_clause_options: &WhereClauseOptions,
     serialization_denylist: Option<&BitSet<u32>>,
+    inspected_fields: &[InspectedField],
 ) -> proc_macro2::TokenStream {
     let type_path = meta.type_path();
     let bevy_reflect_path = meta.bevy_reflect_path();
@@ -34,6 +46,44 @@ pub(crate) fn impl_get_type_registration(
         }
     });
 
+    let inspector_options = if inspected_fields.is_empty() {
+        None
+    } else {
+        let inserts = inspected_fields.iter().map(|field| {
+            let index = field.index;
+            let InspectAttr {
+                display_name,
+                min,
+                max,
+                step,
+                read_only,
+            } = field.attr;
+            let display_name = display_name
+                .as_ref()
+                .map(|name| quote!(.with_display_name(#name)));
+            let min = min.as_ref().map(|min| quote!(.with_min((#min) as f64)));
+            let max = max.as_ref().map(|max| quote!(.with_max((#max) as f64)));
+            let step = step.as_ref().map(|step| quote!(.with_step((#step) as f64)));
+            let field_options = quote! {
+                #bevy_reflect_path::inspect::FieldOptions::new()
+                    #display_name #min #max #step
+                    .with_read_only(#read_only)
+            };
+            match field.variant {
+                Some(variant) => {
+                    let variant = variant.to_string();
+                    quote!(options.insert_variant_field(#variant, #index, #field_options);)
+                }
+                None => quote!(options.insert_field(#index, #field_options);),
+            }
+        });
+        Some(quote! {
+            let mut options = #bevy_reflect_path::inspect::InspectorOptions::default();
+            #(#inserts)*
+            registration.insert::<#bevy_reflect_path::inspect::InspectorOptions>(options);
+        })
+    };
+
     quote! {
         #[allow(unused_mut)]
         impl #impl_generics #bevy_reflect_path::GetTypeRegistration for #type_path #ty_generics #where_reflect_clause {
@@ -42,6 +92,7 @@ pub(crate) fn impl_get_type_registration(
                 registration.insert::<#bevy_reflect_path::ReflectFromPtr>(#bevy_reflect_path::FromType::<Self>::from_type());
                 #from_reflect_data
                 #serialization_data
+                #inspector_options
                 #(registration.insert::<#registration_data>(#bevy_reflect_path::FromType::<Self>::from_type());)*
                 registration
             }
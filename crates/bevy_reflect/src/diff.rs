@@ -0,0 +1,685 @@
+use thiserror::Error;
+
+use crate::{List, Map, ParsedPath, Reflect, ReflectMut, ReflectRef, Set, VariantType};
+
+/// A change to a reflected value, as part of a [`Patch`].
+///
+/// The element the operation applies to is found at [`path`](Self::path), relative to the
+/// value the patch is applied to.
+#[derive(Debug)]
+pub enum PatchOperation {
+    /// Replaces the element at `path` with `value`.
+    Set {
+        path: ParsedPath,
+        value: Box<dyn Reflect>,
+    },
+    /// Inserts `value` at `index` in the [`List`] at `path`.
+    Insert {
+        path: ParsedPath,
+        index: usize,
+        value: Box<dyn Reflect>,
+    },
+    /// Removes the element at `index` from the [`List`] at `path`.
+    Remove { path: ParsedPath, index: usize },
+    /// Inserts `value` under `key` in the [`Map`] at `path`, replacing any previous value.
+    InsertEntry {
+        path: ParsedPath,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    },
+    /// Removes the entry with `key` from the [`Map`] at `path`.
+    RemoveEntry {
+        path: ParsedPath,
+        key: Box<dyn Reflect>,
+    },
+    /// Inserts `value` into the [`Set`] at `path`.
+    InsertValue {
+        path: ParsedPath,
+        value: Box<dyn Reflect>,
+    },
+    /// Removes `value` from the [`Set`] at `path`.
+    RemoveValue {
+        path: ParsedPath,
+        value: Box<dyn Reflect>,
+    },
+}
+
+impl PatchOperation {
+    /// Returns the path to the element this operation applies to.
+    pub fn path(&self) -> &ParsedPath {
+        match self {
+            Self::Set { path, .. }
+            | Self::Insert { path, .. }
+            | Self::Remove { path, .. }
+            | Self::InsertEntry { path, .. }
+            | Self::RemoveEntry { path, .. }
+            | Self::InsertValue { path, .. }
+            | Self::RemoveValue { path, .. } => path,
+        }
+    }
+
+    /// Applies this operation to `target`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value of the operation can't be applied to the element at its path, see
+    /// [`Reflect::apply`], [`List::insert`], [`Map::insert_boxed`] and [`Set::insert_boxed`].
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<(), PatchError> {
+        let path = self.path();
+        let element =
+            path.reflect_element_mut(target)
+                .map_err(|error| PatchError::InvalidPath {
+                    path: path.to_string(),
+                    error: error.to_string(),
+                })?;
+
+        match self {
+            Self::Set { value, .. } => {
+                if let Err(value) = element.set(value.clone_value()) {
+                    element.apply(&*value);
+                }
+            }
+            Self::Insert { index, value, .. } => {
+                let list = as_list(element, path)?;
+                if *index > list.len() {
+                    return Err(PatchError::IndexOutOfBounds {
+                        path: path.to_string(),
+                        index: *index,
+                    });
+                }
+                list.insert(*index, value.clone_value());
+            }
+            Self::Remove { index, .. } => {
+                let list = as_list(element, path)?;
+                if *index >= list.len() {
+                    return Err(PatchError::IndexOutOfBounds {
+                        path: path.to_string(),
+                        index: *index,
+                    });
+                }
+                list.remove(*index);
+            }
+            Self::InsertEntry { key, value, .. } => {
+                as_map(element, path)?.insert_boxed(key.clone_value(), value.clone_value());
+            }
+            Self::RemoveEntry { key, .. } => {
+                as_map(element, path)?.remove(&**key);
+            }
+            Self::InsertValue { value, .. } => {
+                as_set(element, path)?.insert_boxed(value.clone_value());
+            }
+            Self::RemoveValue { value, .. } => {
+                as_set(element, path)?.remove(&**value);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn as_list<'a>(
+    element: &'a mut dyn Reflect,
+    path: &ParsedPath,
+) -> Result<&'a mut dyn List, PatchError> {
+    match element.reflect_mut() {
+        ReflectMut::List(list) => Ok(list),
+        _ => Err(PatchError::ExpectedList(path.to_string())),
+    }
+}
+
+fn as_map<'a>(
+    element: &'a mut dyn Reflect,
+    path: &ParsedPath,
+) -> Result<&'a mut dyn Map, PatchError> {
+    match element.reflect_mut() {
+        ReflectMut::Map(map) => Ok(map),
+        _ => Err(PatchError::ExpectedMap(path.to_string())),
+    }
+}
+
+fn as_set<'a>(
+    element: &'a mut dyn Reflect,
+    path: &ParsedPath,
+) -> Result<&'a mut dyn Set, PatchError> {
+    match element.reflect_mut() {
+        ReflectMut::Set(set) => Ok(set),
+        _ => Err(PatchError::ExpectedSet(path.to_string())),
+    }
+}
+
+/// An error returned when applying a [`Patch`] fails.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PatchError {
+    #[error("invalid path `{path}`: {error}")]
+    InvalidPath { path: String, error: String },
+    #[error("expected a list at `{0}`")]
+    ExpectedList(String),
+    #[error("expected a map at `{0}`")]
+    ExpectedMap(String),
+    #[error("expected a set at `{0}`")]
+    ExpectedSet(String),
+    #[error("index {index} is out of bounds of the list at `{path}`")]
+    IndexOutOfBounds { path: String, index: usize },
+}
+
+/// The changes between two reflected values of the same type.
+///
+/// [`Patch::diff`] walks both values and records the elements that differ as a list of
+/// [`PatchOperation`]s, addressed with [`ParsedPath`]s:
+/// - the fields of structs, tuple structs, tuples, arrays and enums are compared one by one,
+///   unless the enums are of different variants, in which case the whole enum is replaced,
+/// - elements inserted into or removed from lists are detected, so that a single insertion
+///   doesn't change every following element,
+/// - entries inserted into or removed from maps are detected, and changed entries are inserted
+///   again with their new value,
+/// - values inserted into or removed from sets are detected,
+/// - other values are compared with [`Reflect::reflect_partial_eq`], and replaced if they
+///   aren't equal.
+///
+/// Applying the patch to a value equal to the old one makes it equal to the new one. To undo
+/// the changes, apply the patch from the new value to the old one.
+///
+/// Patches can be serialized with [`PatchSerializer`](crate::serde::PatchSerializer) and
+/// deserialized with [`PatchDeserializer`](crate::serde::PatchDeserializer).
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{Patch, Reflect};
+/// #[derive(Reflect, Clone, Debug, PartialEq)]
+/// struct Inventory {
+///     gold: u32,
+///     items: Vec<String>,
+/// }
+///
+/// let old = Inventory {
+///     gold: 10,
+///     items: vec!["sword".to_string(), "shield".to_string()],
+/// };
+/// let new = Inventory {
+///     gold: 5,
+///     items: vec!["bow".to_string(), "sword".to_string(), "shield".to_string()],
+/// };
+///
+/// let patch = Patch::diff(&old, &new);
+/// assert_eq!(patch.operations().len(), 2);
+///
+/// let mut value = old.clone();
+/// patch.apply(&mut value).unwrap();
+/// assert_eq!(value, new);
+/// ```
+#[derive(Debug, Default)]
+pub struct Patch {
+    operations: Vec<PatchOperation>,
+}
+
+impl Patch {
+    /// Creates a patch applying the given operations in order.
+    pub fn new(operations: Vec<PatchOperation>) -> Self {
+        Self { operations }
+    }
+
+    /// Computes the changes from `old` to `new`.
+    pub fn diff(old: &dyn Reflect, new: &dyn Reflect) -> Self {
+        let mut operations = Vec::new();
+        diff_value(String::new(), old, new, &mut operations);
+        Self { operations }
+    }
+
+    /// Returns the operations of this patch, in the order they are applied.
+    pub fn operations(&self) -> &[PatchOperation] {
+        &self.operations
+    }
+
+    /// Returns the operations of this patch, in the order they are applied.
+    pub fn into_operations(self) -> Vec<PatchOperation> {
+        self.operations
+    }
+
+    /// Returns `true` if this patch doesn't change anything.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Applies the operations of this patch to `target`, in order.
+    ///
+    /// Stops at the first operation that fails, leaving the previous ones applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value of the patch can't be applied to the element at its path, see
+    /// [`PatchOperation::apply`].
+    pub fn apply(&self, target: &mut dyn Reflect) -> Result<(), PatchError> {
+        for operation in &self.operations {
+            operation.apply(target)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_path(path: &str) -> ParsedPath {
+    ParsedPath::parse(path).expect("paths to the fields of reflected values should be valid")
+}
+
+fn set(path: String, new: &dyn Reflect, operations: &mut Vec<PatchOperation>) {
+    operations.push(PatchOperation::Set {
+        path: parse_path(&path),
+        value: new.clone_value(),
+    });
+}
+
+fn diff_value(
+    path: String,
+    old: &dyn Reflect,
+    new: &dyn Reflect,
+    operations: &mut Vec<PatchOperation>,
+) {
+    if old.type_name() != new.type_name() {
+        set(path, new, operations);
+        return;
+    }
+
+    match (old.reflect_ref(), new.reflect_ref()) {
+        (ReflectRef::Struct(old_struct), ReflectRef::Struct(new_struct)) => {
+            let fields = (0..new_struct.field_len())
+                .map(|index| {
+                    let name = new_struct.name_at(index).unwrap();
+                    (
+                        name,
+                        old_struct.field(name),
+                        new_struct.field_at(index).unwrap(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if old_struct.field_len() != fields.len()
+                || fields.iter().any(|(_, old, _)| old.is_none())
+            {
+                set(path, new, operations);
+                return;
+            }
+            for (name, old_field, new_field) in fields {
+                diff_value(
+                    format!("{path}.{name}"),
+                    old_field.unwrap(),
+                    new_field,
+                    operations,
+                );
+            }
+        }
+        (ReflectRef::TupleStruct(old_tuple), ReflectRef::TupleStruct(new_tuple)) => {
+            if old_tuple.field_len() != new_tuple.field_len() {
+                set(path, new, operations);
+                return;
+            }
+            for (index, (old_field, new_field)) in old_tuple
+                .iter_fields()
+                .zip(new_tuple.iter_fields())
+                .enumerate()
+            {
+                diff_value(format!("{path}.{index}"), old_field, new_field, operations);
+            }
+        }
+        (ReflectRef::Tuple(old_tuple), ReflectRef::Tuple(new_tuple)) => {
+            if old_tuple.field_len() != new_tuple.field_len() {
+                set(path, new, operations);
+                return;
+            }
+            for (index, (old_field, new_field)) in old_tuple
+                .iter_fields()
+                .zip(new_tuple.iter_fields())
+                .enumerate()
+            {
+                diff_value(format!("{path}.{index}"), old_field, new_field, operations);
+            }
+        }
+        (ReflectRef::Array(old_array), ReflectRef::Array(new_array)) => {
+            if old_array.len() != new_array.len() {
+                set(path, new, operations);
+                return;
+            }
+            for (index, (old_item, new_item)) in old_array.iter().zip(new_array.iter()).enumerate()
+            {
+                diff_value(format!("{path}[{index}]"), old_item, new_item, operations);
+            }
+        }
+        (ReflectRef::List(old_list), ReflectRef::List(new_list)) => {
+            diff_list(path, old_list, new_list, operations);
+        }
+        (ReflectRef::Map(old_map), ReflectRef::Map(new_map)) => {
+            diff_map(path, old_map, new_map, operations);
+        }
+        (ReflectRef::Set(old_set), ReflectRef::Set(new_set)) => {
+            diff_set(path, old_set, new_set, operations);
+        }
+        (ReflectRef::Enum(old_enum), ReflectRef::Enum(new_enum)) => {
+            if old_enum.variant_name() != new_enum.variant_name()
+                || old_enum.field_len() != new_enum.field_len()
+            {
+                set(path, new, operations);
+                return;
+            }
+            for (index, (old_field, new_field)) in old_enum
+                .iter_fields()
+                .zip(new_enum.iter_fields())
+                .enumerate()
+            {
+                let path = match new_enum.variant_type() {
+                    VariantType::Struct => format!("{path}.{}", new_field.name().unwrap()),
+                    _ => format!("{path}.{index}"),
+                };
+                diff_value(path, old_field.value(), new_field.value(), operations);
+            }
+        }
+        _ => {
+            if old.reflect_partial_eq(new) != Some(true) {
+                set(path, new, operations);
+            }
+        }
+    }
+}
+
+/// Diffs two lists from the longest common subsequence of their elements: the elements outside
+/// of it are removed or inserted, or diffed against each other when one replaces the other.
+fn diff_list(path: String, old: &dyn List, new: &dyn List, operations: &mut Vec<PatchOperation>) {
+    let (old_len, new_len) = (old.len(), new.len());
+    let equal = old
+        .iter()
+        .map(|old_item| {
+            new.iter()
+                .map(|new_item| old_item.reflect_partial_eq(new_item) == Some(true))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // the length of the longest common subsequence of `old[i..]` and `new[j..]`
+    let mut common = vec![vec![0; new_len + 1]; old_len + 1];
+    for i in (0..old_len).rev() {
+        for j in (0..new_len).rev() {
+            common[i][j] = if equal[i][j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    // `index` is the position in the list as the operations are applied
+    let (mut i, mut j, mut index) = (0, 0, 0);
+    while i < old_len || j < new_len {
+        if i < old_len && j < new_len && (equal[i][j] || common[i + 1][j + 1] == common[i][j]) {
+            if !equal[i][j] {
+                diff_value(
+                    format!("{path}[{index}]"),
+                    old.get(i).unwrap(),
+                    new.get(j).unwrap(),
+                    operations,
+                );
+            }
+            i += 1;
+            j += 1;
+            index += 1;
+        } else if j == new_len || (i < old_len && common[i + 1][j] >= common[i][j + 1]) {
+            operations.push(PatchOperation::Remove {
+                path: parse_path(&path),
+                index,
+            });
+            i += 1;
+        } else {
+            operations.push(PatchOperation::Insert {
+                path: parse_path(&path),
+                index,
+                value: new.get(j).unwrap().clone_value(),
+            });
+            j += 1;
+            index += 1;
+        }
+    }
+}
+
+fn diff_map(path: String, old: &dyn Map, new: &dyn Map, operations: &mut Vec<PatchOperation>) {
+    for (key, _) in old.iter() {
+        if new.get(key).is_none() {
+            operations.push(PatchOperation::RemoveEntry {
+                path: parse_path(&path),
+                key: key.clone_value(),
+            });
+        }
+    }
+    for (key, value) in new.iter() {
+        let unchanged = old
+            .get(key)
+            .and_then(|old_value| old_value.reflect_partial_eq(value))
+            .unwrap_or(false);
+        if !unchanged {
+            operations.push(PatchOperation::InsertEntry {
+                path: parse_path(&path),
+                key: key.clone_value(),
+                value: value.clone_value(),
+            });
+        }
+    }
+}
+
+fn diff_set(path: String, old: &dyn Set, new: &dyn Set, operations: &mut Vec<PatchOperation>) {
+    for value in old.iter() {
+        if !new.contains(value) {
+            operations.push(PatchOperation::RemoveValue {
+                path: parse_path(&path),
+                value: value.clone_value(),
+            });
+        }
+    }
+    for value in new.iter() {
+        if !old.contains(value) {
+            operations.push(PatchOperation::InsertValue {
+                path: parse_path(&path),
+                value: value.clone_value(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_reflect;
+    use crate::{Patch, PatchError, PatchOperation, Reflect};
+    use bevy_utils::HashMap;
+    use std::collections::BTreeSet;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Character {
+        name: String,
+        position: (f32, f32),
+        stats: [u8; 3],
+        inventory: Vec<Item>,
+        flags: HashMap<String, bool>,
+        skills: BTreeSet<String>,
+        state: State,
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Item(String, u32);
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Walking { speed: f32 },
+    }
+
+    fn item(name: &str, count: u32) -> Item {
+        Item(name.to_string(), count)
+    }
+
+    fn character() -> Character {
+        Character {
+            name: "hero".to_string(),
+            position: (0.0, 0.0),
+            stats: [1, 2, 3],
+            inventory: vec![item("sword", 1), item("potion", 3), item("shield", 1)],
+            flags: HashMap::from([("met_king".to_string(), true)]),
+            skills: BTreeSet::from(["swimming".to_string()]),
+            state: State::Walking { speed: 1.0 },
+        }
+    }
+
+    fn paths(patch: &Patch) -> Vec<String> {
+        patch
+            .operations()
+            .iter()
+            .map(|operation| match operation {
+                PatchOperation::Set { path, .. } => format!("set {path}"),
+                PatchOperation::Insert { path, index, .. } => format!("insert {path}[{index}]"),
+                PatchOperation::Remove { path, index } => format!("remove {path}[{index}]"),
+                PatchOperation::InsertEntry { path, .. } => format!("insert entry {path}"),
+                PatchOperation::RemoveEntry { path, .. } => format!("remove entry {path}"),
+                PatchOperation::InsertValue { path, .. } => format!("insert value {path}"),
+                PatchOperation::RemoveValue { path, .. } => format!("remove value {path}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_diff_nothing_for_equal_values() {
+        assert!(Patch::diff(&character(), &character()).is_empty());
+    }
+
+    #[test]
+    fn should_diff_and_apply_fields() {
+        let old = character();
+        let mut new = character();
+        new.name = "villain".to_string();
+        new.position.1 = 2.0;
+        new.stats[2] = 4;
+        new.state = State::Walking { speed: 2.0 };
+
+        let patch = Patch::diff(&old, &new);
+        assert_eq!(
+            paths(&patch),
+            vec![
+                "set .name",
+                "set .position.1",
+                "set .stats[2]",
+                "set .state.speed",
+            ]
+        );
+
+        let mut value = old.clone();
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+
+        new.state = State::Idle;
+        let patch = Patch::diff(&old, &new);
+        assert_eq!(
+            paths(&patch),
+            vec![
+                "set .name",
+                "set .position.1",
+                "set .stats[2]",
+                "set .state",
+            ]
+        );
+        let mut value = old;
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn should_detect_list_insertions_and_removals() {
+        let old = character();
+        let mut new = character();
+        new.inventory = vec![
+            item("bow", 1),
+            item("sword", 1),
+            item("potion", 2),
+            item("arrow", 20),
+        ];
+
+        let patch = Patch::diff(&old, &new);
+        assert_eq!(
+            paths(&patch),
+            vec![
+                "insert .inventory[0]",
+                "set .inventory[2].1",
+                "set .inventory[3].0",
+                "set .inventory[3].1",
+            ]
+        );
+
+        let mut value = old.clone();
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+
+        // undo
+        let mut value = new.clone();
+        Patch::diff(&new, &old).apply(&mut value).unwrap();
+        assert_eq!(value, old);
+
+        new.inventory = vec![item("shield", 1)];
+        let patch = Patch::diff(&old, &new);
+        assert_eq!(
+            paths(&patch),
+            vec!["remove .inventory[0]", "remove .inventory[0]"]
+        );
+        let mut value = old;
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn should_diff_map_entries() {
+        let old = character();
+        let mut new = character();
+        new.flags.remove("met_king");
+        new.flags.insert("has_boat".to_string(), true);
+
+        let patch = Patch::diff(&old, &new);
+        assert_eq!(
+            paths(&patch),
+            vec!["remove entry .flags", "insert entry .flags"]
+        );
+
+        let mut value = old;
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn should_diff_set_values() {
+        let old = character();
+        let mut new = character();
+        new.skills.remove("swimming");
+        new.skills.insert("sailing".to_string());
+
+        let patch = Patch::diff(&old, &new);
+        assert_eq!(
+            paths(&patch),
+            vec!["remove value .skills", "insert value .skills"]
+        );
+
+        let mut value = old;
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn should_fail_to_apply_to_invalid_paths() {
+        let mut new = character();
+        new.inventory.push(item("key", 1));
+        let patch = Patch::diff(&character(), &new);
+
+        let mut value = (1u8, 2u8);
+        assert!(matches!(
+            patch.apply(&mut value),
+            Err(PatchError::InvalidPath { .. })
+        ));
+
+        let mut value = character();
+        value.inventory.clear();
+        assert_eq!(
+            patch.apply(&mut value),
+            Err(PatchError::IndexOutOfBounds {
+                path: ".inventory".to_string(),
+                index: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::any::type_name;
+use std::fmt;
+
+use crate::func::FunctionError;
+use crate::{FromReflect, Reflect};
+
+/// How an argument or return value of a reflected function is passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Ownership {
+    /// By value, as `T`.
+    Owned,
+    /// By reference, as `&T`.
+    Ref,
+    /// By mutable reference, as `&mut T`.
+    Mut,
+}
+
+impl fmt::Display for Ownership {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Owned => f.write_str("an owned value"),
+            Self::Ref => f.write_str("a reference"),
+            Self::Mut => f.write_str("a mutable reference"),
+        }
+    }
+}
+
+/// An argument passed to a [`DynamicFunction`](crate::func::DynamicFunction).
+#[derive(Debug)]
+pub enum Arg<'a> {
+    /// An argument passed by value.
+    Owned(Box<dyn Reflect>),
+    /// An argument passed by reference.
+    Ref(&'a dyn Reflect),
+    /// An argument passed by mutable reference.
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Arg<'a> {
+    /// Returns how this argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        match self {
+            Self::Owned(_) => Ownership::Owned,
+            Self::Ref(_) => Ownership::Ref,
+            Self::Mut(_) => Ownership::Mut,
+        }
+    }
+
+    /// Returns the value of this argument.
+    pub fn value(&self) -> &dyn Reflect {
+        match self {
+            Self::Owned(value) => &**value,
+            Self::Ref(value) => *value,
+            Self::Mut(value) => &**value,
+        }
+    }
+
+    /// Takes the value of an argument passed by value, as the argument at `index` of a function.
+    ///
+    /// Dynamic values are converted to `T` with [`FromReflect`].
+    pub fn take_owned<T: FromReflect>(self, index: usize) -> Result<T, FunctionError> {
+        match self {
+            Self::Owned(value) => T::take_from_reflect(value)
+                .map_err(|value| FunctionError::invalid_type::<T>(index, &*value)),
+            arg => Err(FunctionError::invalid_ownership(
+                index,
+                Ownership::Owned,
+                arg.ownership(),
+            )),
+        }
+    }
+
+    /// Takes the value of an argument passed by reference, as the argument at `index` of a
+    /// function.
+    ///
+    /// Mutable references are accepted as well.
+    pub fn take_ref<T: Reflect>(self, index: usize) -> Result<&'a T, FunctionError> {
+        let value: &'a dyn Reflect = match self {
+            Self::Ref(value) => value,
+            Self::Mut(value) => value,
+            Self::Owned(_) => {
+                return Err(FunctionError::invalid_ownership(
+                    index,
+                    Ownership::Ref,
+                    Ownership::Owned,
+                ))
+            }
+        };
+        value
+            .downcast_ref()
+            .ok_or_else(|| FunctionError::invalid_type::<T>(index, value))
+    }
+
+    /// Takes the value of an argument passed by mutable reference, as the argument at `index`
+    /// of a function.
+    pub fn take_mut<T: Reflect>(self, index: usize) -> Result<&'a mut T, FunctionError> {
+        match self {
+            Self::Mut(value) => {
+                if value.is::<T>() {
+                    Ok(value.downcast_mut().unwrap())
+                } else {
+                    Err(FunctionError::invalid_type::<T>(index, value))
+                }
+            }
+            arg => Err(FunctionError::invalid_ownership(
+                index,
+                Ownership::Mut,
+                arg.ownership(),
+            )),
+        }
+    }
+}
+
+/// The arguments passed to a [`DynamicFunction`](crate::func::DynamicFunction), in order.
+///
+/// For methods, the first argument is the receiver.
+///
+/// ```
+/// # use bevy_reflect::func::ArgList;
+/// let mut name = String::from("player");
+/// let args = ArgList::new().push_mut(&mut name).push_owned(1_usize);
+/// assert_eq!(args.len(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct ArgList<'a>(Vec<Arg<'a>>);
+
+impl<'a> ArgList<'a> {
+    /// Creates an empty list of arguments.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Appends an argument.
+    pub fn push(mut self, arg: Arg<'a>) -> Self {
+        self.0.push(arg);
+        self
+    }
+
+    /// Appends an argument passed by value.
+    pub fn push_owned<T: Reflect>(self, value: T) -> Self {
+        self.push(Arg::Owned(Box::new(value)))
+    }
+
+    /// Appends an argument passed by value.
+    pub fn push_boxed(self, value: Box<dyn Reflect>) -> Self {
+        self.push(Arg::Owned(value))
+    }
+
+    /// Appends an argument passed by reference.
+    pub fn push_ref(self, value: &'a dyn Reflect) -> Self {
+        self.push(Arg::Ref(value))
+    }
+
+    /// Appends an argument passed by mutable reference.
+    pub fn push_mut(self, value: &'a mut dyn Reflect) -> Self {
+        self.push(Arg::Mut(value))
+    }
+
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the arguments.
+    pub fn iter(&self) -> impl Iterator<Item = &Arg<'a>> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for ArgList<'a> {
+    type Item = Arg<'a>;
+    type IntoIter = std::vec::IntoIter<Arg<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl FunctionError {
+    fn invalid_type<T>(index: usize, value: &dyn Reflect) -> Self {
+        Self::InvalidArgType {
+            index,
+            expected: type_name::<T>(),
+            received: value.type_name().to_string(),
+        }
+    }
+
+    fn invalid_ownership(index: usize, expected: Ownership, received: Ownership) -> Self {
+        Self::InvalidArgOwnership {
+            index,
+            expected,
+            received,
+        }
+    }
+}
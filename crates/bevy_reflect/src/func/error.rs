@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+use crate::func::Ownership;
+
+/// An error returned when calling a [`DynamicFunction`](crate::func::DynamicFunction) fails.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FunctionError {
+    #[error("no function named `{0}`")]
+    NotFound(String),
+    #[error("expected {expected} arguments but received {received}")]
+    ArgCount { expected: usize, received: usize },
+    #[error("expected argument {index} to be of type `{expected}` but received `{received}`")]
+    InvalidArgType {
+        index: usize,
+        expected: &'static str,
+        received: String,
+    },
+    #[error("expected argument {index} to be {expected} but received {received}")]
+    InvalidArgOwnership {
+        index: usize,
+        expected: Ownership,
+        received: Ownership,
+    },
+}
@@ -0,0 +1,295 @@
+use std::any::type_name;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::func::{ArgList, FunctionError, Ownership};
+use crate::{FromType, Reflect};
+
+/// The value returned by a [`DynamicFunction`].
+#[derive(Debug)]
+pub enum Return<'a> {
+    /// The function doesn't return anything.
+    Unit,
+    /// A value returned by value.
+    Owned(Box<dyn Reflect>),
+    /// A value returned by reference.
+    Ref(&'a dyn Reflect),
+    /// A value returned by mutable reference.
+    Mut(&'a mut dyn Reflect),
+}
+
+impl<'a> Return<'a> {
+    /// Returns `true` if the function didn't return anything.
+    pub fn is_unit(&self) -> bool {
+        matches!(self, Self::Unit)
+    }
+
+    /// Returns the returned value, or `None` if the function didn't return anything.
+    pub fn value(&self) -> Option<&dyn Reflect> {
+        match self {
+            Self::Unit => None,
+            Self::Owned(value) => Some(&**value),
+            Self::Ref(value) => Some(*value),
+            Self::Mut(value) => Some(&**value),
+        }
+    }
+
+    /// Returns the value returned by value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value isn't returned by value.
+    pub fn unwrap_owned(self) -> Box<dyn Reflect> {
+        match self {
+            Self::Owned(value) => value,
+            _ => panic!("expected the function to return an owned value"),
+        }
+    }
+
+    /// Returns the value returned by reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value isn't returned by reference.
+    pub fn unwrap_ref(self) -> &'a dyn Reflect {
+        match self {
+            Self::Ref(value) => value,
+            _ => panic!("expected the function to return a reference"),
+        }
+    }
+
+    /// Returns the value returned by mutable reference.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value isn't returned by mutable reference.
+    pub fn unwrap_mut(self) -> &'a mut dyn Reflect {
+        match self {
+            Self::Mut(value) => value,
+            _ => panic!("expected the function to return a mutable reference"),
+        }
+    }
+}
+
+/// The result of calling a [`DynamicFunction`].
+pub type FunctionResult<'a> = Result<Return<'a>, FunctionError>;
+
+/// A container for compile-time info related to an argument of a [`DynamicFunction`].
+#[derive(Clone, Debug)]
+pub struct ArgInfo {
+    index: usize,
+    name: Option<&'static str>,
+    type_name: &'static str,
+    ownership: Ownership,
+}
+
+impl ArgInfo {
+    /// Creates info for the argument at `index`, of type `T` passed as `ownership`.
+    pub fn new<T: ?Sized>(index: usize, ownership: Ownership) -> Self {
+        Self {
+            index,
+            name: None,
+            type_name: type_name::<T>(),
+            ownership,
+        }
+    }
+
+    /// Sets the name of the argument.
+    pub fn with_name(self, name: &'static str) -> Self {
+        Self {
+            name: Some(name),
+            ..self
+        }
+    }
+
+    /// The index of the argument.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The name of the argument, if it is a plain identifier.
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// The [type name] of the argument, without the reference.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// How the argument is passed.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+}
+
+/// A container for compile-time info related to the return value of a [`DynamicFunction`].
+#[derive(Clone, Debug)]
+pub struct ReturnInfo {
+    type_name: &'static str,
+    ownership: Ownership,
+}
+
+impl ReturnInfo {
+    /// Creates info for a return value of type `T` returned as `ownership`.
+    pub fn new<T: ?Sized>(ownership: Ownership) -> Self {
+        Self {
+            type_name: type_name::<T>(),
+            ownership,
+        }
+    }
+
+    /// The [type name] of the return value, without the reference, or `()` if the function
+    /// doesn't return anything.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// How the value is returned.
+    pub fn ownership(&self) -> Ownership {
+        self.ownership
+    }
+}
+
+/// A container for compile-time info related to a [`DynamicFunction`].
+#[derive(Clone, Debug)]
+pub struct FunctionInfo {
+    name: &'static str,
+    args: Vec<ArgInfo>,
+    return_info: ReturnInfo,
+}
+
+impl FunctionInfo {
+    /// Creates info for a function taking `args` and returning `return_info`.
+    pub fn new(name: &'static str, args: Vec<ArgInfo>, return_info: ReturnInfo) -> Self {
+        Self {
+            name,
+            args,
+            return_info,
+        }
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The arguments of the function, including the receiver of methods.
+    pub fn args(&self) -> &[ArgInfo] {
+        &self.args
+    }
+
+    /// The return value of the function.
+    pub fn return_info(&self) -> &ReturnInfo {
+        &self.return_info
+    }
+}
+
+/// A function that can be called with reflected arguments.
+///
+/// Methods of a type are usually created with the
+/// [`#[reflect_functions]`](crate::reflect_functions) attribute, and can be retrieved from its
+/// [`ReflectFunctions`] type data.
+///
+/// Calling the function checks the number of arguments against its [`FunctionInfo`], and the
+/// type and [ownership](Ownership) of each argument as it is passed to the underlying
+/// function.
+#[derive(Clone)]
+pub struct DynamicFunction {
+    info: FunctionInfo,
+    func: Arc<dyn for<'a> Fn(ArgList<'a>) -> FunctionResult<'a> + Send + Sync>,
+}
+
+impl DynamicFunction {
+    /// Creates a function described by `info` calling `func`.
+    ///
+    /// `func` is only called with as many arguments as `info` describes.
+    pub fn new<F>(info: FunctionInfo, func: F) -> Self
+    where
+        F: for<'a> Fn(ArgList<'a>) -> FunctionResult<'a> + Send + Sync + 'static,
+    {
+        Self {
+            info,
+            func: Arc::new(func),
+        }
+    }
+
+    /// The name of the function.
+    pub fn name(&self) -> &'static str {
+        self.info.name()
+    }
+
+    /// Returns the [`FunctionInfo`] of the function.
+    pub fn info(&self) -> &FunctionInfo {
+        &self.info
+    }
+
+    /// Calls the function with the given arguments.
+    pub fn call<'a>(&self, args: ArgList<'a>) -> FunctionResult<'a> {
+        let expected = self.info.args().len();
+        if args.len() != expected {
+            return Err(FunctionError::ArgCount {
+                expected,
+                received: args.len(),
+            });
+        }
+        (self.func)(args)
+    }
+}
+
+impl fmt::Debug for DynamicFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicFunction")
+            .field("info", &self.info)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A trait for types with reflected functions, usually implemented with the
+/// [`#[reflect_functions]`](crate::reflect_functions) attribute.
+pub trait TypeFunctions {
+    /// Returns the reflected functions of the type.
+    fn functions() -> Vec<DynamicFunction>;
+}
+
+/// Type data for the reflected functions of a type.
+///
+/// A [`ReflectFunctions`] for type `T` can be obtained via [`FromType::from_type`], for types
+/// implementing [`TypeFunctions`].
+#[derive(Clone, Debug)]
+pub struct ReflectFunctions {
+    functions: Vec<DynamicFunction>,
+}
+
+impl ReflectFunctions {
+    /// Returns the function with the given name.
+    pub fn get(&self, name: &str) -> Option<&DynamicFunction> {
+        self.functions
+            .iter()
+            .find(|function| function.name() == name)
+    }
+
+    /// Calls the function with the given name.
+    pub fn call<'a>(&self, name: &str, args: ArgList<'a>) -> FunctionResult<'a> {
+        self.get(name)
+            .ok_or_else(|| FunctionError::NotFound(name.to_string()))?
+            .call(args)
+    }
+
+    /// Returns an iterator over the functions.
+    pub fn iter(&self) -> impl Iterator<Item = &DynamicFunction> {
+        self.functions.iter()
+    }
+}
+
+impl<T: TypeFunctions> FromType<T> for ReflectFunctions {
+    fn from_type() -> Self {
+        Self {
+            functions: T::functions(),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Reflected functions, which can be called with reflected arguments.
+//!
+//! The functions of a type are made callable through reflection by adding the
+//! [`#[reflect_functions]`](crate::reflect_functions) attribute to their impl block, and
+//! registering [`ReflectFunctions`] for the type:
+//!
+//! ```
+//! # use std::any::TypeId;
+//! # use bevy_reflect::{reflect_functions, Reflect, TypeRegistry};
+//! # use bevy_reflect::func::{ArgList, ReflectFunctions};
+//! #[derive(Reflect)]
+//! #[reflect(Functions)]
+//! struct Player {
+//!     name: String,
+//!     health: u32,
+//! }
+//!
+//! #[reflect_functions]
+//! impl Player {
+//!     fn heal(&mut self, amount: u32) -> u32 {
+//!         self.health += amount;
+//!         self.health
+//!     }
+//!
+//!     fn name(&self) -> &String {
+//!         &self.name
+//!     }
+//! }
+//!
+//! let mut registry = TypeRegistry::default();
+//! registry.register::<Player>();
+//! let functions = registry
+//!     .get_type_data::<ReflectFunctions>(TypeId::of::<Player>())
+//!     .unwrap();
+//!
+//! let mut player = Player {
+//!     name: "hero".to_string(),
+//!     health: 10,
+//! };
+//! let args = ArgList::new().push_mut(&mut player).push_owned(5_u32);
+//! let health = functions.call("heal", args).unwrap().unwrap_owned();
+//! assert_eq!(health.downcast_ref::<u32>(), Some(&15));
+//!
+//! let name = functions
+//!     .call("name", ArgList::new().push_ref(&player))
+//!     .unwrap();
+//! assert_eq!(name.unwrap_ref().downcast_ref::<String>().unwrap(), "hero");
+//! ```
+//!
+//! The [`FunctionInfo`] of each [`DynamicFunction`] describes its arguments, so that callers
+//! such as consoles or scripting languages can convert their values beforehand.
+//!
+//! Arguments and return values are passed as `dyn Reflect`, so references to unsized types such
+//! as `&str` or `&[T]` can't be passed, and functions using them must be ignored:
+//!
+//! ```compile_fail
+//! # use bevy_reflect::{reflect_functions, Reflect};
+//! #[derive(Reflect)]
+//! struct Player {
+//!     name: String,
+//! }
+//!
+//! #[reflect_functions]
+//! impl Player {
+//!     // error: references to `str` cannot be reflected
+//!     fn rename(&mut self, name: &str) {
+//!         self.name = name.to_string();
+//!     }
+//! }
+//! ```
+
+mod args;
+mod error;
+mod function;
+
+pub use args::*;
+pub use error::*;
+pub use function::*;
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_reflect;
+    use crate::func::{ArgList, FunctionError, Ownership, ReflectFunctions, TypeFunctions};
+    use crate::{reflect_functions, DynamicStruct, FromType, Reflect};
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    #[reflect_functions]
+    impl Point {
+        fn new(x: f32, y: f32) -> Self {
+            Self { x, y }
+        }
+
+        fn length(&self) -> f32 {
+            (self.x * self.x + self.y * self.y).sqrt()
+        }
+
+        fn translate(&mut self, offset: &Point) {
+            self.x += offset.x;
+            self.y += offset.y;
+        }
+
+        fn x_mut(&mut self) -> &mut f32 {
+            &mut self.x
+        }
+
+        fn into_tuple(self) -> (f32, f32) {
+            (self.x, self.y)
+        }
+
+        #[reflect(ignore)]
+        #[allow(dead_code)]
+        fn scale<T: Into<f32>>(&mut self, factor: T) {
+            let factor = factor.into();
+            self.x *= factor;
+            self.y *= factor;
+        }
+    }
+
+    fn functions() -> ReflectFunctions {
+        <ReflectFunctions as FromType<Point>>::from_type()
+    }
+
+    #[test]
+    fn should_list_functions() {
+        let names = Point::functions()
+            .iter()
+            .map(|function| function.name())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["new", "length", "translate", "x_mut", "into_tuple"]);
+
+        let functions = functions();
+        let info = functions.get("translate").unwrap().info();
+        let args = info
+            .args()
+            .iter()
+            .map(|arg| (arg.index(), arg.name(), arg.type_name(), arg.ownership()))
+            .collect::<Vec<_>>();
+        let point = std::any::type_name::<Point>();
+        assert_eq!(
+            args,
+            [
+                (0, Some("self"), point, Ownership::Mut),
+                (1, Some("offset"), point, Ownership::Ref),
+            ]
+        );
+        assert_eq!(info.return_info().type_name(), "()");
+    }
+
+    #[test]
+    fn should_call_functions() {
+        let functions = functions();
+
+        let point = functions
+            .call(
+                "new",
+                ArgList::new().push_owned(3.0_f32).push_owned(4.0_f32),
+            )
+            .unwrap()
+            .unwrap_owned();
+        let mut point = point.take::<Point>().unwrap();
+        assert_eq!(point, Point { x: 3.0, y: 4.0 });
+
+        let length = functions
+            .call("length", ArgList::new().push_ref(&point))
+            .unwrap()
+            .unwrap_owned();
+        assert_eq!(length.downcast_ref::<f32>(), Some(&5.0));
+
+        let offset = Point::new(1.0, 2.0);
+        let result = functions
+            .call(
+                "translate",
+                ArgList::new().push_mut(&mut point).push_ref(&offset),
+            )
+            .unwrap();
+        assert!(result.is_unit());
+        assert_eq!(point, Point { x: 4.0, y: 6.0 });
+
+        let x = functions
+            .call("x_mut", ArgList::new().push_mut(&mut point))
+            .unwrap()
+            .unwrap_mut();
+        *x.downcast_mut::<f32>().unwrap() = 0.0;
+        assert_eq!(point.x, 0.0);
+
+        let tuple = functions
+            .call("into_tuple", ArgList::new().push_owned(point))
+            .unwrap()
+            .unwrap_owned();
+        assert_eq!(tuple.downcast_ref::<(f32, f32)>(), Some(&(0.0, 6.0)));
+
+        // owned arguments are converted from dynamic values
+        let mut dynamic_point = DynamicStruct::default();
+        dynamic_point.insert("x", 1.0_f32);
+        dynamic_point.insert("y", 2.0_f32);
+        let tuple = functions
+            .call("into_tuple", ArgList::new().push_owned(dynamic_point))
+            .unwrap()
+            .unwrap_owned();
+        assert_eq!(tuple.downcast_ref::<(f32, f32)>(), Some(&(1.0, 2.0)));
+    }
+
+    #[test]
+    fn should_check_arguments() {
+        let functions = functions();
+        let mut point = Point::new(1.0, 1.0);
+
+        assert_eq!(
+            functions.call("scale", ArgList::new()).unwrap_err(),
+            FunctionError::NotFound("scale".to_string())
+        );
+        assert_eq!(
+            functions.call("length", ArgList::new()).unwrap_err(),
+            FunctionError::ArgCount {
+                expected: 1,
+                received: 0
+            }
+        );
+        assert_eq!(
+            functions
+                .call(
+                    "new",
+                    ArgList::new()
+                        .push_owned(1.0_f32)
+                        .push_owned("y".to_string())
+                )
+                .unwrap_err(),
+            FunctionError::InvalidArgType {
+                index: 1,
+                expected: "f32",
+                received: "alloc::string::String".to_string()
+            }
+        );
+        assert_eq!(
+            functions
+                .call("x_mut", ArgList::new().push_ref(&point))
+                .unwrap_err(),
+            FunctionError::InvalidArgOwnership {
+                index: 0,
+                expected: Ownership::Mut,
+                received: Ownership::Ref
+            }
+        );
+        // mutable references can be passed as references
+        assert!(functions
+            .call("length", ArgList::new().push_mut(&mut point))
+            .is_ok());
+    }
+}
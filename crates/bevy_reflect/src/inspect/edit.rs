@@ -0,0 +1,322 @@
+use thiserror::Error;
+
+use crate::inspect::{InspectorNode, InspectorValue, NodeKind};
+use crate::{ParsedPath, PatchOperation, Reflect};
+
+/// An error returned when an edit of an [`InspectorNode`] tree is rejected.
+#[derive(Debug, Error, PartialEq)]
+pub enum EditError {
+    #[error("no node found at `{0}`")]
+    NotFound(String),
+    #[error("the node at `{0}` is read-only")]
+    ReadOnly(String),
+    #[error("expected a value of type `{expected}` for `{path}`, received `{received}`")]
+    TypeMismatch {
+        path: String,
+        expected: String,
+        received: String,
+    },
+    #[error("the value {value} is out of the range of `{path}`")]
+    OutOfRange { path: String, value: f64 },
+    #[error("the index {index} is out of bounds of the list at `{path}`")]
+    IndexOutOfBounds { path: String, index: usize },
+    #[error("expected a {expected} at `{path}`")]
+    UnexpectedKind {
+        path: String,
+        expected: &'static str,
+    },
+}
+
+impl InspectorNode {
+    /// Returns the operation replacing the value at `path` with `value`.
+    ///
+    /// The node at `path` must be editable, of the same type as `value`, and the value must
+    /// be within its range if it's a number.
+    ///
+    /// The returned operation is applied with [`PatchOperation::apply`], and can be
+    /// [serialized](crate::serde::PatchSerializer) to edit a remote value.
+    pub fn edit(
+        &self,
+        path: &ParsedPath,
+        value: Box<dyn Reflect>,
+    ) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        node.expect_type(value.type_name())?;
+        if let Some(number) = InspectorValue::from_reflect(&*value).as_f64() {
+            node.expect_in_range(number)?;
+        }
+        Ok(PatchOperation::Set {
+            path: node.path().clone(),
+            value,
+        })
+    }
+
+    /// Returns the operation replacing the primitive or [`String`] at `path` with `value`.
+    ///
+    /// The value is converted to the type of the node, and rejected if it doesn't fit in it,
+    /// for instance `InspectorValue::Int(-1)` for a `u8`, or if it is out of the range of the node.
+    pub fn edit_value(
+        &self,
+        path: &ParsedPath,
+        value: InspectorValue,
+    ) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        if let Some(number) = value.as_f64() {
+            node.expect_in_range(number)?;
+        }
+        let value = node.convert(value)?;
+        Ok(PatchOperation::Set {
+            path: node.path().clone(),
+            value,
+        })
+    }
+
+    /// Returns the operation inserting `value` at `index` in the list at `path`.
+    pub fn insert(
+        &self,
+        path: &ParsedPath,
+        index: usize,
+        value: Box<dyn Reflect>,
+    ) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        let NodeKind::List { len, item_type } = node.kind() else {
+            return Err(node.unexpected_kind("list"));
+        };
+        if index > *len {
+            return Err(node.index_out_of_bounds(index));
+        }
+        node.expect_element_type(*item_type, &*value)?;
+        Ok(PatchOperation::Insert {
+            path: node.path().clone(),
+            index,
+            value,
+        })
+    }
+
+    /// Returns the operation removing the item at `index` from the list at `path`.
+    pub fn remove(&self, path: &ParsedPath, index: usize) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        let NodeKind::List { len, .. } = node.kind() else {
+            return Err(node.unexpected_kind("list"));
+        };
+        if index >= *len {
+            return Err(node.index_out_of_bounds(index));
+        }
+        Ok(PatchOperation::Remove {
+            path: node.path().clone(),
+            index,
+        })
+    }
+
+    /// Returns the operation inserting `value` under `key` in the map at `path`.
+    pub fn insert_entry(
+        &self,
+        path: &ParsedPath,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        let NodeKind::Map {
+            key_type,
+            value_type,
+            ..
+        } = node.kind()
+        else {
+            return Err(node.unexpected_kind("map"));
+        };
+        node.expect_element_type(*key_type, &*key)?;
+        node.expect_element_type(*value_type, &*value)?;
+        Ok(PatchOperation::InsertEntry {
+            path: node.path().clone(),
+            key,
+            value,
+        })
+    }
+
+    /// Returns the operation removing the entry with `key` from the map at `path`.
+    pub fn remove_entry(
+        &self,
+        path: &ParsedPath,
+        key: Box<dyn Reflect>,
+    ) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        let NodeKind::Map { key_type, .. } = node.kind() else {
+            return Err(node.unexpected_kind("map"));
+        };
+        node.expect_element_type(*key_type, &*key)?;
+        Ok(PatchOperation::RemoveEntry {
+            path: node.path().clone(),
+            key,
+        })
+    }
+
+    /// Returns the operation inserting `value` in the set at `path`.
+    pub fn insert_value(
+        &self,
+        path: &ParsedPath,
+        value: Box<dyn Reflect>,
+    ) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        let NodeKind::Set { value_type, .. } = node.kind() else {
+            return Err(node.unexpected_kind("set"));
+        };
+        node.expect_element_type(*value_type, &*value)?;
+        Ok(PatchOperation::InsertValue {
+            path: node.path().clone(),
+            value,
+        })
+    }
+
+    /// Returns the operation removing `value` from the set at `path`.
+    pub fn remove_value(
+        &self,
+        path: &ParsedPath,
+        value: Box<dyn Reflect>,
+    ) -> Result<PatchOperation, EditError> {
+        let node = self.find_editable(path)?;
+        let NodeKind::Set { value_type, .. } = node.kind() else {
+            return Err(node.unexpected_kind("set"));
+        };
+        node.expect_element_type(*value_type, &*value)?;
+        Ok(PatchOperation::RemoveValue {
+            path: node.path().clone(),
+            value,
+        })
+    }
+
+    fn find_editable(&self, path: &ParsedPath) -> Result<&InspectorNode, EditError> {
+        let node = self
+            .find(path)
+            .ok_or_else(|| EditError::NotFound(path.to_string()))?;
+        if node.is_read_only() {
+            return Err(EditError::ReadOnly(path.to_string()));
+        }
+        Ok(node)
+    }
+
+    fn expect_type(&self, type_name: &str) -> Result<(), EditError> {
+        if type_name == self.type_name() {
+            Ok(())
+        } else {
+            Err(EditError::TypeMismatch {
+                path: self.path().to_string(),
+                expected: self.type_name().to_string(),
+                received: type_name.to_string(),
+            })
+        }
+    }
+
+    fn expect_element_type(
+        &self,
+        expected: Option<&'static str>,
+        value: &dyn Reflect,
+    ) -> Result<(), EditError> {
+        match expected {
+            Some(expected) if expected != value.type_name() => Err(EditError::TypeMismatch {
+                path: self.path().to_string(),
+                expected: expected.to_string(),
+                received: value.type_name().to_string(),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn expect_in_range(&self, value: f64) -> Result<(), EditError> {
+        if self.options().contains(value) {
+            Ok(())
+        } else {
+            Err(EditError::OutOfRange {
+                path: self.path().to_string(),
+                value,
+            })
+        }
+    }
+
+    fn unexpected_kind(&self, expected: &'static str) -> EditError {
+        EditError::UnexpectedKind {
+            path: self.path().to_string(),
+            expected,
+        }
+    }
+
+    fn index_out_of_bounds(&self, index: usize) -> EditError {
+        EditError::IndexOutOfBounds {
+            path: self.path().to_string(),
+            index,
+        }
+    }
+
+    /// Converts `value` to the type of this node.
+    fn convert(&self, value: InspectorValue) -> Result<Box<dyn Reflect>, EditError> {
+        let out_of_range = |value: f64| EditError::OutOfRange {
+            path: self.path().to_string(),
+            value,
+        };
+
+        macro_rules! convert_integer {
+            ($($ty:ty),*) => {
+                $(
+                    if self.type_name() == std::any::type_name::<$ty>() {
+                        let number = value.as_f64().unwrap_or_default();
+                        let converted = match value {
+                            InspectorValue::Int(value) => <$ty>::try_from(value).ok(),
+                            InspectorValue::UInt(value) => <$ty>::try_from(value).ok(),
+                            InspectorValue::Float(value) if value.fract() == 0.0 => {
+                                <$ty>::try_from(value as i128).ok()
+                            }
+                            _ => return Err(self.mismatch(&value)),
+                        };
+                        return match converted {
+                            Some(converted) => Ok(Box::new(converted)),
+                            None => Err(out_of_range(number)),
+                        };
+                    }
+                )*
+            };
+        }
+
+        convert_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+        if self.type_name() == std::any::type_name::<f32>() {
+            if let Some(number) = value.as_f64() {
+                return Ok(Box::new(number as f32));
+            }
+        } else if self.type_name() == std::any::type_name::<f64>() {
+            if let Some(number) = value.as_f64() {
+                return Ok(Box::new(number));
+            }
+        }
+
+        match value {
+            InspectorValue::Bool(value) if self.type_name() == std::any::type_name::<bool>() => {
+                Ok(Box::new(value))
+            }
+            InspectorValue::Char(value) if self.type_name() == std::any::type_name::<char>() => {
+                Ok(Box::new(value))
+            }
+            InspectorValue::String(value)
+                if self.type_name() == std::any::type_name::<String>() =>
+            {
+                Ok(Box::new(value))
+            }
+            value => Err(self.mismatch(&value)),
+        }
+    }
+
+    fn mismatch(&self, value: &InspectorValue) -> EditError {
+        let received = match value {
+            InspectorValue::Bool(_) => "bool",
+            InspectorValue::Int(_) => "signed integer",
+            InspectorValue::UInt(_) => "unsigned integer",
+            InspectorValue::Float(_) => "float",
+            InspectorValue::Char(_) => "char",
+            InspectorValue::String(_) => "string",
+            InspectorValue::Opaque(_) => "opaque value",
+        };
+        EditError::TypeMismatch {
+            path: self.path().to_string(),
+            expected: self.type_name().to_string(),
+            received: received.to_string(),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+//! A UI-agnostic data model for inspectors of reflected values.
+//!
+//! An [`InspectorNode`] tree describes a value with type-aware metadata: labels, the kind and
+//! current value of each element, and the [`FieldOptions`] set on fields with the
+//! `#[reflect(inspect(...))]` attribute.
+//! Any UI can render this tree, and turn user input into edit commands with methods such as
+//! [`InspectorNode::edit_value`].
+//! Edits are validated against the metadata of the tree, and returned as [`PatchOperation`]s
+//! which can be applied to the value, or serialized to edit a remote value.
+//!
+//! ```
+//! # use bevy_reflect::{ParsedPath, Reflect, TypeRegistry};
+//! # use bevy_reflect::inspect::{EditError, InspectorNode, InspectorValue, NodeKind};
+//! #[derive(Reflect)]
+//! struct Light {
+//!     #[reflect(inspect(display_name = "Intensity (lm)", min = 0, max = 10_000))]
+//!     intensity: f32,
+//!     #[reflect(inspect(read_only))]
+//!     id: u32,
+//! }
+//!
+//! let mut registry = TypeRegistry::default();
+//! registry.register::<Light>();
+//!
+//! let mut light = Light { intensity: 800.0, id: 3 };
+//! let tree = InspectorNode::new(&light, &registry);
+//! let intensity = &tree.children()[0];
+//! assert_eq!(intensity.label(), "Intensity (lm)");
+//! assert_eq!(intensity.kind(), &NodeKind::Value(InspectorValue::Float(800.0)));
+//!
+//! let edit = tree
+//!     .edit_value(intensity.path(), InspectorValue::Float(1600.0))
+//!     .unwrap();
+//! edit.apply(&mut light).unwrap();
+//! assert_eq!(light.intensity, 1600.0);
+//!
+//! let id = ParsedPath::parse("id").unwrap();
+//! assert_eq!(
+//!     tree.edit_value(&id, InspectorValue::UInt(4)).unwrap_err(),
+//!     EditError::ReadOnly(".id".to_string())
+//! );
+//! ```
+//!
+//! [`PatchOperation`]: crate::PatchOperation
+
+mod edit;
+mod node;
+mod options;
+
+pub use edit::*;
+pub use node::*;
+pub use options::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::{ParsedPath, Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Character {
+        #[reflect(inspect(display_name = "Name"))]
+        name: String,
+        #[reflect(inspect(min = 0, max = 100, step = 5))]
+        health: u8,
+        #[reflect(ignore)]
+        _cache: u32,
+        #[reflect(inspect(read_only))]
+        origin: (f32, f32),
+        inventory: Vec<String>,
+        stats: HashMap<String, u32>,
+        state: State,
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    enum State {
+        Idle,
+        Moving {
+            #[reflect(inspect(min = -1.0, max = 1.0))]
+            direction: f32,
+        },
+    }
+
+    fn character() -> Character {
+        Character {
+            name: "hero".to_string(),
+            health: 50,
+            _cache: 0,
+            origin: (1.0, 2.0),
+            inventory: vec!["sword".to_string()],
+            stats: HashMap::from([("strength".to_string(), 3)]),
+            state: State::Moving { direction: 0.5 },
+        }
+    }
+
+    fn registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Character>();
+        registry.register::<State>();
+        registry.register::<Vec<String>>();
+        registry.register::<HashMap<String, u32>>();
+        registry
+    }
+
+    fn path(path: &str) -> ParsedPath {
+        ParsedPath::parse(path).unwrap()
+    }
+
+    #[test]
+    fn should_build_tree_with_options() {
+        let tree = InspectorNode::new(&character(), &registry());
+
+        assert_eq!(tree.label(), "Character");
+        assert_eq!(tree.kind(), &NodeKind::Struct);
+        let labels = tree
+            .children()
+            .iter()
+            .map(InspectorNode::label)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            labels,
+            ["Name", "health", "origin", "inventory", "stats", "state"]
+        );
+
+        let health = tree.find(&path("health")).unwrap();
+        assert_eq!(health.kind(), &NodeKind::Value(InspectorValue::UInt(50)));
+        assert_eq!(health.options().max(), Some(100.0));
+        assert_eq!(health.options().step(), Some(5.0));
+
+        let origin_x = tree.find(&path("origin.0")).unwrap();
+        assert!(origin_x.is_read_only());
+        assert_eq!(
+            origin_x.kind(),
+            &NodeKind::Value(InspectorValue::Float(1.0))
+        );
+
+        let inventory = tree.find(&path("inventory")).unwrap();
+        assert_eq!(
+            inventory.kind(),
+            &NodeKind::List {
+                len: 1,
+                item_type: Some(std::any::type_name::<String>()),
+            }
+        );
+        assert_eq!(
+            tree.find(&path("inventory[0]")).unwrap().kind(),
+            &NodeKind::Value(InspectorValue::String("sword".to_string()))
+        );
+
+        let stats = tree.find(&path("stats")).unwrap();
+        assert_eq!(stats.children()[0].label(), "\"strength\"");
+        assert!(stats.children()[0].is_read_only());
+
+        let state = tree.find(&path("state")).unwrap();
+        assert_eq!(
+            state.kind(),
+            &NodeKind::Enum {
+                variant: "Moving".to_string(),
+                variants: vec!["Idle", "Moving"],
+            }
+        );
+        let direction = tree.find(&path("state.direction")).unwrap();
+        assert_eq!(direction.options().min(), Some(-1.0));
+    }
+
+    #[test]
+    fn should_apply_edits() {
+        let registry = registry();
+        let mut value = character();
+        let tree = InspectorNode::new(&value, &registry);
+
+        let edits = [
+            tree.edit_value(&path("health"), InspectorValue::Int(80)),
+            tree.edit_value(&path("name"), InspectorValue::String("villain".to_string())),
+            tree.edit(&path("state"), Box::new(State::Idle)),
+            tree.insert(&path("inventory"), 1, Box::new("shield".to_string())),
+            tree.remove(&path("inventory"), 0),
+            tree.insert_entry(
+                &path("stats"),
+                Box::new("speed".to_string()),
+                Box::new(7_u32),
+            ),
+        ];
+        for edit in edits {
+            edit.unwrap().apply(&mut value).unwrap();
+        }
+
+        let mut expected = character();
+        expected.health = 80;
+        expected.name = "villain".to_string();
+        expected.state = State::Idle;
+        expected.inventory = vec!["shield".to_string()];
+        expected.stats.insert("speed".to_string(), 7);
+        assert_eq!(value, expected);
+
+        let tree = InspectorNode::new(&value, &registry);
+        assert_eq!(
+            tree.find(&path("health")).unwrap().kind(),
+            &NodeKind::Value(InspectorValue::UInt(80))
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_edits() {
+        let tree = InspectorNode::new(&character(), &registry());
+
+        assert_eq!(
+            tree.edit_value(&path("health"), InspectorValue::UInt(120))
+                .unwrap_err(),
+            EditError::OutOfRange {
+                path: ".health".to_string(),
+                value: 120.0,
+            }
+        );
+        assert_eq!(
+            tree.edit_value(&path("state.direction"), InspectorValue::Float(2.0))
+                .unwrap_err(),
+            EditError::OutOfRange {
+                path: ".state.direction".to_string(),
+                value: 2.0,
+            }
+        );
+        assert_eq!(
+            tree.edit_value(&path("name"), InspectorValue::Bool(true))
+                .unwrap_err(),
+            EditError::TypeMismatch {
+                path: ".name".to_string(),
+                expected: std::any::type_name::<String>().to_string(),
+                received: "bool".to_string(),
+            }
+        );
+        assert_eq!(
+            tree.edit(&path("health"), Box::new(10_u32)).unwrap_err(),
+            EditError::TypeMismatch {
+                path: ".health".to_string(),
+                expected: "u8".to_string(),
+                received: "u32".to_string(),
+            }
+        );
+        assert_eq!(
+            tree.edit_value(&path("origin.1"), InspectorValue::Float(0.0))
+                .unwrap_err(),
+            EditError::ReadOnly(".origin.1".to_string())
+        );
+        assert_eq!(
+            tree.edit_value(&path("mana"), InspectorValue::Int(0))
+                .unwrap_err(),
+            EditError::NotFound(".mana".to_string())
+        );
+        assert_eq!(
+            tree.remove(&path("inventory"), 1).unwrap_err(),
+            EditError::IndexOutOfBounds {
+                path: ".inventory".to_string(),
+                index: 1,
+            }
+        );
+        assert_eq!(
+            tree.insert(&path("name"), 0, Box::new('a')).unwrap_err(),
+            EditError::UnexpectedKind {
+                path: ".name".to_string(),
+                expected: "list",
+            }
+        );
+        assert!(matches!(
+            tree.insert_entry(&path("stats"), Box::new(1_u32), Box::new(1_u32)),
+            Err(EditError::TypeMismatch { .. })
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::inspect::{FieldOptions, InspectorOptions};
+use crate::{ParsedPath, Reflect, ReflectRef, TypeInfo, TypeRegistry, VariantType};
+
+/// A node of the inspector tree of a reflected value.
+///
+/// The tree is built with [`InspectorNode::new`], and mirrors the structure of the value:
+/// struct fields, tuple fields, list and array items and enum variant fields are the
+/// children of their container, and can be edited through their [`path`](Self::path).
+///
+/// Map entries and set values aren't addressable by paths, so they are listed as
+/// read-only [`NodeKind::Value`] children sharing the path of their collection.
+/// They are edited as a whole, with [`insert_entry`](Self::insert_entry) and
+/// [`insert_value`](Self::insert_value) for instance.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InspectorNode {
+    path: ParsedPath,
+    label: String,
+    type_name: String,
+    kind: NodeKind,
+    options: FieldOptions,
+    children: Vec<InspectorNode>,
+}
+
+/// The kind of an [`InspectorNode`], with the data needed to display it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NodeKind {
+    Struct,
+    TupleStruct,
+    Tuple,
+    List {
+        len: usize,
+        /// The type of the items, if the type of the list is known.
+        item_type: Option<&'static str>,
+    },
+    Array {
+        len: usize,
+    },
+    Map {
+        len: usize,
+        /// The type of the keys, if the type of the map is known.
+        key_type: Option<&'static str>,
+        /// The type of the values, if the type of the map is known.
+        value_type: Option<&'static str>,
+    },
+    Set {
+        len: usize,
+        /// The type of the values, if the type of the set is known.
+        value_type: Option<&'static str>,
+    },
+    Enum {
+        /// The name of the current variant.
+        variant: String,
+        /// The names of all the variants, if the type of the enum is known.
+        variants: Vec<&'static str>,
+    },
+    /// A value without reflected fields, such as a primitive.
+    Value(InspectorValue),
+}
+
+/// The value of a [`NodeKind::Value`] node, or a value edited with [`InspectorNode::edit_value`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum InspectorValue {
+    Bool(bool),
+    /// A signed integer.
+    Int(i128),
+    /// An unsigned integer.
+    UInt(u128),
+    /// A floating point number.
+    Float(f64),
+    Char(char),
+    String(String),
+    /// A value of another type, described by its [`Debug`] output.
+    ///
+    /// It can only be edited with [`InspectorNode::edit`].
+    Opaque(String),
+}
+
+impl InspectorValue {
+    /// Creates the inspector value of `value`.
+    ///
+    /// Returns [`InspectorValue::Opaque`] if `value` isn't a primitive or a [`String`].
+    pub fn from_reflect(value: &dyn Reflect) -> Self {
+        macro_rules! downcast {
+            ($($ty:ty => $variant:ident($as:ty)),* $(,)?) => {
+                $(
+                    if let Some(value) = value.downcast_ref::<$ty>() {
+                        return Self::$variant(*value as $as);
+                    }
+                )*
+            };
+        }
+
+        downcast!(
+            i8 => Int(i128), i16 => Int(i128), i32 => Int(i128), i64 => Int(i128),
+            i128 => Int(i128), isize => Int(i128),
+            u8 => UInt(u128), u16 => UInt(u128), u32 => UInt(u128), u64 => UInt(u128),
+            u128 => UInt(u128), usize => UInt(u128),
+            f32 => Float(f64), f64 => Float(f64),
+        );
+
+        if let Some(value) = value.downcast_ref::<bool>() {
+            Self::Bool(*value)
+        } else if let Some(value) = value.downcast_ref::<char>() {
+            Self::Char(*value)
+        } else if let Some(value) = value.downcast_ref::<String>() {
+            Self::String(value.clone())
+        } else {
+            Self::Opaque(format!("{value:?}"))
+        }
+    }
+
+    /// Returns the value as a number, if it is one.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Int(value) => Some(*value as f64),
+            Self::UInt(value) => Some(*value as f64),
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+}
+
+impl InspectorNode {
+    /// Builds the inspector tree of `value`.
+    ///
+    /// The [`InspectorOptions`] registered in `registry` provide the display names,
+    /// numeric ranges and read-only flags of the fields.
+    /// Fields of a read-only field are read-only too.
+    pub fn new(value: &dyn Reflect, registry: &TypeRegistry) -> Self {
+        Self::build(
+            String::new(),
+            value.reflect_short_type_path().to_string(),
+            FieldOptions::default(),
+            value,
+            registry,
+        )
+    }
+
+    fn build(
+        path: String,
+        label: String,
+        options: FieldOptions,
+        value: &dyn Reflect,
+        registry: &TypeRegistry,
+    ) -> Self {
+        let inspector_options = registry
+            .get_with_name(value.type_name())
+            .and_then(|registration| registration.data::<InspectorOptions>());
+        let read_only = options.is_read_only();
+        let child_options = |options: Option<&FieldOptions>| {
+            let options = options.cloned().unwrap_or_default();
+            if read_only {
+                options.with_read_only(true)
+            } else {
+                options
+            }
+        };
+        let field_options = |index: usize| {
+            child_options(inspector_options.and_then(|options| options.field(index)))
+        };
+        let type_info = value.get_represented_type_info();
+        let parsed_path = ParsedPath::parse(&path)
+            .expect("paths to the fields of reflected values should be valid");
+
+        let mut children = Vec::new();
+        let mut child = |path: String, label: String, options: FieldOptions, value| {
+            children.push(Self::build(path, label, options, value, registry));
+        };
+
+        let kind = match value.reflect_ref() {
+            ReflectRef::Struct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    let name = value.name_at(index).unwrap();
+                    child(
+                        format!("{path}.{name}"),
+                        name.to_string(),
+                        field_options(index),
+                        field,
+                    );
+                }
+                NodeKind::Struct
+            }
+            ReflectRef::TupleStruct(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    child(
+                        format!("{path}.{index}"),
+                        index.to_string(),
+                        field_options(index),
+                        field,
+                    );
+                }
+                NodeKind::TupleStruct
+            }
+            ReflectRef::Tuple(value) => {
+                for (index, field) in value.iter_fields().enumerate() {
+                    child(
+                        format!("{path}.{index}"),
+                        index.to_string(),
+                        field_options(index),
+                        field,
+                    );
+                }
+                NodeKind::Tuple
+            }
+            ReflectRef::List(value) => {
+                for (index, item) in value.iter().enumerate() {
+                    child(
+                        format!("{path}[{index}]"),
+                        index.to_string(),
+                        child_options(None),
+                        item,
+                    );
+                }
+                NodeKind::List {
+                    len: value.len(),
+                    item_type: match type_info {
+                        Some(TypeInfo::List(info)) => Some(info.item_type_name()),
+                        _ => None,
+                    },
+                }
+            }
+            ReflectRef::Array(value) => {
+                for (index, item) in value.iter().enumerate() {
+                    child(
+                        format!("{path}[{index}]"),
+                        index.to_string(),
+                        child_options(None),
+                        item,
+                    );
+                }
+                NodeKind::Array { len: value.len() }
+            }
+            ReflectRef::Map(value) => {
+                children.extend(value.iter().map(|(key, entry)| {
+                    Self::collection_entry(&parsed_path, format!("{key:?}"), entry)
+                }));
+                let (key_type, value_type) = match type_info {
+                    Some(TypeInfo::Map(info)) => {
+                        (Some(info.key_type_name()), Some(info.value_type_name()))
+                    }
+                    _ => (None, None),
+                };
+                NodeKind::Map {
+                    len: value.len(),
+                    key_type,
+                    value_type,
+                }
+            }
+            ReflectRef::Set(value) => {
+                children.extend(value.iter().enumerate().map(|(index, set_value)| {
+                    Self::collection_entry(&parsed_path, index.to_string(), set_value)
+                }));
+                NodeKind::Set {
+                    len: value.len(),
+                    value_type: match type_info {
+                        Some(TypeInfo::Set(info)) => Some(info.value_type_name()),
+                        _ => None,
+                    },
+                }
+            }
+            ReflectRef::Enum(value) => {
+                let variant = value.variant_name();
+                for (index, field) in value.iter_fields().enumerate() {
+                    let options = child_options(
+                        inspector_options.and_then(|options| options.variant_field(variant, index)),
+                    );
+                    match value.variant_type() {
+                        VariantType::Struct => {
+                            let name = field.name().unwrap();
+                            child(
+                                format!("{path}.{name}"),
+                                name.to_string(),
+                                options,
+                                field.value(),
+                            );
+                        }
+                        _ => {
+                            child(
+                                format!("{path}.{index}"),
+                                index.to_string(),
+                                options,
+                                field.value(),
+                            );
+                        }
+                    }
+                }
+                NodeKind::Enum {
+                    variant: variant.to_string(),
+                    variants: match type_info {
+                        Some(TypeInfo::Enum(info)) => info.variant_names().to_vec(),
+                        _ => Vec::new(),
+                    },
+                }
+            }
+            ReflectRef::Value(value) => NodeKind::Value(InspectorValue::from_reflect(value)),
+        };
+
+        Self {
+            path: parsed_path,
+            label: options
+                .display_name()
+                .map(ToString::to_string)
+                .unwrap_or(label),
+            type_name: value.type_name().to_string(),
+            kind,
+            options,
+            children,
+        }
+    }
+
+    /// Creates the read-only node of a map entry or set value.
+    fn collection_entry(path: &ParsedPath, label: String, value: &dyn Reflect) -> Self {
+        Self {
+            path: path.clone(),
+            label,
+            type_name: value.type_name().to_string(),
+            kind: NodeKind::Value(InspectorValue::from_reflect(value)),
+            options: FieldOptions::default().with_read_only(true),
+            children: Vec::new(),
+        }
+    }
+
+    /// The path to this node from the root value.
+    pub fn path(&self) -> &ParsedPath {
+        &self.path
+    }
+
+    /// The name to display for this node.
+    ///
+    /// This is the [display name](FieldOptions::display_name) of the field if it has one,
+    /// and otherwise the field name, the index of the element, or the type of the root value.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// The [type name](Reflect::type_name) of the value of this node.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The kind of this node.
+    pub fn kind(&self) -> &NodeKind {
+        &self.kind
+    }
+
+    /// The inspector options of this node.
+    pub fn options(&self) -> &FieldOptions {
+        &self.options
+    }
+
+    /// Returns true if this node can't be edited.
+    pub fn is_read_only(&self) -> bool {
+        self.options.is_read_only()
+    }
+
+    /// The children of this node.
+    pub fn children(&self) -> &[InspectorNode] {
+        &self.children
+    }
+
+    /// Returns the node at `path` in this tree, if any.
+    ///
+    /// Paths are compared by their [`Display`](std::fmt::Display) output, so `"a.b"` and `".a.b"`
+    /// find the same node.
+    pub fn find(&self, path: &ParsedPath) -> Option<&InspectorNode> {
+        self.find_by_str(&path.to_string())
+    }
+
+    fn find_by_str(&self, path: &str) -> Option<&InspectorNode> {
+        if self.path.to_string() == path {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .filter(|child| path.starts_with(&child.path.to_string()))
+            .find_map(|child| child.find_by_str(path))
+    }
+}
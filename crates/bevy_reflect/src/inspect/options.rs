@@ -0,0 +1,135 @@
+use std::borrow::Cow;
+
+use bevy_utils::HashMap;
+
+/// Inspector metadata of a single field.
+///
+/// These are usually set with the `#[reflect(inspect(...))]` field attribute:
+///
+/// ```
+/// # use bevy_reflect::Reflect;
+/// #[derive(Reflect)]
+/// struct Light {
+///     #[reflect(inspect(display_name = "Intensity (lm)", min = 0, max = 10_000, step = 10))]
+///     intensity: f32,
+///     #[reflect(inspect(read_only))]
+///     id: u32,
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldOptions {
+    display_name: Option<Cow<'static, str>>,
+    min: Option<f64>,
+    max: Option<f64>,
+    step: Option<f64>,
+    read_only: bool,
+}
+
+impl FieldOptions {
+    /// Creates options without any metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name displayed instead of the field name.
+    pub fn with_display_name(mut self, display_name: impl Into<Cow<'static, str>>) -> Self {
+        self.display_name = Some(display_name.into());
+        self
+    }
+
+    /// Sets the minimum numeric value of the field.
+    pub fn with_min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self
+    }
+
+    /// Sets the maximum numeric value of the field.
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self
+    }
+
+    /// Sets the increment used when editing the field, for instance by a slider.
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Sets whether the field can be edited.
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// The name displayed instead of the field name, if any.
+    pub fn display_name(&self) -> Option<&str> {
+        self.display_name.as_deref()
+    }
+
+    /// The minimum numeric value of the field, if any.
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /// The maximum numeric value of the field, if any.
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /// The increment used when editing the field, if any.
+    pub fn step(&self) -> Option<f64> {
+        self.step
+    }
+
+    /// Returns true if the field can't be edited.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Returns true if `value` is within the [`min`](Self::min) and [`max`](Self::max) bounds.
+    pub fn contains(&self, value: f64) -> bool {
+        !self.min.is_some_and(|min| value < min) && !self.max.is_some_and(|max| value > max)
+    }
+}
+
+/// Type data holding the [`FieldOptions`] of the fields of a type.
+///
+/// Fields are identified by their index among the reflected fields of their struct
+/// or enum variant, ignored fields being skipped.
+///
+/// This is inserted by `#[derive(Reflect)]` when a field has a `#[reflect(inspect(...))]` attribute.
+#[derive(Clone, Debug, Default)]
+pub struct InspectorOptions {
+    fields: HashMap<usize, FieldOptions>,
+    variant_fields: HashMap<Cow<'static, str>, HashMap<usize, FieldOptions>>,
+}
+
+impl InspectorOptions {
+    /// Sets the options of the field at `index` of a struct, tuple struct or tuple.
+    pub fn insert_field(&mut self, index: usize, options: FieldOptions) {
+        self.fields.insert(index, options);
+    }
+
+    /// Sets the options of the field at `index` of the enum variant named `variant`.
+    pub fn insert_variant_field(
+        &mut self,
+        variant: impl Into<Cow<'static, str>>,
+        index: usize,
+        options: FieldOptions,
+    ) {
+        self.variant_fields
+            .entry(variant.into())
+            .or_default()
+            .insert(index, options);
+    }
+
+    /// Returns the options of the field at `index` of a struct, tuple struct or tuple.
+    pub fn field(&self, index: usize) -> Option<&FieldOptions> {
+        self.fields.get(&index)
+    }
+
+    /// Returns the options of the field at `index` of the enum variant named `variant`.
+    pub fn variant_field(&self, variant: &str, index: usize) -> Option<&FieldOptions> {
+        self.variant_fields.get(variant)?.get(&index)
+    }
+}
@@ -65,6 +65,7 @@
 //! * [`Array`]
 //! * [`List`]
 //! * [`Map`]
+//! * [`Set`]
 //! * [`Struct`]
 //! * [`TupleStruct`]
 //! * [`Enum`]
@@ -125,6 +126,7 @@
 //! * [`DynamicArray`]
 //! * [`DynamicList`]
 //! * [`DynamicMap`]
+//! * [`DynamicSet`]
 //! * [`DynamicStruct`]
 //! * [`DynamicTupleStruct`]
 //! * [`DynamicEnum`]
@@ -252,6 +254,28 @@
 //! );
 //! ```
 //!
+//! # Diffing
+//!
+//! A [`Patch`] records the changes between two values of the same type as operations on the
+//! elements at given paths, which can be applied to other values of that type, or serialized.
+//! See its documentation for how values are compared.
+//!
+//! ```
+//! # use bevy_reflect::{Patch, Reflect};
+//! #[derive(Reflect, Clone, PartialEq, Debug)]
+//! struct MyStruct {
+//!   value: Vec<u32>
+//! }
+//!
+//! let old = MyStruct { value: vec![1, 2] };
+//! let new = MyStruct { value: vec![0, 1, 2] };
+//!
+//! let patch = Patch::diff(&old, &new);
+//! let mut value = old.clone();
+//! patch.apply(&mut value).unwrap();
+//! assert_eq!(value, new);
+//! ```
+//!
 //! # Type Registration
 //!
 //! This crate also comes with a [`TypeRegistry`] that can be used to store and retrieve additional type metadata at runtime,
@@ -399,9 +423,10 @@
 //! Another limitation is the inability to fully reflect functions and methods.
 //! Most languages offer some way of calling methods dynamically,
 //! but Rust makes this very difficult to do.
-//! For non-generic methods, this can be done by registering custom [type data] that
-//! contains function pointers.
-//! For generic methods, the same can be done but will typically require manual monomorphization
+//! Non-generic methods can be made callable with reflected arguments using the
+//! [`#[reflect_functions]`](reflect_functions) attribute, see the [`func`] module.
+//! For generic methods, the same can be done by registering custom [type data] that
+//! contains function pointers, but will typically require manual monomorphization
 //! (i.e. manually specifying the types the generic method can take).
 //!
 //! ## Manual Registration
@@ -467,12 +492,14 @@
 #![allow(clippy::type_complexity)]
 
 mod array;
+mod diff;
 mod fields;
 mod from_reflect;
 mod list;
 mod map;
 mod path;
 mod reflect;
+mod set;
 mod struct_trait;
 mod tuple;
 mod tuple_struct;
@@ -505,6 +532,8 @@ mod impls {
 }
 
 mod enums;
+pub mod func;
+pub mod inspect;
 pub mod serde;
 pub mod std_traits;
 pub mod utility;
@@ -519,6 +548,7 @@ pub mod prelude {
 }
 
 pub use array::*;
+pub use diff::*;
 pub use enums::*;
 pub use fields::*;
 pub use from_reflect::*;
@@ -527,6 +557,7 @@ pub use list::*;
 pub use map::*;
 pub use path::*;
 pub use reflect::*;
+pub use set::*;
 pub use struct_trait::*;
 pub use tuple::*;
 pub use tuple_struct::*;
@@ -671,6 +702,51 @@ mod tests {
         assert_eq!(20, *map.get(&key_b).unwrap().downcast_ref::<u32>().unwrap());
     }
 
+    #[test]
+    fn reflect_set() {
+        let mut set = bevy_utils::HashSet::from([1u32, 2, 3]);
+        let mut dynamic_set = DynamicSet::default();
+        dynamic_set.insert(3u32);
+        dynamic_set.insert(4u32);
+
+        set.apply(&dynamic_set);
+        assert_eq!(set, bevy_utils::HashSet::from([1, 2, 3, 4]));
+
+        let value = std::collections::BTreeSet::<u32>::from_reflect(&set).unwrap();
+        assert_eq!(value, std::collections::BTreeSet::from([1, 2, 3, 4]));
+        assert!(value.reflect_partial_eq(&set).unwrap());
+        assert!(!value.reflect_partial_eq(&dynamic_set).unwrap());
+
+        let values = Set::iter(&value)
+            .map(|value| *value.downcast_ref::<u32>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn reflect_ordered_map() {
+        let map = std::collections::BTreeMap::from(
+            [(3u32, "c"), (1, "a"), (2, "b")].map(|(key, value)| (key, value.to_string())),
+        );
+
+        let dynamic_map = map.clone_dynamic();
+        let keys = dynamic_map
+            .iter()
+            .map(|(key, _)| *key.downcast_ref::<u32>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![1, 2, 3]);
+
+        let (key, value) = Map::get_at(&map, 1).unwrap();
+        assert_eq!(key.downcast_ref::<u32>(), Some(&2));
+        assert_eq!(
+            value.downcast_ref::<String>().map(String::as_str),
+            Some("b")
+        );
+
+        let value = std::collections::BTreeMap::<u32, String>::from_reflect(&dynamic_map).unwrap();
+        assert_eq!(value, map);
+    }
+
     #[test]
     #[allow(clippy::disallowed_types)]
     fn reflect_unit_struct() {
@@ -1492,6 +1568,23 @@ mod tests {
         let info = value.get_represented_type_info().unwrap();
         assert!(info.is::<MyMap>());
 
+        // Set
+        type MySet = std::collections::BTreeSet<usize>;
+
+        let info = MySet::type_info();
+        if let TypeInfo::Set(info) = info {
+            assert!(info.is::<MySet>());
+            assert!(info.value_is::<usize>());
+            assert_eq!(std::any::type_name::<MySet>(), info.type_name());
+            assert_eq!(std::any::type_name::<usize>(), info.value_type_name());
+        } else {
+            panic!("Expected `TypeInfo::Set`");
+        }
+
+        let value: &dyn Reflect = &MySet::new();
+        let info = value.get_represented_type_info().unwrap();
+        assert!(info.is::<MySet>());
+
         // Value
         type MyValue = String;
 
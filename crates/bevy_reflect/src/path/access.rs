@@ -1,7 +1,7 @@
 use std::{borrow::Cow, fmt};
 
 use super::{AccessError, ReflectPathError};
-use crate::{Reflect, ReflectMut, ReflectRef, VariantType};
+use crate::{Map, Reflect, ReflectMut, ReflectRef, VariantType};
 use thiserror::Error;
 
 type InnerResult<T> = Result<Option<T>, Error<'static>>;
@@ -57,6 +57,7 @@ pub(super) enum TypeShape {
     List,
     Array,
     Map,
+    Set,
     Enum,
     Value,
     Unit,
@@ -71,6 +72,7 @@ impl fmt::Display for TypeShape {
             TypeShape::List => "list",
             TypeShape::Array => "array",
             TypeShape::Map => "map",
+            TypeShape::Set => "set",
             TypeShape::Enum => "enum",
             TypeShape::Value => "value",
             TypeShape::Unit => "unit",
@@ -87,6 +89,7 @@ impl<'a> From<ReflectRef<'a>> for TypeShape {
             ReflectRef::List(_) => TypeShape::List,
             ReflectRef::Array(_) => TypeShape::Array,
             ReflectRef::Map(_) => TypeShape::Map,
+            ReflectRef::Set(_) => TypeShape::Set,
             ReflectRef::Enum(_) => TypeShape::Enum,
             ReflectRef::Value(_) => TypeShape::Value,
         }
@@ -196,6 +199,46 @@ impl<'a> Access<'a> {
             .map_err(|err| err.with_offset(offset))
     }
 
+    /// Inserts `value` at the index of this list access into `base`, shifting the following
+    /// elements.
+    pub(super) fn insert(
+        &self,
+        base: &mut dyn Reflect,
+        value: Box<dyn Reflect>,
+        offset: usize,
+    ) -> Result<(), ReflectPathError<'a>> {
+        let ty = base.reflect_ref().into();
+        match (self, base.reflect_mut()) {
+            (&Self::ListIndex(index), ReflectMut::List(list)) if index <= list.len() => {
+                list.insert(index, value);
+                Ok(())
+            }
+            (Self::ListIndex(_), ReflectMut::List(_)) => {
+                Err(Error::access(ty, self.clone()).with_offset(offset))
+            }
+            _ => Err(Error::bad_type(TypeShape::List, ty).with_offset(offset)),
+        }
+    }
+
+    /// Removes the element at the index of this list access from `base`, shifting the following
+    /// elements.
+    pub(super) fn remove(
+        &self,
+        base: &mut dyn Reflect,
+        offset: usize,
+    ) -> Result<Box<dyn Reflect>, ReflectPathError<'a>> {
+        let ty = base.reflect_ref().into();
+        match (self, base.reflect_mut()) {
+            (&Self::ListIndex(index), ReflectMut::List(list)) if index < list.len() => {
+                Ok(list.remove(index))
+            }
+            (Self::ListIndex(_), ReflectMut::List(_)) => {
+                Err(Error::access(ty, self.clone()).with_offset(offset))
+            }
+            _ => Err(Error::bad_type(TypeShape::List, ty).with_offset(offset)),
+        }
+    }
+
     fn element_inner_mut<'r>(&self, base: &'r mut dyn Reflect) -> InnerResult<&'r mut dyn Reflect> {
         use ReflectMut::*;
         let base_shape: TypeShape = base.reflect_ref().into();
@@ -223,3 +266,15 @@ impl<'a> Access<'a> {
         }
     }
 }
+
+/// Returns `base` as a [`Map`], or an error at `offset` if it isn't one.
+pub(super) fn expect_map(
+    base: &mut dyn Reflect,
+    offset: usize,
+) -> Result<&mut dyn Map, ReflectPathError<'static>> {
+    let ty: TypeShape = base.reflect_ref().into();
+    match base.reflect_mut() {
+        ReflectMut::Map(map) => Ok(map),
+        _ => Err(Error::bad_type(TypeShape::Map, ty).with_offset(offset)),
+    }
+}
@@ -0,0 +1,546 @@
+use serde::de::{DeserializeSeed, Deserializer, Error};
+
+use super::{access, Access, ParsedPath, ReflectPathError};
+use crate::serde::TypedReflectDeserializer;
+use crate::{Reflect, ReflectFromReflect, ReflectRef, TypeRegistry, VariantType};
+
+impl ParsedPath {
+    /// Inserts `value` into a [`List`] on the given [`Reflect`] object, at the position given by
+    /// the last access of this path.
+    ///
+    /// The path must end with a list index (`[2]`), which may be equal to the length of the list
+    /// to push the value at its end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` can't be inserted into the list, see [`List::insert`].
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_reflect::{ParsedPath, Reflect};
+    /// #[derive(Reflect)]
+    /// struct Inventory {
+    ///   items: Vec<String>,
+    /// }
+    ///
+    /// let mut inventory = Inventory {
+    ///   items: vec!["sword".to_string()],
+    /// };
+    ///
+    /// let path = ParsedPath::parse("items[0]").unwrap();
+    /// path.insert(&mut inventory, Box::new("shield".to_string())).unwrap();
+    /// assert_eq!(inventory.items, vec!["shield", "sword"]);
+    ///
+    /// let removed = path.remove(&mut inventory).unwrap();
+    /// assert_eq!(removed.take::<String>().unwrap(), "shield");
+    /// ```
+    ///
+    /// [`List`]: crate::List
+    /// [`List::insert`]: crate::List::insert
+    pub fn insert<'p>(
+        &'p self,
+        root: &mut dyn Reflect,
+        value: Box<dyn Reflect>,
+    ) -> Result<(), ReflectPathError<'p>> {
+        let (list, access, offset) = self.reflect_parent_mut(root)?;
+        access.insert(list, value, offset)
+    }
+
+    /// Removes the element of a [`List`] this path leads to on the given [`Reflect`] object,
+    /// returning it.
+    ///
+    /// The path must end with a list index (`[2]`).
+    ///
+    /// [`List`]: crate::List
+    pub fn remove<'p>(
+        &'p self,
+        root: &mut dyn Reflect,
+    ) -> Result<Box<dyn Reflect>, ReflectPathError<'p>> {
+        let (list, access, offset) = self.reflect_parent_mut(root)?;
+        access.remove(list, offset)
+    }
+
+    /// Inserts the `key`, `value` pair into the [`Map`] this path leads to on the given
+    /// [`Reflect`] object.
+    ///
+    /// Returns the previous value of `key`, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` or `value` can't be inserted into the map, see [`Map::insert_boxed`].
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_reflect::{ParsedPath, Reflect};
+    /// # use bevy_utils::HashMap;
+    /// #[derive(Reflect)]
+    /// struct Scores {
+    ///   by_name: HashMap<String, u32>,
+    /// }
+    ///
+    /// let mut scores = Scores {
+    ///   by_name: HashMap::default(),
+    /// };
+    ///
+    /// let path = ParsedPath::parse("by_name").unwrap();
+    /// path.insert_entry(&mut scores, Box::new("alice".to_string()), Box::new(10u32))
+    ///     .unwrap();
+    /// assert_eq!(scores.by_name.get("alice"), Some(&10));
+    /// ```
+    ///
+    /// [`Map`]: crate::Map
+    /// [`Map::insert_boxed`]: crate::Map::insert_boxed
+    pub fn insert_entry<'p>(
+        &'p self,
+        root: &mut dyn Reflect,
+        key: Box<dyn Reflect>,
+        value: Box<dyn Reflect>,
+    ) -> Result<Option<Box<dyn Reflect>>, ReflectPathError<'p>> {
+        let map = access::expect_map(self.reflect_element_mut(root)?, self.end_offset())?;
+        Ok(map.insert_boxed(key, value))
+    }
+
+    /// Removes the entry with `key` from the [`Map`] this path leads to on the given
+    /// [`Reflect`] object.
+    ///
+    /// Returns the value of the removed entry, if any.
+    ///
+    /// [`Map`]: crate::Map
+    pub fn remove_entry<'p>(
+        &'p self,
+        root: &mut dyn Reflect,
+        key: &dyn Reflect,
+    ) -> Result<Option<Box<dyn Reflect>>, ReflectPathError<'p>> {
+        let map = access::expect_map(self.reflect_element_mut(root)?, self.end_offset())?;
+        Ok(map.remove(key))
+    }
+
+    /// Deserializes a new value for the element this path leads to on the given [`Reflect`]
+    /// object, and sets the element to it.
+    ///
+    /// The type of the element must be registered in `registry`. If it registers
+    /// [`ReflectFromReflect`], the element is replaced by the deserialized value, otherwise the
+    /// value is [applied](Reflect::apply) to it, which keeps the extra items of lists and the
+    /// extra entries of maps.
+    ///
+    /// Path errors are returned as [custom](Error::custom) errors of the deserializer.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_reflect::{ParsedPath, Reflect, TypeRegistry};
+    /// #[derive(Reflect)]
+    /// struct Player {
+    ///   name: String,
+    ///   position: (f32, f32),
+    /// }
+    ///
+    /// let mut registry = TypeRegistry::default();
+    /// registry.register::<Player>();
+    /// registry.register::<(f32, f32)>();
+    ///
+    /// let mut player = Player {
+    ///   name: "alice".to_string(),
+    ///   position: (0.0, 0.0),
+    /// };
+    ///
+    /// let path = ParsedPath::parse("position").unwrap();
+    /// let mut deserializer = ron::de::Deserializer::from_str("(1.0, 2.5)").unwrap();
+    /// path.deserialize_element(&mut player, &registry, &mut deserializer)
+    ///     .unwrap();
+    /// assert_eq!(player.position, (1.0, 2.5));
+    /// ```
+    pub fn deserialize_element<'de, D: Deserializer<'de>>(
+        &self,
+        root: &mut dyn Reflect,
+        registry: &TypeRegistry,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        let element = self.reflect_element_mut(root).map_err(Error::custom)?;
+        let value = deserialize_value(element, registry, deserializer)?;
+        set_value(element, value);
+        Ok(())
+    }
+
+    /// Returns the paths to all the leaf elements of the given [`Reflect`] object.
+    ///
+    /// The fields of structs, tuple structs, tuples and enum variants, and the items of lists and
+    /// arrays are walked recursively. Their paths are returned in order when they contain no
+    /// such element themselves: values, maps, sets, unit variants and empty containers are
+    /// leaves.
+    ///
+    /// A value without any field is its own only leaf, with an empty path.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_reflect::{ParsedPath, Reflect};
+    /// #[derive(Reflect)]
+    /// struct Player {
+    ///   name: String,
+    ///   position: (f32, f32),
+    ///   items: Vec<u32>,
+    /// }
+    ///
+    /// let player = Player {
+    ///   name: "alice".to_string(),
+    ///   position: (0.0, 0.0),
+    ///   items: vec![7],
+    /// };
+    ///
+    /// let leaves = ParsedPath::leaves(&player)
+    ///     .iter()
+    ///     .map(ToString::to_string)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(
+    ///   leaves,
+    ///   vec![".name", ".position.0", ".position.1", ".items[0]"],
+    /// );
+    /// ```
+    pub fn leaves(root: &dyn Reflect) -> Vec<ParsedPath> {
+        let mut leaves = Vec::new();
+        push_leaves(String::new(), root, &mut leaves);
+        leaves
+            .into_iter()
+            .map(|path| {
+                ParsedPath::parse(&path)
+                    .expect("paths to the elements of reflected values should be valid")
+            })
+            .collect()
+    }
+
+    /// Returns the offset of the last access of this path, to report errors about the element
+    /// this path leads to.
+    fn end_offset(&self) -> usize {
+        self.0.last().map_or(0, |(_, offset)| *offset)
+    }
+
+    /// Returns the parent of the element this path leads to, along with the last access of
+    /// this path and its offset.
+    fn reflect_parent_mut<'r, 'p>(
+        &'p self,
+        root: &'r mut dyn Reflect,
+    ) -> Result<(&'r mut dyn Reflect, &'p Access<'static>, usize), ReflectPathError<'p>> {
+        let Some(((access, offset), parents)) = self.0.split_last() else {
+            return Err(ReflectPathError::ExpectedListIndex);
+        };
+        if !matches!(access, Access::ListIndex(_)) {
+            return Err(ReflectPathError::ExpectedListIndex);
+        }
+        let mut current = root;
+        for (access, offset) in parents {
+            current = access.element_mut(current, *offset)?;
+        }
+        Ok((current, access, *offset))
+    }
+}
+
+fn push_leaves(path: String, value: &dyn Reflect, leaves: &mut Vec<String>) {
+    let children: Vec<(String, &dyn Reflect)> = match value.reflect_ref() {
+        ReflectRef::Struct(value) => (0..value.field_len())
+            .map(|index| {
+                let name = value.name_at(index).unwrap();
+                (format!("{path}.{name}"), value.field_at(index).unwrap())
+            })
+            .collect(),
+        ReflectRef::TupleStruct(value) => value
+            .iter_fields()
+            .enumerate()
+            .map(|(index, field)| (format!("{path}.{index}"), field))
+            .collect(),
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .enumerate()
+            .map(|(index, field)| (format!("{path}.{index}"), field))
+            .collect(),
+        ReflectRef::List(value) => value
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (format!("{path}[{index}]"), item))
+            .collect(),
+        ReflectRef::Array(value) => value
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (format!("{path}[{index}]"), item))
+            .collect(),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .enumerate()
+            .map(|(index, field)| match value.variant_type() {
+                VariantType::Struct => (format!("{path}.{}", field.name().unwrap()), field.value()),
+                _ => (format!("{path}.{index}"), field.value()),
+            })
+            .collect(),
+        ReflectRef::Map(_) | ReflectRef::Set(_) | ReflectRef::Value(_) => Vec::new(),
+    };
+
+    if children.is_empty() {
+        leaves.push(path);
+    }
+    for (path, child) in children {
+        push_leaves(path, child, leaves);
+    }
+}
+
+/// Deserializes a value of the registered type of `element`, converting it to the concrete type
+/// when possible.
+fn deserialize_value<'de, D: Deserializer<'de>>(
+    element: &dyn Reflect,
+    registry: &TypeRegistry,
+    deserializer: D,
+) -> Result<Box<dyn Reflect>, D::Error> {
+    let type_name = element.type_name();
+    let registration = registry.get_with_name(type_name).ok_or_else(|| {
+        Error::custom(format_args!("no registration found for type `{type_name}`"))
+    })?;
+
+    let value = TypedReflectDeserializer::new(registration, registry).deserialize(deserializer)?;
+    Ok(match registration.data::<ReflectFromReflect>() {
+        Some(from_reflect) => from_reflect.from_reflect(&*value).unwrap_or(value),
+        None => value,
+    })
+}
+
+/// Replaces `element` by `value`, or applies `value` to it if their types differ.
+fn set_value(element: &mut dyn Reflect, value: Box<dyn Reflect>) {
+    if let Err(value) = element.set(value) {
+        element.apply(&*value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_reflect;
+    use crate::path::access::TypeShape;
+    use crate::path::AccessError;
+    use crate::ReflectMut;
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, Debug, PartialEq)]
+    struct Level {
+        name: String,
+        spawns: Vec<(i32, i32)>,
+        corners: [u8; 2],
+        scores: HashMap<String, u32>,
+        state: State,
+        unlocked: Vec<bool>,
+    }
+
+    #[derive(Reflect, Debug, PartialEq)]
+    enum State {
+        Locked,
+        Open { visits: u32 },
+    }
+
+    fn level() -> Level {
+        Level {
+            name: "forest".to_string(),
+            spawns: vec![(0, 0), (4, 2)],
+            corners: [1, 2],
+            scores: HashMap::default(),
+            state: State::Open { visits: 3 },
+            unlocked: Vec::new(),
+        }
+    }
+
+    fn parse(path: &str) -> ParsedPath {
+        ParsedPath::parse(path).unwrap()
+    }
+
+    #[test]
+    fn should_insert_and_remove_list_items() {
+        let mut level = level();
+
+        parse("spawns[2]")
+            .insert(&mut level, Box::new((9, 9)))
+            .unwrap();
+        parse("spawns[0]")
+            .insert(&mut level, Box::new((1, 1)))
+            .unwrap();
+        assert_eq!(level.spawns, vec![(1, 1), (0, 0), (4, 2), (9, 9)]);
+
+        let removed = parse("spawns[1]").remove(&mut level).unwrap();
+        assert_eq!(removed.take::<(i32, i32)>().unwrap(), (0, 0));
+        assert_eq!(level.spawns, vec![(1, 1), (4, 2), (9, 9)]);
+    }
+
+    #[test]
+    fn should_fail_to_insert_or_remove_invalid_items() {
+        let mut level = level();
+
+        assert_eq!(
+            parse("spawns[5]").insert(&mut level, Box::new((9, 9))),
+            Err(ReflectPathError::InvalidAccess {
+                offset: 7,
+                error: AccessError(access::Error::Access {
+                    ty: TypeShape::List,
+                    access: Access::ListIndex(5),
+                }),
+            })
+        );
+        assert!(matches!(
+            parse("spawns[2]").remove(&mut level),
+            Err(ReflectPathError::InvalidAccess { offset: 7, .. })
+        ));
+        assert!(matches!(
+            parse("corners[0]").remove(&mut level),
+            Err(ReflectPathError::InvalidAccess {
+                offset: 8,
+                error: AccessError(access::Error::Type {
+                    expected: TypeShape::List,
+                    actual: TypeShape::Array,
+                }),
+            })
+        ));
+        assert!(matches!(
+            parse("spawns").remove(&mut level),
+            Err(ReflectPathError::ExpectedListIndex)
+        ));
+        assert!(matches!(
+            parse("").remove(&mut level),
+            Err(ReflectPathError::ExpectedListIndex)
+        ));
+        assert_eq!(level, self::level());
+    }
+
+    #[test]
+    fn should_insert_and_remove_map_entries() {
+        let mut level = level();
+        let path = parse("scores");
+
+        let previous = path
+            .insert_entry(&mut level, Box::new("alice".to_string()), Box::new(10u32))
+            .unwrap();
+        assert!(previous.is_none());
+        let previous = path
+            .insert_entry(&mut level, Box::new("alice".to_string()), Box::new(12u32))
+            .unwrap();
+        assert_eq!(previous.unwrap().take::<u32>().unwrap(), 10);
+        assert_eq!(level.scores.get("alice"), Some(&12));
+
+        let removed = path.remove_entry(&mut level, &"alice".to_string()).unwrap();
+        assert_eq!(removed.unwrap().take::<u32>().unwrap(), 12);
+        assert!(level.scores.is_empty());
+
+        assert!(matches!(
+            parse("name").remove_entry(&mut level, &"alice".to_string()),
+            Err(ReflectPathError::InvalidAccess {
+                error: AccessError(access::Error::Type {
+                    expected: TypeShape::Map,
+                    actual: TypeShape::Value,
+                }),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn should_deserialize_elements() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Level>();
+        registry.register::<Vec<(i32, i32)>>();
+        registry.register_type_data::<Vec<(i32, i32)>, ReflectFromReflect>();
+        registry.register::<(i32, i32)>();
+        registry.register::<i32>();
+        registry.register::<u32>();
+        registry.register::<State>();
+
+        let mut level = level();
+        let mut deserialize = |path: &str, input: &str| {
+            let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+            parse(path).deserialize_element(&mut level, &registry, &mut deserializer)
+        };
+
+        deserialize("spawns", "[(5, 5)]").unwrap();
+        deserialize("spawns[0].1", "7").unwrap();
+        deserialize("state", "Locked").unwrap();
+        assert!(deserialize("corners", "[3, 4]")
+            .unwrap_err()
+            .to_string()
+            .contains("no registration found for type `[u8; 2]`"));
+        assert!(deserialize("missing", "1").is_err());
+        assert!(deserialize("spawns[0].1", r#""seven""#).is_err());
+
+        assert_eq!(level.spawns, vec![(5, 7)]);
+        assert_eq!(level.state, State::Locked);
+        assert_eq!(level.corners, [1, 2]);
+    }
+
+    #[test]
+    fn should_edit_elements_in_turn() {
+        let mut level = level();
+        let (name, spawn, spawns, corner, visits) = (
+            parse("name"),
+            parse("spawns[1].0"),
+            parse("spawns"),
+            parse("corners[1]"),
+            parse("state.visits"),
+        );
+
+        let values: [&dyn Reflect; 4] = [&"cave".to_string(), &6, &7u8, &4u32];
+        ParsedPath::for_each_element_mut(
+            &mut level,
+            &[&name, &spawn, &corner, &visits],
+            |index, element| element.apply(values[index]),
+        )
+        .unwrap();
+        assert_eq!(level.name, "cave");
+        assert_eq!(level.spawns, vec![(0, 0), (6, 2)]);
+        assert_eq!(level.corners, [1, 7]);
+        assert_eq!(level.state, State::Open { visits: 4 });
+
+        // later paths see the edits made through earlier ones
+        let mut lengths = Vec::new();
+        ParsedPath::for_each_element_mut(&mut level, &[&spawns, &spawns], |_, element| {
+            let ReflectMut::List(list) = element.reflect_mut() else {
+                panic!("expected a list");
+            };
+            list.push(Box::new((3, 3)));
+            lengths.push(list.len());
+        })
+        .unwrap();
+        assert_eq!(lengths, vec![3, 4]);
+
+        // nothing is edited when a path is invalid
+        let mut calls = 0;
+        assert!(matches!(
+            ParsedPath::for_each_element_mut(&mut level, &[&name, &parse("missing")], |_, _| {
+                calls += 1
+            }),
+            Err(ReflectPathError::InvalidAccess { .. })
+        ));
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn should_list_leaves() {
+        let leaves = ParsedPath::leaves(&level())
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            leaves,
+            vec![
+                ".name",
+                ".spawns[0].0",
+                ".spawns[0].1",
+                ".spawns[1].0",
+                ".spawns[1].1",
+                ".corners[0]",
+                ".corners[1]",
+                ".scores",
+                ".state.visits",
+                ".unlocked",
+            ]
+        );
+
+        let mut level = level();
+        level.state = State::Locked;
+        for path in ParsedPath::leaves(&level) {
+            assert!(path.reflect_element(&level).is_ok());
+        }
+        assert!(ParsedPath::leaves(&level)
+            .iter()
+            .any(|path| path.to_string() == ".state"));
+        assert_eq!(ParsedPath::leaves(&1u8), vec![parse("")]);
+    }
+}
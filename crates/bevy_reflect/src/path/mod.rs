@@ -1,4 +1,5 @@
 mod access;
+mod edit;
 mod parse;
 
 use std::fmt;
@@ -28,6 +29,9 @@ pub enum ReflectPathError<'a> {
     #[error("failed to downcast to the path result to the given type")]
     InvalidDowncast,
 
+    #[error("expected the path to end with a list index")]
+    ExpectedListIndex,
+
     #[error("at {offset} in '{path}': {error}")]
     ParseError {
         /// Position in `path`.
@@ -262,6 +266,12 @@ impl GetPath for dyn Reflect {
 ///
 /// It's recommended to use this in place of `GetPath` when the path string is
 /// unlikely to be changed and will be accessed repeatedly.
+///
+/// Paths can also be used to edit reflected values generically, by inserting or removing
+/// [list items](Self::insert) and [map entries](Self::insert_entry),
+/// or by [deserializing](Self::deserialize_element) a new value for an element.
+/// Several elements can be edited in turn with [`ParsedPath::for_each_element_mut`],
+/// and all the paths of a value can be found with [`ParsedPath::leaves`].
 #[derive(Clone, Debug, PartialEq, PartialOrd, Ord, Eq, Hash)]
 pub struct ParsedPath(
     /// This is the boxed slice of pre-parsed accesses.
@@ -369,6 +379,50 @@ impl ParsedPath {
         Ok(current)
     }
 
+    /// Calls `f` with the index of each of the given paths and a mutable reference to the
+    /// element it leads to on the given [`Reflect`] object, one path at a time.
+    ///
+    /// All the paths are checked before `f` is called, so nothing is edited if one of them is
+    /// invalid for the provided type. Later paths see the edits made through earlier ones.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_reflect::{ParsedPath, Reflect};
+    /// #[derive(Reflect)]
+    /// struct Player {
+    ///   health: u32,
+    ///   items: Vec<u32>,
+    /// }
+    ///
+    /// let mut player = Player {
+    ///   health: 10,
+    ///   items: vec![1, 2],
+    /// };
+    ///
+    /// let health = ParsedPath::parse("health").unwrap();
+    /// let item = ParsedPath::parse("items[1]").unwrap();
+    /// let values = [5u32, 7];
+    ///
+    /// ParsedPath::for_each_element_mut(&mut player, &[&health, &item], |index, element| {
+    ///   element.apply(&values[index]);
+    /// })
+    /// .unwrap();
+    /// assert_eq!((player.health, player.items[1]), (5, 7));
+    /// ```
+    pub fn for_each_element_mut<'p>(
+        root: &mut dyn Reflect,
+        paths: &[&'p ParsedPath],
+        mut f: impl FnMut(usize, &mut dyn Reflect),
+    ) -> Result<(), ReflectPathError<'p>> {
+        for path in paths {
+            path.reflect_element(root)?;
+        }
+        for (index, path) in paths.iter().enumerate() {
+            f(index, path.reflect_element_mut(root)?);
+        }
+        Ok(())
+    }
+
     /// Gets a typed, read-only reference to the specified element on the given [`Reflect`] object.
     ///
     /// Returns an error if the path is invalid for the provided type.
@@ -1,10 +1,10 @@
 use crate::serde::SerializationData;
 use crate::{
-    ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicStruct, DynamicTuple,
-    DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField, Reflect,
-    ReflectDeserialize, StructInfo, StructVariantInfo, Tuple, TupleInfo, TupleStruct,
-    TupleStructInfo, TupleVariantInfo, TypeInfo, TypeRegistration, TypeRegistry, UnnamedField,
-    VariantInfo,
+    ArrayInfo, DynamicArray, DynamicEnum, DynamicList, DynamicMap, DynamicSet, DynamicStruct,
+    DynamicTuple, DynamicTupleStruct, DynamicVariant, EnumInfo, ListInfo, Map, MapInfo, NamedField,
+    Reflect, ReflectDeserialize, ReflectFromReflect, Set, SetInfo, StructInfo, StructVariantInfo,
+    Tuple, TupleInfo, TupleStruct, TupleStructInfo, TupleVariantInfo, TypeInfo, TypeRegistration,
+    TypeRegistry, UnnamedField, VariantInfo,
 };
 use erased_serde::Deserializer;
 use serde::de::{
@@ -159,7 +159,7 @@ impl<T: Display> Debug for ExpectedValues<T> {
 
 /// Represents a simple reflected identifier.
 #[derive(Debug, Clone, Eq, PartialEq)]
-struct Ident(String);
+pub(super) struct Ident(pub(super) String);
 
 impl<'de> Deserialize<'de> for Ident {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -439,6 +439,14 @@ impl<'a, 'de> DeserializeSeed<'de> for TypedReflectDeserializer<'a> {
                 dynamic_map.set_represented_type(Some(self.registration.type_info()));
                 Ok(Box::new(dynamic_map))
             }
+            TypeInfo::Set(set_info) => {
+                let mut dynamic_set = deserializer.deserialize_seq(SetVisitor {
+                    set_info,
+                    registry: self.registry,
+                })?;
+                dynamic_set.set_represented_type(Some(self.registration.type_info()));
+                Ok(Box::new(dynamic_set))
+            }
             TypeInfo::Tuple(tuple_info) => {
                 let mut dynamic_tuple = deserializer.deserialize_tuple(
                     tuple_info.field_len(),
@@ -745,6 +753,49 @@ impl<'a, 'de> Visitor<'de> for MapVisitor<'a> {
     }
 }
 
+struct SetVisitor<'a> {
+    set_info: &'static SetInfo,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SetVisitor<'a> {
+    type Value = DynamicSet;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("reflected set value")
+    }
+
+    fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
+    where
+        V: SeqAccess<'de>,
+    {
+        let mut dynamic_set = DynamicSet::default();
+        let registration = get_registration(
+            self.set_info.value_type_id(),
+            self.set_info.value_type_name(),
+            self.registry,
+        )?;
+        let from_reflect = registration.data::<ReflectFromReflect>();
+        while let Some(value) = seq.next_element_seed(TypedReflectDeserializer {
+            registration,
+            registry: self.registry,
+        })? {
+            // concrete values can usually be hashed, unlike the dynamic ones
+            let value = from_reflect
+                .and_then(|from_reflect| from_reflect.from_reflect(&*value))
+                .unwrap_or(value);
+            if value.reflect_hash().is_none() && value.reflect_partial_eq(&*value).is_none() {
+                return Err(Error::custom(format_args!(
+                    "the values of {} can't be hashed or compared, so they can't be deduplicated",
+                    self.set_info.type_name()
+                )));
+            }
+            dynamic_set.insert_boxed(value);
+        }
+        Ok(dynamic_set)
+    }
+}
+
 struct EnumVisitor<'a> {
     enum_info: &'static EnumInfo,
     registration: &'a TypeRegistration,
@@ -803,7 +854,12 @@ impl<'a, 'de> Visitor<'de> for EnumVisitor<'a> {
                 .into(),
         };
 
-        dynamic_enum.set_variant(variant_info.name(), value);
+        let variant_name = variant_info.name();
+        let variant_index = self
+            .enum_info
+            .index_of(variant_name)
+            .expect("variant should exist");
+        dynamic_enum.set_variant_with_index(variant_index, variant_name, value);
         Ok(dynamic_enum)
     }
 }
@@ -1087,6 +1143,7 @@ fn get_registration<'a, E: Error>(
 mod tests {
     use bincode::Options;
     use std::any::TypeId;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::f32::consts::PI;
 
     use serde::de::DeserializeSeed;
@@ -1096,7 +1153,7 @@ mod tests {
 
     use crate as bevy_reflect;
     use crate::serde::{TypedReflectDeserializer, UntypedReflectDeserializer};
-    use crate::{DynamicEnum, FromReflect, Reflect, ReflectDeserialize, TypeRegistry};
+    use crate::{DynamicEnum, FromReflect, Reflect, ReflectDeserialize, ReflectRef, TypeRegistry};
 
     #[derive(Reflect, Debug, PartialEq)]
     struct MyStruct {
@@ -1335,6 +1392,97 @@ mod tests {
         assert_eq!(expected, output);
     }
 
+    #[test]
+    fn should_deserialize_sets_and_ordered_maps() {
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Collections {
+            tags: bevy_utils::HashSet<String>,
+            ids: BTreeSet<i32>,
+            scores: BTreeMap<String, i32>,
+        }
+
+        let expected = Collections {
+            tags: bevy_utils::HashSet::from(["a".to_string(), "b".to_string()]),
+            ids: BTreeSet::from([1, 2, 3]),
+            scores: BTreeMap::from([("x".to_string(), 1), ("y".to_string(), 2)]),
+        };
+
+        let input = r#"(
+            tags: ["b", "a"],
+            ids: [3, 1, 2],
+            scores: {
+                "y": 2,
+                "x": 1,
+            },
+        )"#;
+
+        let mut registry = get_registry();
+        registry.register::<Collections>();
+        registry.register::<bevy_utils::HashSet<String>>();
+        registry.register::<BTreeSet<i32>>();
+        registry.register::<BTreeMap<String, i32>>();
+        let registration = registry.get(TypeId::of::<Collections>()).unwrap();
+        let reflect_deserializer = TypedReflectDeserializer::new(registration, &registry);
+        let mut ron_deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let dynamic_output = reflect_deserializer
+            .deserialize(&mut ron_deserializer)
+            .unwrap();
+
+        let output = <Collections as FromReflect>::from_reflect(dynamic_output.as_ref()).unwrap();
+        assert_eq!(expected, output);
+    }
+
+    #[test]
+    fn should_deserialize_set_of_structs() {
+        #[derive(Reflect, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Reflect, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        #[reflect(Hash, PartialEq)]
+        struct HashablePoint {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Reflect, Debug, PartialEq)]
+        struct Shapes {
+            points: BTreeSet<Point>,
+            hashable_points: bevy_utils::HashSet<HashablePoint>,
+        }
+
+        let expected = Shapes {
+            points: BTreeSet::from([Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]),
+            hashable_points: bevy_utils::HashSet::from([
+                HashablePoint { x: 1, y: 2 },
+                HashablePoint { x: 3, y: 4 },
+            ]),
+        };
+
+        let input = r#"(
+            points: [(x: 3, y: 4), (x: 1, y: 2), (x: 3, y: 4)],
+            hashable_points: [(x: 1, y: 2), (x: 3, y: 4)],
+        )"#;
+
+        let mut registry = get_registry();
+        registry.register::<Shapes>();
+        registry.register::<Point>();
+        registry.register::<HashablePoint>();
+        registry.register::<BTreeSet<Point>>();
+        registry.register::<bevy_utils::HashSet<HashablePoint>>();
+        let registration = registry.get(TypeId::of::<Shapes>()).unwrap();
+        let reflect_deserializer = TypedReflectDeserializer::new(registration, &registry);
+        let mut ron_deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let dynamic_output = reflect_deserializer
+            .deserialize(&mut ron_deserializer)
+            .unwrap();
+
+        let output = <Shapes as FromReflect>::from_reflect(dynamic_output.as_ref()).unwrap();
+        assert_eq!(expected, output);
+    }
+
     #[test]
     fn should_deserialize_option() {
         #[derive(Reflect, Debug, PartialEq)]
@@ -1460,6 +1608,12 @@ mod tests {
             value: String::from("I <3 Enums"),
         });
         assert!(expected.reflect_partial_eq(output.as_ref()).unwrap());
+
+        // the variant index is kept so the output can be serialized again
+        let ReflectRef::Enum(output) = output.reflect_ref() else {
+            panic!("expected enum");
+        };
+        assert_eq!(3, output.variant_index());
     }
 
     #[test]
@@ -1,8 +1,12 @@
 mod de;
+mod patch;
+mod schema;
 mod ser;
 mod type_data;
 
 pub use de::*;
+pub use patch::*;
+pub use schema::*;
 pub use ser::*;
 pub use type_data::*;
 
@@ -0,0 +1,476 @@
+use std::fmt;
+
+use serde::de::{DeserializeSeed, EnumAccess, Error, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{SerializeSeq, SerializeStructVariant};
+use serde::{Serialize, Serializer};
+
+use crate::serde::{de::Ident, ReflectSerializer, UntypedReflectDeserializer};
+use crate::{ParsedPath, Patch, PatchOperation, Reflect, TypeRegistry};
+
+const OPERATION_NAME: &str = "PatchOperation";
+const OPERATION_VARIANTS: &[&str] = &[
+    "Set",
+    "Insert",
+    "Remove",
+    "InsertEntry",
+    "RemoveEntry",
+    "InsertValue",
+    "RemoveValue",
+];
+
+/// A serializer for [`Patch`]es.
+///
+/// The patch is serialized as a sequence of its operations, in the shape of a `PatchOperation`
+/// enum with a struct variant for each [`PatchOperation`]. The paths of the operations are
+/// serialized as strings, and their keys and values with [`ReflectSerializer`].
+pub struct PatchSerializer<'a> {
+    pub patch: &'a Patch,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> PatchSerializer<'a> {
+    pub fn new(patch: &'a Patch, registry: &'a TypeRegistry) -> Self {
+        Self { patch, registry }
+    }
+}
+
+impl<'a> Serialize for PatchSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let operations = self.patch.operations();
+        let mut state = serializer.serialize_seq(Some(operations.len()))?;
+        for operation in operations {
+            state.serialize_element(&OperationSerializer {
+                operation,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct OperationSerializer<'a> {
+    operation: &'a PatchOperation,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> OperationSerializer<'a> {
+    fn reflect(&self, value: &'a dyn Reflect) -> ReflectSerializer<'a> {
+        ReflectSerializer::new(value, self.registry)
+    }
+}
+
+impl<'a> Serialize for OperationSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let path = self.operation.path().to_string();
+        let (variant_index, len) = match self.operation {
+            PatchOperation::Set { .. } => (0, 2),
+            PatchOperation::Insert { .. } => (1, 3),
+            PatchOperation::Remove { .. } => (2, 2),
+            PatchOperation::InsertEntry { .. } => (3, 3),
+            PatchOperation::RemoveEntry { .. } => (4, 2),
+            PatchOperation::InsertValue { .. } => (5, 2),
+            PatchOperation::RemoveValue { .. } => (6, 2),
+        };
+        let mut state = serializer.serialize_struct_variant(
+            OPERATION_NAME,
+            variant_index,
+            OPERATION_VARIANTS[variant_index as usize],
+            len,
+        )?;
+        state.serialize_field("path", &path)?;
+        match self.operation {
+            PatchOperation::Set { value, .. } => {
+                state.serialize_field("value", &self.reflect(&**value))?;
+            }
+            PatchOperation::Insert { index, value, .. } => {
+                state.serialize_field("index", index)?;
+                state.serialize_field("value", &self.reflect(&**value))?;
+            }
+            PatchOperation::Remove { index, .. } => {
+                state.serialize_field("index", index)?;
+            }
+            PatchOperation::InsertEntry { key, value, .. } => {
+                state.serialize_field("key", &self.reflect(&**key))?;
+                state.serialize_field("value", &self.reflect(&**value))?;
+            }
+            PatchOperation::RemoveEntry { key, .. } => {
+                state.serialize_field("key", &self.reflect(&**key))?;
+            }
+            PatchOperation::InsertValue { value, .. }
+            | PatchOperation::RemoveValue { value, .. } => {
+                state.serialize_field("value", &self.reflect(&**value))?;
+            }
+        }
+        state.end()
+    }
+}
+
+/// A deserializer for [`Patch`]es serialized with [`PatchSerializer`].
+///
+/// The keys and values of the operations are deserialized with [`UntypedReflectDeserializer`],
+/// so they have the same dynamic types.
+pub struct PatchDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> PatchDeserializer<'a> {
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self { registry }
+    }
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for PatchDeserializer<'a> {
+    type Value = Patch;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PatchVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct PatchVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for PatchVisitor<'a> {
+    type Value = Patch;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("patch operations")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut operations = Vec::new();
+        while let Some(operation) = seq.next_element_seed(OperationDeserializer {
+            registry: self.registry,
+        })? {
+            operations.push(operation);
+        }
+        Ok(Patch::new(operations))
+    }
+}
+
+struct OperationDeserializer<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for OperationDeserializer<'a> {
+    type Value = PatchOperation;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(
+            OPERATION_NAME,
+            OPERATION_VARIANTS,
+            OperationVisitor {
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct OperationVisitor<'a> {
+    registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for OperationVisitor<'a> {
+    type Value = PatchOperation;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("patch operation")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let (variant_index, variant) = data.variant_seed(VariantDeserializer)?;
+        let fields: &'static [&'static str] = match variant_index {
+            0 => &["path", "value"],
+            1 => &["path", "index", "value"],
+            2 => &["path", "index"],
+            3 => &["path", "key", "value"],
+            4 => &["path", "key"],
+            _ => &["path", "value"],
+        };
+        variant.struct_variant(
+            fields,
+            OperationFieldsVisitor {
+                variant_index,
+                fields,
+                registry: self.registry,
+            },
+        )
+    }
+}
+
+struct VariantDeserializer;
+
+impl<'de> DeserializeSeed<'de> for VariantDeserializer {
+    type Value = usize;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct VariantVisitor;
+
+        impl<'de> Visitor<'de> for VariantVisitor {
+            type Value = usize;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("either a variant index or variant name")
+            }
+
+            fn visit_str<E>(self, variant_name: &str) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                OPERATION_VARIANTS
+                    .iter()
+                    .position(|name| *name == variant_name)
+                    .ok_or_else(|| Error::unknown_variant(variant_name, OPERATION_VARIANTS))
+            }
+
+            fn visit_u64<E>(self, variant_index: u64) -> Result<Self::Value, E>
+            where
+                E: Error,
+            {
+                usize::try_from(variant_index)
+                    .ok()
+                    .filter(|index| *index < OPERATION_VARIANTS.len())
+                    .ok_or_else(|| {
+                        Error::custom(format_args!(
+                            "no variant found at index `{variant_index}` on enum `{OPERATION_NAME}`"
+                        ))
+                    })
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
+
+struct OperationFieldsVisitor<'a> {
+    variant_index: usize,
+    fields: &'static [&'static str],
+    registry: &'a TypeRegistry,
+}
+
+/// The fields of a patch operation, as they are deserialized.
+#[derive(Default)]
+struct OperationFields {
+    path: Option<ParsedPath>,
+    index: Option<usize>,
+    key: Option<Box<dyn Reflect>>,
+    value: Option<Box<dyn Reflect>>,
+}
+
+impl<'a> OperationFieldsVisitor<'a> {
+    fn build<E: Error>(&self, fields: OperationFields) -> Result<PatchOperation, E> {
+        let path = fields.path.ok_or_else(|| Error::missing_field("path"))?;
+        let index = || fields.index.ok_or_else(|| Error::missing_field("index"));
+        Ok(match self.variant_index {
+            0 => PatchOperation::Set {
+                path,
+                value: fields.value.ok_or_else(|| Error::missing_field("value"))?,
+            },
+            1 => PatchOperation::Insert {
+                path,
+                index: index()?,
+                value: fields.value.ok_or_else(|| Error::missing_field("value"))?,
+            },
+            2 => PatchOperation::Remove {
+                path,
+                index: index()?,
+            },
+            3 => PatchOperation::InsertEntry {
+                path,
+                key: fields.key.ok_or_else(|| Error::missing_field("key"))?,
+                value: fields.value.ok_or_else(|| Error::missing_field("value"))?,
+            },
+            4 => PatchOperation::RemoveEntry {
+                path,
+                key: fields.key.ok_or_else(|| Error::missing_field("key"))?,
+            },
+            5 => PatchOperation::InsertValue {
+                path,
+                value: fields.value.ok_or_else(|| Error::missing_field("value"))?,
+            },
+            _ => PatchOperation::RemoveValue {
+                path,
+                value: fields.value.ok_or_else(|| Error::missing_field("value"))?,
+            },
+        })
+    }
+}
+
+fn parse_path<E: Error>(path: &str) -> Result<ParsedPath, E> {
+    ParsedPath::parse(path).map_err(Error::custom)
+}
+
+impl<'a, 'de> Visitor<'de> for OperationFieldsVisitor<'a> {
+    type Value = PatchOperation;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("patch operation fields")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut fields = OperationFields::default();
+        for (index, field) in self.fields.iter().enumerate() {
+            let missing = || Error::invalid_length(index, &self);
+            match *field {
+                "path" => {
+                    let path = seq.next_element::<String>()?.ok_or_else(missing)?;
+                    fields.path = Some(parse_path(&path)?);
+                }
+                "index" => fields.index = Some(seq.next_element()?.ok_or_else(missing)?),
+                "key" => {
+                    fields.key = Some(
+                        seq.next_element_seed(UntypedReflectDeserializer::new(self.registry))?
+                            .ok_or_else(missing)?,
+                    );
+                }
+                _ => {
+                    fields.value = Some(
+                        seq.next_element_seed(UntypedReflectDeserializer::new(self.registry))?
+                            .ok_or_else(missing)?,
+                    );
+                }
+            }
+        }
+        self.build(fields)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut fields = OperationFields::default();
+        while let Some(Ident(field)) = map.next_key::<Ident>()? {
+            match field.as_str() {
+                "path" => {
+                    let path = map.next_value::<String>()?;
+                    fields.path = Some(parse_path(&path)?);
+                }
+                "index" => fields.index = Some(map.next_value()?),
+                "key" => {
+                    fields.key =
+                        Some(map.next_value_seed(UntypedReflectDeserializer::new(self.registry))?);
+                }
+                "value" => {
+                    fields.value =
+                        Some(map.next_value_seed(UntypedReflectDeserializer::new(self.registry))?);
+                }
+                _ => return Err(Error::unknown_field(&field, self.fields)),
+            }
+        }
+        self.build(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_reflect;
+    use crate::serde::{PatchDeserializer, PatchSerializer};
+    use crate::{Patch, Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+    use bincode::Options;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Level {
+        name: String,
+        spawns: Vec<(i32, i32)>,
+        scores: HashMap<String, u32>,
+    }
+
+    fn get_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Level>();
+        registry.register::<(i32, i32)>();
+        registry
+    }
+
+    fn levels() -> (Level, Level) {
+        let old = Level {
+            name: "forest".to_string(),
+            spawns: vec![(0, 0), (4, 2)],
+            scores: HashMap::from([("alice".to_string(), 10), ("bob".to_string(), 5)]),
+        };
+        let new = Level {
+            name: "dark forest".to_string(),
+            spawns: vec![(1, 1), (0, 0)],
+            scores: HashMap::from([("alice".to_string(), 12)]),
+        };
+        (old, new)
+    }
+
+    #[test]
+    fn should_roundtrip_patch_with_ron() {
+        let registry = get_registry();
+        let (old, new) = levels();
+        let patch = Patch::diff(&old, &new);
+
+        let ron = ron::to_string(&PatchSerializer::new(&patch, &registry)).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let patch = PatchDeserializer::new(&registry)
+            .deserialize(&mut deserializer)
+            .unwrap();
+
+        let mut value = old;
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn should_roundtrip_patch_with_bincode() {
+        let registry = get_registry();
+        let (old, new) = levels();
+        let patch = Patch::diff(&old, &new);
+
+        let bytes = bincode::serialize(&PatchSerializer::new(&patch, &registry)).unwrap();
+        let patch = bincode::DefaultOptions::new()
+            .with_fixint_encoding()
+            .deserialize_seed(PatchDeserializer::new(&registry), &bytes)
+            .unwrap();
+
+        let mut value = old;
+        patch.apply(&mut value).unwrap();
+        assert_eq!(value, new);
+    }
+
+    #[test]
+    fn should_serialize_paths_as_strings() {
+        let registry = get_registry();
+        let (old, mut new) = levels();
+        new.spawns = old.spawns.clone();
+        new.scores = old.scores.clone();
+        let patch = Patch::diff(&old, &new);
+
+        let ron = ron::to_string(&PatchSerializer::new(&patch, &registry)).unwrap();
+        assert_eq!(
+            ron,
+            r#"[Set(path:".name",value:{"alloc::string::String":"dark forest"})]"#
+        );
+    }
+}
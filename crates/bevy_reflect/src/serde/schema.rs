@@ -0,0 +1,760 @@
+use std::any::TypeId;
+use std::collections::BTreeMap;
+
+use bevy_utils::HashSet;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+
+use crate::serde::{SerializationData, TypedReflectSerializer};
+use crate::{
+    std_traits::ReflectDefault, NamedField, Reflect, ReflectRef, TypeInfo, TypeRegistration,
+    TypeRegistry, VariantInfo,
+};
+
+/// The JSON Schema dialect of the schemas generated by [`TypeRegistrySchema`].
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A [JSON Schema] describing the types of a [`TypeRegistry`], as serialized by
+/// [`TypedReflectSerializer`].
+///
+/// Serializing this with [`serde`] produces a schema with a definition for each registered type
+/// under `$defs`, keyed by its [type name](TypeRegistration::type_name), in the shape
+/// [`TypedReflectSerializer`] gives its values in self-describing formats such as JSON or RON:
+/// - structs are objects with a property for each field that isn't skipped,
+/// - tuple structs, tuples and arrays are arrays with an item for each field,
+/// - lists are arrays, and maps are objects,
+/// - enums are one of their variants, which are either the name of a unit variant, or an
+///   object with the name of the variant as its only property, containing its fields,
+/// - `Option`s are either `null` or their value.
+///
+/// Fields of other registered types refer to their definition, fields of unregistered types
+/// can be anything. Types with [`ReflectDefault`] have their default value, serialized with
+/// [`TypedReflectSerializer`], as the `default` of their definition and of their fields.
+///
+/// Serializing fails if a default value can't be serialized.
+///
+/// [JSON Schema]: https://json-schema.org
+pub struct TypeRegistrySchema<'a> {
+    registry: &'a TypeRegistry,
+    filter: Option<Box<dyn Fn(&TypeRegistration) -> bool + 'a>>,
+}
+
+impl<'a> TypeRegistrySchema<'a> {
+    /// Describes all the types of `registry`.
+    pub fn new(registry: &'a TypeRegistry) -> Self {
+        Self {
+            registry,
+            filter: None,
+        }
+    }
+
+    /// Only describes the types `filter` returns `true` for, and the registered types used by
+    /// their fields.
+    pub fn with_filter(mut self, filter: impl Fn(&TypeRegistration) -> bool + 'a) -> Self {
+        self.filter = Some(Box::new(filter));
+        self
+    }
+
+    /// Returns the registrations of the described types, ordered by type name.
+    fn definitions(&self) -> BTreeMap<&'static str, &'a TypeRegistration> {
+        let mut pending = self
+            .registry
+            .iter()
+            .filter(|registration| match &self.filter {
+                Some(filter) => filter(registration),
+                None => true,
+            })
+            .collect::<Vec<_>>();
+        let mut visited = HashSet::new();
+        let mut definitions = BTreeMap::new();
+        while let Some(registration) = pending.pop() {
+            if !visited.insert(registration.type_id()) {
+                continue;
+            }
+            definitions.insert(registration.type_name(), registration);
+            pending.extend(
+                field_type_ids(registration.type_info())
+                    .into_iter()
+                    .filter_map(|type_id| self.registry.get(type_id)),
+            );
+        }
+        definitions
+    }
+}
+
+impl<'a> Serialize for TypeRegistrySchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let definitions = self
+            .definitions()
+            .into_iter()
+            .map(|(type_name, registration)| {
+                (type_name, TypeSchema::new(registration, self.registry))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let mut state = serializer.serialize_map(Some(2))?;
+        state.serialize_entry("$schema", JSON_SCHEMA_DIALECT)?;
+        state.serialize_entry("$defs", &definitions)?;
+        state.end()
+    }
+}
+
+/// The [JSON Schema] of a single registered type, as it is found under `$defs` in a
+/// [`TypeRegistrySchema`].
+///
+/// References to other types point to their definitions in the [`TypeRegistrySchema`].
+///
+/// [JSON Schema]: https://json-schema.org
+pub struct TypeSchema<'a> {
+    registration: &'a TypeRegistration,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> TypeSchema<'a> {
+    /// Describes the type of `registration`, referring to the other types of `registry`.
+    pub fn new(registration: &'a TypeRegistration, registry: &'a TypeRegistry) -> Self {
+        Self {
+            registration,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for TypeSchema<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let type_info = self.registration.type_info();
+        let default = self
+            .registration
+            .data::<ReflectDefault>()
+            .map(ReflectDefault::default);
+
+        let mut schema = match type_info {
+            TypeInfo::Value(_) => primitive_schema(type_info.type_name()).unwrap_or_default(),
+            TypeInfo::Struct(info) => {
+                let serialization_data = self.registration.data::<SerializationData>();
+                let fields = info
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| {
+                        !matches!(serialization_data, Some(data) if data.is_ignored_field(*index))
+                    })
+                    .map(|(_, field)| field);
+                let mut schema = self.struct_schema(fields);
+                // the default values of the fields are the ones of the default value, and the
+                // fields with one can be omitted
+                if let Some(ReflectRef::Struct(default)) = default.as_ref().map(|d| d.reflect_ref())
+                {
+                    for (name, field_schema) in schema.properties.iter_mut().flatten() {
+                        field_schema.default = default.field(name).map(Reflect::clone_value);
+                    }
+                    schema.required = None;
+                }
+                schema
+            }
+            TypeInfo::TupleStruct(info) => {
+                let serialization_data = self.registration.data::<SerializationData>();
+                self.tuple_schema(
+                    info.iter()
+                        .filter(|field| {
+                            !matches!(serialization_data, Some(data) if data.is_ignored_field(field.index()))
+                        })
+                        .map(|field| field.type_id()),
+                )
+            }
+            TypeInfo::Tuple(info) => self.tuple_schema(info.iter().map(|field| field.type_id())),
+            TypeInfo::List(info) => Schema {
+                kind: Some("array"),
+                items: Some(Box::new(self.field_schema(info.item_type_id()))),
+                ..Default::default()
+            },
+            TypeInfo::Array(info) => Schema {
+                kind: Some("array"),
+                items: Some(Box::new(self.field_schema(info.item_type_id()))),
+                min_items: Some(info.capacity()),
+                max_items: Some(info.capacity()),
+                ..Default::default()
+            },
+            TypeInfo::Set(info) => Schema {
+                kind: Some("array"),
+                items: Some(Box::new(self.field_schema(info.value_type_id()))),
+                unique_items: true,
+                ..Default::default()
+            },
+            TypeInfo::Map(info) => Schema {
+                kind: Some("object"),
+                additional_properties: Some(Box::new(self.field_schema(info.value_type_id()))),
+                ..Default::default()
+            },
+            TypeInfo::Enum(info) if info.type_name().starts_with("core::option::Option") => {
+                let some = match info.variant("Some") {
+                    Some(VariantInfo::Tuple(variant)) if variant.field_len() == 1 => {
+                        self.field_schema(variant.field_at(0).unwrap().type_id())
+                    }
+                    _ => Schema::default(),
+                };
+                let none = Schema {
+                    kind: Some("null"),
+                    ..Default::default()
+                };
+                Schema {
+                    any_of: Some(vec![none, some]),
+                    ..Default::default()
+                }
+            }
+            TypeInfo::Enum(info) => Schema {
+                one_of: Some(
+                    info.iter()
+                        .map(|variant| self.variant_schema(variant))
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        };
+
+        schema.title = Some(type_info.type_name().to_string());
+        #[cfg(feature = "documentation")]
+        {
+            schema.description = type_info.docs().map(str::to_string);
+        }
+        schema.default = default;
+
+        SchemaSerializer {
+            schema: &schema,
+            registry: self.registry,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'a> TypeSchema<'a> {
+    /// The schema of a field of the given type: a reference to the definition of the type if it
+    /// is registered and isn't a primitive, or anything if it isn't registered.
+    fn field_schema(&self, type_id: TypeId) -> Schema {
+        let Some(registration) = self.registry.get(type_id) else {
+            return Schema::default();
+        };
+        primitive_schema(registration.type_name()).unwrap_or_else(|| Schema {
+            reference: Some(definition_reference(registration.type_name())),
+            ..Default::default()
+        })
+    }
+
+    fn struct_schema<'f>(&self, fields: impl Iterator<Item = &'f NamedField>) -> Schema {
+        let mut properties = Vec::new();
+        let mut required = Vec::new();
+        for field in fields {
+            properties.push((field.name(), self.field_schema(field.type_id())));
+            required.push(field.name());
+        }
+        Schema {
+            kind: Some("object"),
+            properties: Some(properties),
+            required: Some(required),
+            additional_properties: Some(Box::new(Schema::never())),
+            ..Default::default()
+        }
+    }
+
+    fn tuple_schema(&self, fields: impl Iterator<Item = TypeId>) -> Schema {
+        let prefix_items = fields
+            .map(|type_id| self.field_schema(type_id))
+            .collect::<Vec<_>>();
+        Schema {
+            kind: Some("array"),
+            min_items: Some(prefix_items.len()),
+            prefix_items: Some(prefix_items),
+            items: Some(Box::new(Schema::never())),
+            ..Default::default()
+        }
+    }
+
+    fn variant_schema(&self, variant: &VariantInfo) -> Schema {
+        let payload = match variant {
+            VariantInfo::Unit(variant) => {
+                return Schema {
+                    constant: Some(variant.name()),
+                    ..Default::default()
+                };
+            }
+            VariantInfo::Tuple(variant) if variant.field_len() == 1 => {
+                self.field_schema(variant.field_at(0).unwrap().type_id())
+            }
+            VariantInfo::Tuple(variant) => {
+                self.tuple_schema(variant.iter().map(|field| field.type_id()))
+            }
+            VariantInfo::Struct(variant) => self.struct_schema(variant.iter()),
+        };
+        Schema {
+            kind: Some("object"),
+            properties: Some(vec![(variant.name(), payload)]),
+            required: Some(vec![variant.name()]),
+            additional_properties: Some(Box::new(Schema::never())),
+            ..Default::default()
+        }
+    }
+}
+
+/// A JSON Schema, with the keywords used by [`TypeSchema`].
+#[derive(Default)]
+struct Schema {
+    /// `false`, the schema nothing is valid against.
+    never: bool,
+    reference: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    kind: Option<&'static str>,
+    constant: Option<&'static str>,
+    minimum: Option<i64>,
+    properties: Option<Vec<(&'static str, Schema)>>,
+    required: Option<Vec<&'static str>>,
+    additional_properties: Option<Box<Schema>>,
+    prefix_items: Option<Vec<Schema>>,
+    items: Option<Box<Schema>>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    unique_items: bool,
+    one_of: Option<Vec<Schema>>,
+    any_of: Option<Vec<Schema>>,
+    default: Option<Box<dyn Reflect>>,
+}
+
+impl Schema {
+    fn never() -> Self {
+        Self {
+            never: true,
+            ..Default::default()
+        }
+    }
+}
+
+struct SchemaSerializer<'a> {
+    schema: &'a Schema,
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> SchemaSerializer<'a> {
+    fn child(&self, schema: &'a Schema) -> Self {
+        Self {
+            schema,
+            registry: self.registry,
+        }
+    }
+}
+
+impl<'a> Serialize for SchemaSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let schema = self.schema;
+        if schema.never {
+            return serializer.serialize_bool(false);
+        }
+
+        let mut state = serializer.serialize_map(None)?;
+        if let Some(reference) = &schema.reference {
+            state.serialize_entry("$ref", reference)?;
+        }
+        if let Some(title) = &schema.title {
+            state.serialize_entry("title", title)?;
+        }
+        if let Some(description) = &schema.description {
+            state.serialize_entry("description", description)?;
+        }
+        if let Some(kind) = schema.kind {
+            state.serialize_entry("type", kind)?;
+        }
+        if let Some(constant) = schema.constant {
+            state.serialize_entry("const", constant)?;
+        }
+        if let Some(minimum) = schema.minimum {
+            state.serialize_entry("minimum", &minimum)?;
+        }
+        if let Some(properties) = &schema.properties {
+            let properties = PropertiesSerializer {
+                properties,
+                registry: self.registry,
+            };
+            state.serialize_entry("properties", &properties)?;
+        }
+        if let Some(required) = &schema.required {
+            state.serialize_entry("required", required)?;
+        }
+        if let Some(additional_properties) = &schema.additional_properties {
+            state.serialize_entry("additionalProperties", &self.child(additional_properties))?;
+        }
+        if let Some(prefix_items) = &schema.prefix_items {
+            state.serialize_entry("prefixItems", &self.children(prefix_items))?;
+        }
+        if let Some(items) = &schema.items {
+            state.serialize_entry("items", &self.child(items))?;
+        }
+        if let Some(min_items) = schema.min_items {
+            state.serialize_entry("minItems", &min_items)?;
+        }
+        if let Some(max_items) = schema.max_items {
+            state.serialize_entry("maxItems", &max_items)?;
+        }
+        if schema.unique_items {
+            state.serialize_entry("uniqueItems", &true)?;
+        }
+        if let Some(one_of) = &schema.one_of {
+            state.serialize_entry("oneOf", &self.children(one_of))?;
+        }
+        if let Some(any_of) = &schema.any_of {
+            state.serialize_entry("anyOf", &self.children(any_of))?;
+        }
+        if let Some(default) = &schema.default {
+            state.serialize_entry(
+                "default",
+                &TypedReflectSerializer::new(&**default, self.registry),
+            )?;
+        }
+        state.end()
+    }
+}
+
+impl<'a> SchemaSerializer<'a> {
+    fn children(&self, schemas: &'a [Schema]) -> SchemasSerializer<'a> {
+        SchemasSerializer {
+            schemas,
+            registry: self.registry,
+        }
+    }
+}
+
+struct SchemasSerializer<'a> {
+    schemas: &'a [Schema],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SchemasSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.schemas.len()))?;
+        for schema in self.schemas {
+            state.serialize_element(&SchemaSerializer {
+                schema,
+                registry: self.registry,
+            })?;
+        }
+        state.end()
+    }
+}
+
+struct PropertiesSerializer<'a> {
+    properties: &'a [(&'static str, Schema)],
+    registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for PropertiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_map(Some(self.properties.len()))?;
+        for (name, schema) in self.properties {
+            state.serialize_entry(
+                name,
+                &SchemaSerializer {
+                    schema,
+                    registry: self.registry,
+                },
+            )?;
+        }
+        state.end()
+    }
+}
+
+/// The schema of value types that are serialized as a JSON primitive.
+fn primitive_schema(type_name: &str) -> Option<Schema> {
+    let (kind, minimum) = match type_name {
+        "bool" => ("boolean", None),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => ("integer", Some(0)),
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => ("integer", None),
+        "f32" | "f64" => ("number", None),
+        "char"
+        | "alloc::string::String"
+        | "&str"
+        | "alloc::borrow::Cow<str>"
+        | "std::path::PathBuf"
+        | "std::ffi::os_str::OsString"
+        | "smol_str::SmolStr" => ("string", None),
+        _ => return None,
+    };
+    Some(Schema {
+        kind: Some(kind),
+        minimum,
+        ..Default::default()
+    })
+}
+
+/// The `$ref` to the definition of the given type in a [`TypeRegistrySchema`], as a URI
+/// fragment containing a JSON pointer.
+fn definition_reference(type_name: &str) -> String {
+    let mut reference = String::from("#/$defs/");
+    for byte in type_name.bytes() {
+        match byte {
+            b'~' => reference.push_str("~0"),
+            b'/' => reference.push_str("~1"),
+            b'a'..=b'z'
+            | b'A'..=b'Z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b':'
+            | b'@'
+            | b'!'
+            | b'$'
+            | b'&'
+            | b'\''
+            | b'('
+            | b')'
+            | b'*'
+            | b'+'
+            | b','
+            | b';'
+            | b'=' => reference.push(byte as char),
+            _ => reference.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    reference
+}
+
+/// Returns the types of the fields, items, keys and values of the given type.
+fn field_type_ids(type_info: &TypeInfo) -> Vec<TypeId> {
+    match type_info {
+        TypeInfo::Struct(info) => info.iter().map(NamedField::type_id).collect(),
+        TypeInfo::TupleStruct(info) => info.iter().map(|field| field.type_id()).collect(),
+        TypeInfo::Tuple(info) => info.iter().map(|field| field.type_id()).collect(),
+        TypeInfo::List(info) => vec![info.item_type_id()],
+        TypeInfo::Array(info) => vec![info.item_type_id()],
+        TypeInfo::Map(info) => vec![info.key_type_id(), info.value_type_id()],
+        TypeInfo::Set(info) => vec![info.value_type_id()],
+        TypeInfo::Enum(info) => info
+            .iter()
+            .flat_map(|variant| match variant {
+                VariantInfo::Struct(variant) => {
+                    variant.iter().map(NamedField::type_id).collect::<Vec<_>>()
+                }
+                VariantInfo::Tuple(variant) => {
+                    variant.iter().map(|field| field.type_id()).collect()
+                }
+                VariantInfo::Unit(_) => Vec::new(),
+            })
+            .collect(),
+        TypeInfo::Value(_) => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_reflect;
+    use crate::serde::{TypeRegistrySchema, TypedReflectDeserializer, JSON_SCHEMA_DIALECT};
+    use crate::{std_traits::ReflectDefault, Reflect, TypeRegistry};
+    use bevy_utils::HashMap;
+    use serde::de::DeserializeSeed;
+    use serde_json::json;
+    use std::any::TypeId;
+
+    #[derive(Reflect, Default)]
+    #[reflect(Default)]
+    struct Player {
+        name: String,
+        health: u32,
+        position: Position,
+        state: State,
+        inventory: Vec<Item>,
+        tags: HashMap<String, f32>,
+        target: Option<u8>,
+        #[reflect(skip_serializing)]
+        cached: bool,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Position(f32, f32);
+
+    #[derive(Reflect, Default)]
+    enum State {
+        #[default]
+        Idle,
+        Walking(f32),
+        Attacking {
+            target: u64,
+        },
+    }
+
+    #[derive(Reflect)]
+    struct Item;
+
+    #[derive(Reflect)]
+    struct Spawn {
+        x: f32,
+        y: f32,
+    }
+
+    fn get_registry() -> TypeRegistry {
+        let mut registry = TypeRegistry::default();
+        registry.register::<Player>();
+        registry.register::<Position>();
+        registry.register::<State>();
+        registry.register::<Item>();
+        registry.register::<Vec<Item>>();
+        registry.register::<HashMap<String, f32>>();
+        registry.register::<Option<u8>>();
+        registry.register::<String>();
+        registry.register::<u32>();
+        registry.register::<f32>();
+        registry.register::<u8>();
+        registry.register::<u64>();
+        registry
+    }
+
+    #[test]
+    fn should_describe_registered_types() {
+        let registry = get_registry();
+        let schema = TypeRegistrySchema::new(&registry)
+            .with_filter(|registration| registration.type_name().ends_with("Player"));
+        let schema = serde_json::to_value(&schema).unwrap();
+
+        assert_eq!(schema["$schema"], json!(JSON_SCHEMA_DIALECT));
+        let defs = &schema["$defs"];
+        let names = defs.as_object().unwrap().keys().collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec![
+                "alloc::string::String",
+                "alloc::vec::Vec<bevy_reflect::serde::schema::tests::Item>",
+                "bevy_reflect::serde::schema::tests::Item",
+                "bevy_reflect::serde::schema::tests::Player",
+                "bevy_reflect::serde::schema::tests::Position",
+                "bevy_reflect::serde::schema::tests::State",
+                "bool",
+                "core::option::Option<u8>",
+                "f32",
+                "hashbrown::map::HashMap<alloc::string::String, f32>",
+                "u32",
+                "u64",
+                "u8",
+            ]
+        );
+
+        let player = &defs["bevy_reflect::serde::schema::tests::Player"];
+        assert_eq!(player["type"], json!("object"));
+        assert_eq!(player["additionalProperties"], json!(false));
+        assert!(player.get("required").is_none());
+        let properties = &player["properties"];
+        assert_eq!(
+            properties["health"],
+            json!({ "type": "integer", "minimum": 0, "default": 0 })
+        );
+        assert_eq!(
+            properties["name"],
+            json!({ "type": "string", "default": "" })
+        );
+        assert_eq!(
+            properties["position"],
+            json!({
+                "$ref": "#/$defs/bevy_reflect::serde::schema::tests::Position",
+                "default": [0.0, 0.0],
+            })
+        );
+        assert_eq!(
+            properties["inventory"]["$ref"],
+            json!("#/$defs/alloc::vec::Vec%3Cbevy_reflect::serde::schema::tests::Item%3E")
+        );
+        assert_eq!(properties["target"]["default"], json!(null));
+        assert!(properties.get("cached").is_none());
+        assert_eq!(player["default"]["state"], json!("Idle"));
+
+        assert_eq!(
+            defs["bevy_reflect::serde::schema::tests::Position"],
+            json!({
+                "title": "bevy_reflect::serde::schema::tests::Position",
+                "type": "array",
+                "prefixItems": [{ "type": "number" }, { "type": "number" }],
+                "items": false,
+                "minItems": 2,
+            })
+        );
+        assert_eq!(
+            defs["bevy_reflect::serde::schema::tests::State"]["oneOf"],
+            json!([
+                { "const": "Idle" },
+                {
+                    "type": "object",
+                    "properties": { "Walking": { "type": "number" } },
+                    "required": ["Walking"],
+                    "additionalProperties": false,
+                },
+                {
+                    "type": "object",
+                    "properties": {
+                        "Attacking": {
+                            "type": "object",
+                            "properties": { "target": { "type": "integer", "minimum": 0 } },
+                            "required": ["target"],
+                            "additionalProperties": false,
+                        },
+                    },
+                    "required": ["Attacking"],
+                    "additionalProperties": false,
+                },
+            ])
+        );
+        assert_eq!(
+            defs["alloc::vec::Vec<bevy_reflect::serde::schema::tests::Item>"]["items"],
+            json!({ "$ref": "#/$defs/bevy_reflect::serde::schema::tests::Item" })
+        );
+        assert_eq!(
+            defs["hashbrown::map::HashMap<alloc::string::String, f32>"]["additionalProperties"],
+            json!({ "type": "number" })
+        );
+        assert_eq!(
+            defs["core::option::Option<u8>"]["anyOf"],
+            json!([{ "type": "null" }, { "type": "integer", "minimum": 0 }])
+        );
+    }
+
+    #[test]
+    fn should_only_require_fields_without_defaults() {
+        let mut registry = get_registry();
+        registry.register::<Spawn>();
+        let schema = serde_json::to_value(TypeRegistrySchema::new(&registry)).unwrap();
+        let defs = &schema["$defs"];
+        let player = &defs["bevy_reflect::serde::schema::tests::Player"];
+        assert!(player.get("required").is_none());
+        assert_eq!(
+            defs["bevy_reflect::serde::schema::tests::Spawn"]["required"],
+            json!(["x", "y"])
+        );
+
+        // a partial player is valid against its schema, and is deserialized as such
+        let partial = json!({ "name": "bob", "health": 5 });
+        let properties = player["properties"].as_object().unwrap();
+        assert!(partial
+            .as_object()
+            .unwrap()
+            .keys()
+            .all(|name| properties.contains_key(name)));
+        let registration = registry.get(TypeId::of::<Player>()).unwrap();
+        let value = TypedReflectDeserializer::new(registration, &registry)
+            .deserialize(partial)
+            .unwrap();
+        let mut deserialized = Player::default();
+        deserialized.apply(&*value);
+        assert_eq!(
+            (deserialized.name.as_str(), deserialized.health),
+            ("bob", 5)
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    Array, Enum, List, Map, Reflect, ReflectRef, ReflectSerialize, Struct, Tuple, TupleStruct,
+    Array, Enum, List, Map, Reflect, ReflectRef, ReflectSerialize, Set, Struct, Tuple, TupleStruct,
     TypeInfo, TypeRegistry, VariantInfo, VariantType,
 };
 use serde::ser::{
@@ -130,6 +130,11 @@ impl<'a> Serialize for TypedReflectSerializer<'a> {
                 registry: self.registry,
             }
             .serialize(serializer),
+            ReflectRef::Set(value) => SetSerializer {
+                set: value,
+                registry: self.registry,
+            }
+            .serialize(serializer),
             ReflectRef::Enum(value) => EnumSerializer {
                 enum_value: value,
                 registry: self.registry,
@@ -413,6 +418,24 @@ impl<'a> Serialize for MapSerializer<'a> {
     }
 }
 
+pub struct SetSerializer<'a> {
+    pub set: &'a dyn Set,
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a> Serialize for SetSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.set.len()))?;
+        for value in self.set.iter() {
+            state.serialize_element(&TypedReflectSerializer::new(value, self.registry))?;
+        }
+        state.end()
+    }
+}
+
 pub struct ListSerializer<'a> {
     pub list: &'a dyn List,
     pub registry: &'a TypeRegistry,
@@ -452,7 +475,7 @@ impl<'a> Serialize for ArraySerializer<'a> {
 #[cfg(test)]
 mod tests {
     use crate as bevy_reflect;
-    use crate::serde::ReflectSerializer;
+    use crate::serde::{ReflectSerializer, TypedReflectSerializer};
     use crate::{Reflect, ReflectSerialize, TypeRegistry};
     use bevy_utils::HashMap;
     use ron::extensions::Extensions;
@@ -699,6 +722,26 @@ mod tests {
         assert_eq!(expected, output);
     }
 
+    #[test]
+    fn should_serialize_ordered_collections() {
+        #[derive(Reflect)]
+        struct Collections {
+            ids: std::collections::BTreeSet<i32>,
+            scores: std::collections::BTreeMap<String, i32>,
+        }
+
+        let value = Collections {
+            ids: [3, 1, 2].into(),
+            scores: [("y".to_string(), 2), ("x".to_string(), 1)].into(),
+        };
+
+        let registry = get_registry();
+        let serializer = TypedReflectSerializer::new(&value, &registry);
+        let output = ron::ser::to_string(&serializer).unwrap();
+        let expected = r#"(ids:[1,2,3],scores:{"x":1,"y":2})"#;
+        assert_eq!(expected, output);
+    }
+
     #[test]
     fn enum_should_serialize() {
         #[derive(Reflect)]
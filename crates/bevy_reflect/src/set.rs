@@ -0,0 +1,528 @@
+use std::any::{Any, TypeId};
+use std::fmt::{Debug, Formatter};
+
+use bevy_reflect_derive::impl_type_path;
+use bevy_utils::HashMap;
+
+use crate::{self as bevy_reflect, Reflect, ReflectMut, ReflectOwned, ReflectRef, TypeInfo};
+
+/// A trait used to power [set-like] operations via [reflection].
+///
+/// Sets contain zero or more unique values,
+/// and correspond to types like [`HashSet`] and [`BTreeSet`].
+/// The order of these values is not guaranteed by this trait.
+///
+/// # Hashing
+///
+/// Values are expected to return a valid hash value from [`Reflect::reflect_hash`].
+/// If using the [`#[derive(Reflect)]`](derive@crate::Reflect) macro, this can be done by adding `#[reflect(Hash)]`
+/// to the entire struct or enum.
+/// [`DynamicSet`] also accepts values that can't be hashed, such as the [`DynamicStruct`] values
+/// produced by deserialization, and compares them with [`Reflect::reflect_partial_eq`] instead.
+///
+/// [`DynamicStruct`]: crate::DynamicStruct
+///
+/// # Example
+///
+/// ```
+/// use bevy_reflect::{Reflect, Set};
+/// use bevy_utils::HashSet;
+///
+///
+/// let foo: &mut dyn Set = &mut HashSet::<u32>::new();
+/// foo.insert_boxed(Box::new(123_u32));
+/// assert_eq!(foo.len(), 1);
+///
+/// let value: &dyn Reflect = foo.get(&123_u32).unwrap();
+/// assert_eq!(value.downcast_ref::<u32>(), Some(&123));
+/// ```
+///
+/// [set-like]: https://doc.rust-lang.org/std/collections/struct.HashSet.html
+/// [reflection]: crate
+/// [`HashSet`]: bevy_utils::HashSet
+/// [`BTreeSet`]: std::collections::BTreeSet
+pub trait Set: Reflect {
+    /// Returns a reference to the value equal to the given one.
+    ///
+    /// If the set doesn't contain such a value, returns `None`.
+    fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect>;
+
+    /// Returns the number of values in the set.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the set contains no values.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the values of the set.
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Reflect> + '_>;
+
+    /// Drain the values of this set to get a vector of owned values.
+    fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>>;
+
+    /// Clones the set, producing a [`DynamicSet`].
+    fn clone_dynamic(&self) -> DynamicSet;
+
+    /// Inserts a value into the set.
+    ///
+    /// Returns `true` if the set did not contain the value.
+    fn insert_boxed(&mut self, value: Box<dyn Reflect>) -> bool;
+
+    /// Removes a value from the set.
+    ///
+    /// Returns `true` if the set contained the value.
+    fn remove(&mut self, value: &dyn Reflect) -> bool;
+
+    /// Returns `true` if the set contains the given value.
+    fn contains(&self, value: &dyn Reflect) -> bool {
+        self.get(value).is_some()
+    }
+}
+
+/// A container for compile-time set info.
+#[derive(Clone, Debug)]
+pub struct SetInfo {
+    type_name: &'static str,
+    type_id: TypeId,
+    value_type_name: &'static str,
+    value_type_id: TypeId,
+    #[cfg(feature = "documentation")]
+    docs: Option<&'static str>,
+}
+
+impl SetInfo {
+    /// Create a new [`SetInfo`].
+    pub fn new<TSet: Set, TValue: Reflect>() -> Self {
+        Self {
+            type_name: std::any::type_name::<TSet>(),
+            type_id: TypeId::of::<TSet>(),
+            value_type_name: std::any::type_name::<TValue>(),
+            value_type_id: TypeId::of::<TValue>(),
+            #[cfg(feature = "documentation")]
+            docs: None,
+        }
+    }
+
+    /// Sets the docstring for this set.
+    #[cfg(feature = "documentation")]
+    pub fn with_docs(self, docs: Option<&'static str>) -> Self {
+        Self { docs, ..self }
+    }
+
+    /// The [type name] of the set.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The [`TypeId`] of the set.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Check if the given type matches the set type.
+    pub fn is<T: Any>(&self) -> bool {
+        TypeId::of::<T>() == self.type_id
+    }
+
+    /// The [type name] of the value.
+    ///
+    /// [type name]: std::any::type_name
+    pub fn value_type_name(&self) -> &'static str {
+        self.value_type_name
+    }
+
+    /// The [`TypeId`] of the value.
+    pub fn value_type_id(&self) -> TypeId {
+        self.value_type_id
+    }
+
+    /// Check if the given type matches the value type.
+    pub fn value_is<T: Any>(&self) -> bool {
+        TypeId::of::<T>() == self.value_type_id
+    }
+
+    /// The docstring of this set, if any.
+    #[cfg(feature = "documentation")]
+    pub fn docs(&self) -> Option<&'static str> {
+        self.docs
+    }
+}
+
+/// An ordered set of reflected values.
+///
+/// Values are looked up by their [`Reflect::reflect_hash`], then compared with
+/// [`Reflect::reflect_partial_eq`]. Values that can't be hashed are only compared with
+/// `reflect_partial_eq`, and values that can be neither hashed nor compared are never equal to
+/// another value.
+#[derive(Default)]
+pub struct DynamicSet {
+    represented_type: Option<&'static TypeInfo>,
+    values: Vec<Box<dyn Reflect>>,
+    /// The indices of the values by hash, `None` for the values that can't be hashed.
+    indices: HashMap<Option<u64>, Vec<usize>>,
+}
+
+impl DynamicSet {
+    /// Sets the [type] to be represented by this `DynamicSet`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the given [type] is not a [`TypeInfo::Set`].
+    ///
+    /// [type]: TypeInfo
+    pub fn set_represented_type(&mut self, represented_type: Option<&'static TypeInfo>) {
+        if let Some(represented_type) = represented_type {
+            assert!(
+                matches!(represented_type, TypeInfo::Set(_)),
+                "expected TypeInfo::Set but received: {:?}",
+                represented_type
+            );
+        }
+
+        self.represented_type = represented_type;
+    }
+
+    /// Inserts a typed value into the set.
+    pub fn insert<V: Reflect>(&mut self, value: V) {
+        self.insert_boxed(Box::new(value));
+    }
+
+    /// Returns the index of the value equal to the given one.
+    fn index_of(&self, value: &dyn Reflect) -> Option<usize> {
+        let Some(hash) = value.reflect_hash() else {
+            return (0..self.values.len())
+                .find(|index| reflect_eq(value, &*self.values[*index]) == Some(true));
+        };
+        // values with the same hash are equal unless they can be compared and differ
+        let hashed = self.indices.get(&Some(hash)).into_iter().flatten();
+        let unhashed = self.indices.get(&None).into_iter().flatten();
+        hashed
+            .copied()
+            .find(|index| reflect_eq(value, &*self.values[*index]) != Some(false))
+            .or_else(|| {
+                unhashed
+                    .copied()
+                    .find(|index| reflect_eq(value, &*self.values[*index]) == Some(true))
+            })
+    }
+}
+
+impl Set for DynamicSet {
+    fn get(&self, value: &dyn Reflect) -> Option<&dyn Reflect> {
+        self.index_of(value).map(|index| &*self.values[index])
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &dyn Reflect> + '_> {
+        Box::new(self.values.iter().map(|value| &**value))
+    }
+
+    fn drain(self: Box<Self>) -> Vec<Box<dyn Reflect>> {
+        self.values
+    }
+
+    fn clone_dynamic(&self) -> DynamicSet {
+        DynamicSet {
+            represented_type: self.represented_type,
+            values: self
+                .values
+                .iter()
+                .map(|value| value.clone_value())
+                .collect(),
+            indices: self.indices.clone(),
+        }
+    }
+
+    fn insert_boxed(&mut self, value: Box<dyn Reflect>) -> bool {
+        if self.index_of(&*value).is_some() {
+            return false;
+        }
+        self.indices
+            .entry(value.reflect_hash())
+            .or_default()
+            .push(self.values.len());
+        self.values.push(value);
+        true
+    }
+
+    fn remove(&mut self, value: &dyn Reflect) -> bool {
+        let Some(index) = self.index_of(value) else {
+            return false;
+        };
+        self.values.remove(index);
+        self.indices.retain(|_, indices| {
+            indices.retain(|other_index| *other_index != index);
+            for other_index in indices.iter_mut() {
+                if *other_index > index {
+                    *other_index -= 1;
+                }
+            }
+            !indices.is_empty()
+        });
+        true
+    }
+}
+
+impl Reflect for DynamicSet {
+    fn type_name(&self) -> &str {
+        self.represented_type
+            .map(|info| info.type_name())
+            .unwrap_or_else(|| std::any::type_name::<Self>())
+    }
+
+    #[inline]
+    fn get_represented_type_info(&self) -> Option<&'static TypeInfo> {
+        self.represented_type
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    #[inline]
+    fn into_reflect(self: Box<Self>) -> Box<dyn Reflect> {
+        self
+    }
+
+    #[inline]
+    fn as_reflect(&self) -> &dyn Reflect {
+        self
+    }
+
+    #[inline]
+    fn as_reflect_mut(&mut self) -> &mut dyn Reflect {
+        self
+    }
+
+    fn apply(&mut self, value: &dyn Reflect) {
+        set_apply(self, value);
+    }
+
+    fn set(&mut self, value: Box<dyn Reflect>) -> Result<(), Box<dyn Reflect>> {
+        *self = value.take()?;
+        Ok(())
+    }
+
+    fn reflect_ref(&self) -> ReflectRef {
+        ReflectRef::Set(self)
+    }
+
+    fn reflect_mut(&mut self) -> ReflectMut {
+        ReflectMut::Set(self)
+    }
+
+    fn reflect_owned(self: Box<Self>) -> ReflectOwned {
+        ReflectOwned::Set(self)
+    }
+
+    fn clone_value(&self) -> Box<dyn Reflect> {
+        Box::new(self.clone_dynamic())
+    }
+
+    fn reflect_partial_eq(&self, value: &dyn Reflect) -> Option<bool> {
+        set_partial_eq(self, value)
+    }
+
+    fn debug(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DynamicSet(")?;
+        set_debug(self, f)?;
+        write!(f, ")")
+    }
+
+    #[inline]
+    fn is_dynamic(&self) -> bool {
+        true
+    }
+}
+
+impl_type_path!((in bevy_reflect) DynamicSet);
+
+impl Debug for DynamicSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.debug(f)
+    }
+}
+
+impl IntoIterator for DynamicSet {
+    type Item = Box<dyn Reflect>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
+
+/// Compares two values with [`Reflect::reflect_partial_eq`] both ways, since a concrete value may
+/// not consider itself equal to the equivalent dynamic value.
+fn reflect_eq(a: &dyn Reflect, b: &dyn Reflect) -> Option<bool> {
+    let (a_eq_b, b_eq_a) = (a.reflect_partial_eq(b), b.reflect_partial_eq(a));
+    if a_eq_b == Some(true) || b_eq_a == Some(true) {
+        Some(true)
+    } else {
+        a_eq_b.or(b_eq_a)
+    }
+}
+
+/// Compares a [`Set`] with a [`Reflect`] value.
+///
+/// Returns true if and only if all of the following are true:
+/// - `b` is a set;
+/// - `b` is the same length as `a`;
+/// - For each value in `a`, `b` contains a value for which [`Reflect::reflect_partial_eq`]
+///   returns `Some(true)`.
+///
+/// Returns [`None`] if the comparison couldn't even be performed.
+#[inline]
+pub fn set_partial_eq<S: Set>(a: &S, b: &dyn Reflect) -> Option<bool> {
+    let ReflectRef::Set(set) = b.reflect_ref() else {
+        return Some(false);
+    };
+
+    if a.len() != set.len() {
+        return Some(false);
+    }
+
+    for value in a.iter() {
+        if let Some(set_value) = set.get(value) {
+            let eq_result = value.reflect_partial_eq(set_value);
+            if let failed @ (Some(false) | None) = eq_result {
+                return failed;
+            }
+        } else {
+            return Some(false);
+        }
+    }
+
+    Some(true)
+}
+
+/// The default debug formatter for [`Set`] types.
+///
+/// # Example
+/// ```
+/// # use bevy_utils::HashSet;
+/// use bevy_reflect::Reflect;
+///
+/// let mut my_set = HashSet::new();
+/// my_set.insert(String::from("Hello"));
+/// println!("{:#?}", &my_set as &dyn Reflect);
+///
+/// // Output:
+///
+/// // {
+/// //   "Hello",
+/// // }
+/// ```
+#[inline]
+pub fn set_debug(dyn_set: &dyn Set, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let mut debug = f.debug_set();
+    for value in dyn_set.iter() {
+        debug.entry(&value as &dyn Debug);
+    }
+    debug.finish()
+}
+
+/// Applies the values of reflected set `b` to set `a`.
+///
+/// The values from `b` that `a` doesn't contain are cloned and inserted, values of `a` aren't
+/// removed.
+///
+/// # Panics
+///
+/// This function panics if `b` is not a reflected set.
+#[inline]
+pub fn set_apply<S: Set>(a: &mut S, b: &dyn Reflect) {
+    if let ReflectRef::Set(set_value) = b.reflect_ref() {
+        for value in set_value.iter() {
+            if !a.contains(value) {
+                a.insert_boxed(value.clone_value());
+            }
+        }
+    } else {
+        panic!("Attempted to apply a non-set type to a set type.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DynamicSet;
+    use super::Set;
+    use crate::{self as bevy_reflect, Reflect};
+
+    #[test]
+    fn test_into_iter() {
+        let expected = ["foo", "bar", "baz"];
+
+        let mut set = DynamicSet::default();
+        set.insert(expected[0].to_string());
+        set.insert(expected[1].to_string());
+        set.insert(expected[2].to_string());
+
+        for (index, value) in set.into_iter().enumerate() {
+            let value = value.take::<String>().expect("couldn't downcast to String");
+            assert_eq!(expected[index], value);
+        }
+    }
+
+    #[test]
+    fn test_set_insert_and_remove() {
+        let mut set = DynamicSet::default();
+        assert!(set.insert_boxed(Box::new(1usize)));
+        assert!(set.insert_boxed(Box::new(2usize)));
+        assert!(set.insert_boxed(Box::new(3usize)));
+        assert!(!set.insert_boxed(Box::new(2usize)));
+        assert_eq!(set.len(), 3);
+
+        assert!(set.remove(&1usize as &dyn Reflect));
+        assert!(!set.remove(&1usize as &dyn Reflect));
+        assert!(!set.contains(&1usize as &dyn Reflect));
+        // the values after the removed one can still be found
+        assert_eq!(
+            set.get(&3usize as &dyn Reflect)
+                .and_then(|value| value.downcast_ref::<usize>()),
+            Some(&3)
+        );
+        let values = set
+            .iter()
+            .map(|value| *value.downcast_ref::<usize>().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_set_values_without_hash() {
+        #[derive(Reflect, Clone, PartialEq, Hash)]
+        #[reflect(Hash, PartialEq)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let mut set = DynamicSet::default();
+        assert!(set.insert_boxed(Point { x: 1, y: 2 }.clone_value()));
+        assert!(set.insert_boxed(Point { x: 3, y: 4 }.clone_value()));
+        assert!(!set.insert_boxed(Point { x: 1, y: 2 }.clone_value()));
+        assert_eq!(set.len(), 2);
+
+        // the dynamic values can't be hashed, but are found from hashable values
+        assert!(set.contains(&Point { x: 3, y: 4 }));
+        assert!(!set.insert_boxed(Box::new(Point { x: 3, y: 4 })));
+        assert!(set.remove(&Point { x: 1, y: 2 }));
+        assert!(!set.contains(&Point { x: 1, y: 2 }));
+        assert_eq!(set.len(), 1);
+    }
+}
@@ -2,11 +2,12 @@ use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::prelude::{FromWorld, World};
 use bevy_utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -17,6 +18,59 @@ pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
 }
 
+/// Settings of the [`ImageTextureLoader`], read from the meta file of an image or given to
+/// `AssetServer::load_with_settings`.
+///
+/// For instance, `pixel_art.png.meta` could contain:
+///
+/// ```ron
+/// (
+///     settings: Some((
+///         is_srgb: false,
+///         sampler: Nearest,
+///     )),
+/// )
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct ImageLoaderSettings {
+    /// Whether the image is in the sRGB color space.
+    pub is_srgb: bool,
+    /// The sampler of the image.
+    pub sampler: ImageSamplerSettings,
+}
+
+impl Default for ImageLoaderSettings {
+    fn default() -> Self {
+        Self {
+            is_srgb: true,
+            sampler: ImageSamplerSettings::Default,
+        }
+    }
+}
+
+/// The [`ImageSampler`] of an image loaded with [`ImageLoaderSettings`].
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageSamplerSettings {
+    /// See [`ImageSampler::Default`].
+    #[default]
+    Default,
+    /// See [`ImageSampler::linear`].
+    Linear,
+    /// See [`ImageSampler::nearest`].
+    Nearest,
+}
+
+impl From<ImageSamplerSettings> for ImageSampler {
+    fn from(settings: ImageSamplerSettings) -> Self {
+        match settings {
+            ImageSamplerSettings::Default => ImageSampler::Default,
+            ImageSamplerSettings::Linear => ImageSampler::linear(),
+            ImageSamplerSettings::Nearest => ImageSampler::nearest(),
+        }
+    }
+}
+
 pub(crate) const IMG_FILE_EXTENSIONS: &[&str] = &[
     #[cfg(feature = "basis-universal")]
     "basis",
@@ -53,19 +107,22 @@ impl AssetLoader for ImageTextureLoader {
         load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<()>> {
         Box::pin(async move {
+            let settings: ImageLoaderSettings = load_context.settings()?;
+
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
-                true,
+                settings.is_srgb,
             )
             .map_err(|err| FileTextureError {
                 error: err,
                 path: format!("{}", load_context.path().display()),
             })?;
+            dyn_img.sampler_descriptor = settings.sampler.into();
 
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
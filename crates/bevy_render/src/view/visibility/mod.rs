@@ -4,7 +4,7 @@ pub use render_layers::*;
 
 use bevy_app::{Plugin, PostUpdate};
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{prelude::*, schedule::StateScopedActions};
 use bevy_hierarchy::{Children, Parent};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_transform::{components::GlobalTransform, TransformSystem};
@@ -210,6 +210,13 @@ impl Plugin for VisibilityPlugin {
     fn build(&self, app: &mut bevy_app::App) {
         use VisibilitySystems::*;
 
+        // Entities scoped to a state are hidden and shown by setting their `Visibility`.
+        let mut actions = app
+            .world
+            .get_resource_or_insert_with(StateScopedActions::default);
+        actions.hide = hide_state_scoped_entity;
+        actions.show = show_state_scoped_entity;
+
         app
             // We add an AABB component in CalculateBounds, which must be ready on the same frame.
             .add_systems(PostUpdate, apply_deferred.in_set(CalculateBoundsFlush))
@@ -253,6 +260,39 @@ impl Plugin for VisibilityPlugin {
     }
 }
 
+/// The [`Visibility`] an entity had before it was hidden because its
+/// [`StateScoped`](bevy_ecs::schedule::StateScoped) state was exited.
+#[derive(Component)]
+struct StateScopedHidden(Option<Visibility>);
+
+/// Hides an entity whose [`StateScoped`](bevy_ecs::schedule::StateScoped) state was exited.
+fn hide_state_scoped_entity(world: &mut World, entity: Entity) {
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+    if !entity.contains::<StateScopedHidden>() {
+        let visibility = entity.get::<Visibility>().copied();
+        entity.insert(StateScopedHidden(visibility));
+    }
+    entity.insert(Visibility::Hidden);
+}
+
+/// Restores the [`Visibility`] of an entity hidden by [`hide_state_scoped_entity`].
+fn show_state_scoped_entity(world: &mut World, entity: Entity) {
+    let Some(mut entity) = world.get_entity_mut(entity) else {
+        return;
+    };
+    match entity.take::<StateScopedHidden>() {
+        Some(StateScopedHidden(Some(visibility))) => {
+            entity.insert(visibility);
+        }
+        Some(StateScopedHidden(None)) => {
+            entity.remove::<Visibility>();
+        }
+        None => {}
+    }
+}
+
 pub fn calculate_bounds(
     mut commands: Commands,
     meshes: Res<Assets<Mesh>>,
@@ -648,6 +688,35 @@ mod test {
         assert!(!is_visible(root3), "a hidden root is hidden");
     }
 
+    #[test]
+    fn state_scoped_visibility_is_restored() {
+        let mut world = World::new();
+        let visible = world.spawn(Visibility::Visible).id();
+        let hidden = world.spawn(Visibility::Hidden).id();
+        let without = world.spawn_empty().id();
+        let entities = [visible, hidden, without];
+
+        // entering the state for the first time doesn't change anything
+        for entity in entities {
+            show_state_scoped_entity(&mut world, entity);
+        }
+        assert_eq!(world.get::<Visibility>(visible), Some(&Visibility::Visible));
+
+        for _ in 0..2 {
+            for entity in entities {
+                hide_state_scoped_entity(&mut world, entity);
+                assert_eq!(world.get::<Visibility>(entity), Some(&Visibility::Hidden));
+            }
+        }
+        for entity in entities {
+            show_state_scoped_entity(&mut world, entity);
+        }
+        assert_eq!(world.get::<Visibility>(visible), Some(&Visibility::Visible));
+        assert_eq!(world.get::<Visibility>(hidden), Some(&Visibility::Hidden));
+        assert_eq!(world.get::<Visibility>(without), None);
+        assert!(!world.entity(visible).contains::<StateScopedHidden>());
+    }
+
     #[test]
     fn ensure_visibility_enum_size() {
         use std::mem;
@@ -167,6 +167,18 @@ impl DynamicScene {
     pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
         serialize_ron(SceneSerializer::new(self, registry))
     }
+
+    // TODO: move to AssetSaver when it is implemented
+    /// Serialize this dynamic scene into the compact binary scene format, as read by
+    /// [`BinarySceneLoader`](crate::BinarySceneLoader).
+    ///
+    /// This is much smaller and faster to load than [`serialize_ron`](Self::serialize_ron),
+    /// but it is not human readable and has to be read with the same type registrations it
+    /// was written with.
+    #[cfg(feature = "serialize")]
+    pub fn serialize_binary(&self, registry: &TypeRegistryArc) -> Result<Vec<u8>, postcard::Error> {
+        serialize_binary(SceneSerializer::new(self, registry))
+    }
 }
 
 /// Serialize a given Rust data structure into rust object notation (ron).
@@ -181,6 +193,24 @@ where
     ron::ser::to_string_pretty(&serialize, pretty_config)
 }
 
+/// Serialize a given Rust data structure into the binary scene format.
+///
+/// The data is encoded with [`postcard`], prefixed by [`BINARY_SCENE_MAGIC`].
+#[cfg(feature = "serialize")]
+pub fn serialize_binary<S>(serialize: S) -> Result<Vec<u8>, postcard::Error>
+where
+    S: Serialize,
+{
+    postcard::to_extend(&serialize, BINARY_SCENE_MAGIC.to_vec())
+}
+
+/// The bytes every file in the binary scene format starts with.
+///
+/// The last byte is the version of the format, and is bumped whenever the layout written by
+/// [`SceneSerializer`] changes.
+#[cfg(feature = "serialize")]
+pub const BINARY_SCENE_MAGIC: [u8; 4] = *b"BSN\x01";
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{entity::EntityMap, reflect::AppTypeRegistry, system::Command, world::World};
@@ -1,4 +1,6 @@
-use crate::{DynamicEntity, DynamicScene, SceneFilter};
+use crate::{
+    persistent_id::map_scene_entities, DynamicEntity, DynamicScene, PersistentId, SceneFilter,
+};
 use bevy_ecs::component::{Component, ComponentId};
 use bevy_ecs::system::Resource;
 use bevy_ecs::{
@@ -7,7 +9,7 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_reflect::Reflect;
-use bevy_utils::default;
+use bevy_utils::{default, HashMap};
 use std::collections::BTreeMap;
 
 /// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities and resources.
@@ -36,6 +38,14 @@ use std::collections::BTreeMap;
 /// This means that inserting `Entity(1v0)` then `Entity(0v0)` will always result in the entities
 /// being ordered as `[Entity(0v0), Entity(1v0)]`.
 ///
+/// # Persistent Ids
+///
+/// Entities with a [`PersistentId`] are stored under the id returned by
+/// [`PersistentId::scene_entity`] instead of their current [`Entity`], and references to them in
+/// components that reflect [`MapEntities`](bevy_ecs::entity::MapEntities) are updated to match.
+/// All entities are then ordered by their id in the scene, so saving the same entities always
+/// produces the same scene, regardless of the order they were spawned in.
+///
 /// # Example
 /// ```
 /// # use bevy_scene::DynamicSceneBuilder;
@@ -175,9 +185,27 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// To make sure the dynamic scene doesn't contain entities without any components, call
     /// [`Self::remove_empty_entities`] before building the scene.
     pub fn build(self) -> DynamicScene {
+        let scene_entities = self
+            .extracted_scene
+            .iter()
+            .filter(|(entity, scene_entity)| **entity != scene_entity.entity)
+            .map(|(entity, scene_entity)| (*entity, scene_entity.entity))
+            .collect::<HashMap<_, _>>();
+        let mut entities = self.extracted_scene.into_values().collect::<Vec<_>>();
+        if !scene_entities.is_empty() {
+            let type_registry = self.original_world.resource::<AppTypeRegistry>().read();
+            map_scene_entities(
+                &mut entities,
+                &scene_entities,
+                self.original_world,
+                &type_registry,
+            );
+            entities.sort_by_key(|entity| entity.entity);
+        }
+
         DynamicScene {
             resources: self.extracted_resources.into_values().collect(),
-            entities: self.extracted_scene.into_values().collect(),
+            entities,
         }
     }
 
@@ -239,12 +267,14 @@ impl<'w> DynamicSceneBuilder<'w> {
                 continue;
             }
 
+            let original_entity = self.original_world.entity(entity);
             let mut entry = DynamicEntity {
-                entity,
+                entity: original_entity
+                    .get::<PersistentId>()
+                    .map_or(entity, PersistentId::scene_entity),
                 components: Vec::new(),
             };
 
-            let original_entity = self.original_world.entity(entity);
             for component_id in original_entity.archetype().components() {
                 let mut extract_and_push = || {
                     let type_id = self
@@ -3,9 +3,12 @@
 mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod persistent_id;
+mod prefab;
 mod scene;
 mod scene_filter;
 mod scene_loader;
+mod scene_patch;
 mod scene_spawner;
 
 #[cfg(feature = "serialize")]
@@ -14,16 +17,19 @@ pub mod serde;
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use persistent_id::*;
+pub use prefab::*;
 pub use scene::*;
 pub use scene_filter::*;
 pub use scene_loader::*;
+pub use scene_patch::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, Scene, SceneBundle, SceneFilter,
-        SceneSpawner,
+        DynamicScene, DynamicSceneBuilder, DynamicSceneBundle, PersistentId, Scene, SceneBundle,
+        SceneFilter, ScenePrefab, SceneSpawner,
     };
 }
 
@@ -38,7 +44,11 @@ impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
+            .register_type::<ScenePrefab>()
+            .register_type::<PersistentId>()
+            .register_type::<bevy_utils::Uuid>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BinarySceneLoader>()
             .init_resource::<SceneSpawner>()
             .add_systems(Update, scene_spawner_system)
             // Systems `*_bundle_spawner` must run before `scene_spawner_system`
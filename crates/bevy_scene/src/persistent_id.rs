@@ -0,0 +1,103 @@
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    prelude::Component,
+    reflect::{ReflectComponent, ReflectMapEntities},
+    world::World,
+};
+use bevy_reflect::{Reflect, TypeRegistry};
+use bevy_utils::{HashMap, Uuid};
+
+use crate::DynamicEntity;
+
+/// A persistent identifier for an entity, used as its id in the scenes it is saved to.
+///
+/// [`DynamicSceneBuilder`](crate::DynamicSceneBuilder) stores entities with this component
+/// under an id derived from it, see [`PersistentId::scene_entity`], rather than under their
+/// current [`Entity`], which depends on the order entities happen to be spawned in. References
+/// to these entities in components that reflect [`MapEntities`](bevy_ecs::entity::MapEntities)
+/// are saved with the same id, so they are mapped back to the right entities when the scene is
+/// loaded.
+///
+/// As the component is saved along with the entity, the entity keeps its id when the scene is
+/// loaded and saved again.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component, PartialEq, Hash)]
+pub struct PersistentId(pub Uuid);
+
+impl PersistentId {
+    /// Creates a new random id.
+    pub fn new() -> Self {
+        Self(Uuid::new_v4())
+    }
+
+    /// Returns the id of the entity in saved scenes.
+    ///
+    /// The highest bit of the returned id is always set, so it doesn't collide with the id of
+    /// an entity without a [`PersistentId`] in practice, as that would require its generation
+    /// to reach `2^31`.
+    ///
+    /// The 128 bits of the id are folded into 63 by XOR-ing its halves, so two different ids
+    /// can return the same entity. This is unlikely with random ids, but saving entities with
+    /// colliding ids stores them as a single entity.
+    pub fn scene_entity(&self) -> Entity {
+        let (high, low) = self.0.as_u64_pair();
+        Entity::from_bits((high ^ low) | 1 << 63)
+    }
+}
+
+/// Creates a new random id, so that components inserted through reflection are unique until
+/// they are overwritten with the saved id.
+impl Default for PersistentId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replaces the world entities of `entities` with their ids in the scene, in the components
+/// that reflect [`MapEntities`](bevy_ecs::entity::MapEntities).
+///
+/// The components are mapped with their [`ReflectMapEntities`] in a scratch world, so only the
+/// entities they declare are mapped. References to entities of `world` without a scene id are
+/// kept, and references to entities that don't exist are replaced with dead entities, as when
+/// the scene is loaded.
+pub(crate) fn map_scene_entities(
+    entities: &mut [DynamicEntity],
+    scene_entities: &HashMap<Entity, Entity>,
+    world: &World,
+    type_registry: &TypeRegistry,
+) {
+    if scene_entities.is_empty() {
+        return;
+    }
+    let mut entity_map = EntityMap::default();
+    for entity in world.iter_entities() {
+        let entity = entity.id();
+        entity_map.insert(
+            entity,
+            scene_entities.get(&entity).copied().unwrap_or(entity),
+        );
+    }
+
+    let mut scratch_world = World::new();
+    for entity in entities {
+        for component in &mut entity.components {
+            let Some(registration) = type_registry.get_with_name(component.type_name()) else {
+                continue;
+            };
+            let (Some(reflect_component), Some(map_entities)) = (
+                registration.data::<ReflectComponent>(),
+                registration.data::<ReflectMapEntities>(),
+            ) else {
+                continue;
+            };
+            let mut scratch_entity = scratch_world.spawn_empty();
+            reflect_component.insert(&mut scratch_entity, &**component);
+            let scratch_entity = scratch_entity.id();
+            map_entities.map_entities(&mut scratch_world, &mut entity_map, &[scratch_entity]);
+            if let Some(mapped) = reflect_component.reflect(scratch_world.entity(scratch_entity)) {
+                *component = mapped.clone_value();
+            }
+            scratch_world.despawn(scratch_entity);
+        }
+    }
+}
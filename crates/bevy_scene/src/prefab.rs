@@ -0,0 +1,298 @@
+use std::any::{type_name, TypeId};
+
+use crate::{DynamicEntity, DynamicScene, SceneSpawnError};
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    prelude::Component,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    world::World,
+};
+use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+use bevy_reflect::{FromReflect, Reflect};
+use bevy_utils::{HashMap, HashSet};
+
+/// Makes the entity it is on an instance of the [`DynamicScene`] at the given asset path.
+///
+/// When a scene containing this component is spawned by the [`SceneSpawner`](crate::SceneSpawner),
+/// the referenced scene is loaded and written to the entity with
+/// [`DynamicScene::write_to_entity_with`]: the entity inherits the components of the root
+/// entities of the referenced scene, and the other entities of that scene are spawned as its
+/// descendants. Components the entity already has are kept, overriding the inherited ones.
+///
+/// Referenced scenes can contain prefabs themselves, including on their root entities, which
+/// lets a prefab inherit from another one.
+///
+/// ```ron
+/// (
+///   resources: {},
+///   entities: {
+///     0: (
+///       components: {
+///         "bevy_scene::prefab::ScenePrefab": (
+///           path: "prefabs/tree.scn.ron",
+///         ),
+///         "my_game::Health": (
+///           max: 200,
+///         ),
+///       },
+///     ),
+///   },
+/// )
+/// ```
+#[derive(Component, Reflect, Default, Clone, Debug, PartialEq, Eq)]
+#[reflect(Component)]
+pub struct ScenePrefab {
+    /// The asset path of the referenced scene.
+    pub path: String,
+}
+
+impl ScenePrefab {
+    /// Creates a reference to the scene at the given asset path.
+    pub fn new(path: impl Into<String>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl DynamicScene {
+    /// Returns the prefabs referenced by this scene, as pairs of the entity with the
+    /// [`ScenePrefab`] component and the asset path of the referenced scene.
+    pub fn prefabs(&self) -> Vec<(Entity, String)> {
+        self.entities
+            .iter()
+            .flat_map(|entity| {
+                entity
+                    .components
+                    .iter()
+                    .filter(|component| component.type_name() == type_name::<ScenePrefab>())
+                    .filter_map(|component| ScenePrefab::from_reflect(&**component))
+                    .map(|prefab| (entity.entity, prefab.path))
+            })
+            .collect()
+    }
+
+    /// Returns the scene ids of the root entities of this scene, those without a [`Parent`].
+    pub fn root_entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities
+            .iter()
+            .filter(|entity| is_root(entity))
+            .map(|entity| entity.entity)
+    }
+
+    /// Write the entities of this scene to the given world as an instance of a prefab on
+    /// `entity`.
+    ///
+    /// The root entities of the scene, those without a [`Parent`], are mapped to `entity` in
+    /// `entity_map`: their components are inserted on `entity` unless it already has a
+    /// component of the same type. The other entities are spawned, and the children of the
+    /// roots become children of `entity`. The resources of the scene are not written.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered
+    /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
+    /// [`Component`](bevy_ecs::component::Component) trait.
+    pub fn write_to_entity_with(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        entity_map: &mut EntityMap,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let overrides = component_type_ids(world, entity);
+        self.write_prefab(world, entity, entity_map, type_registry, &overrides)
+    }
+
+    /// Writes the entities of this scene as an instance of a prefab on `entity`, like
+    /// [`Self::write_to_entity_with`], except that the components of the root entities are only
+    /// skipped if their type is in `overrides`.
+    ///
+    /// This lets a prefab be written again to the same entity when its scene is modified.
+    pub(crate) fn write_prefab(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        entity_map: &mut EntityMap,
+        type_registry: &AppTypeRegistry,
+        overrides: &HashSet<TypeId>,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = type_registry.read();
+
+        let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
+        let mut children = Vec::new();
+        // the first root with a component wins over the following ones
+        let mut root_components = HashSet::default();
+
+        for scene_entity in &self.entities {
+            let is_root = is_root(scene_entity);
+            let target = if is_root {
+                entity_map.insert(scene_entity.entity, entity);
+                entity
+            } else {
+                let target = *entity_map
+                    .entry(scene_entity.entity)
+                    .or_insert_with(|| world.spawn_empty().id());
+                children.push(target);
+                target
+            };
+            let entity_mut = &mut world.entity_mut(target);
+
+            for component in &scene_entity.components {
+                let registration = type_registry
+                    .get_with_name(component.type_name())
+                    .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                        type_name: component.type_name().to_string(),
+                    })?;
+                let reflect_component =
+                    registration.data::<ReflectComponent>().ok_or_else(|| {
+                        SceneSpawnError::UnregisteredComponent {
+                            type_name: component.type_name().to_string(),
+                        }
+                    })?;
+
+                if is_root {
+                    // the hierarchy of the roots is fixed below, and the components the
+                    // entity already had override the ones of the prefab
+                    if registration.type_id() == TypeId::of::<Children>()
+                        || overrides.contains(&registration.type_id())
+                        || !root_components.insert(registration.type_id())
+                    {
+                        continue;
+                    }
+                }
+
+                if registration.data::<ReflectMapEntities>().is_some() {
+                    scene_mappings
+                        .entry(registration.type_id())
+                        .or_insert(Vec::new())
+                        .push(target);
+                }
+
+                reflect_component.apply_or_insert(entity_mut, &**component);
+            }
+        }
+
+        for (type_id, entities) in scene_mappings.into_iter() {
+            let registration = type_registry.get(type_id).expect(
+                "we should be getting TypeId from this TypeRegistration in the first place",
+            );
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect.map_entities(world, entity_map, &entities);
+            }
+        }
+
+        // the children of the roots now have `entity` as their parent, but aren't in its
+        // `Children` yet
+        children.retain(|&child| world.get::<Parent>(child).map(Parent::get) == Some(entity));
+        if !children.is_empty() {
+            world.entity_mut(entity).push_children(&children);
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the types of the components of `entity`.
+pub(crate) fn component_type_ids(world: &World, entity: Entity) -> HashSet<TypeId> {
+    let Some(entity) = world.get_entity(entity) else {
+        return HashSet::default();
+    };
+    entity
+        .archetype()
+        .components()
+        .filter_map(|id| world.components().get_info(id)?.type_id())
+        .collect()
+}
+
+fn is_root(entity: &DynamicEntity) -> bool {
+    !entity
+        .components
+        .iter()
+        .any(|component| component.type_name() == type_name::<Parent>())
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        entity::EntityMap,
+        prelude::{Component, ReflectComponent, World},
+        reflect::AppTypeRegistry,
+    };
+    use bevy_hierarchy::{BuildWorldChildren, Children, Parent};
+    use bevy_reflect::Reflect;
+
+    use crate::{DynamicScene, ScenePrefab};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(i32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Speed(f32);
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Speed>();
+            registry.register::<Parent>();
+            registry.register::<Children>();
+            registry.register::<ScenePrefab>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    #[test]
+    fn prefab_references() {
+        let mut world = create_world();
+        world.spawn(Health(1));
+        let prefab = world
+            .spawn((ScenePrefab::new("prefabs/tree.scn.ron"), Health(2)))
+            .id();
+        let scene = DynamicScene::from_world(&world);
+
+        assert_eq!(
+            scene.prefabs(),
+            vec![(prefab, "prefabs/tree.scn.ron".to_string())]
+        );
+    }
+
+    #[test]
+    fn write_prefab_to_entity() {
+        let mut prefab_world = create_world();
+        let root = prefab_world.spawn((Health(100), Speed(1.0))).id();
+        let child = prefab_world.spawn(Speed(2.0)).id();
+        let grandchild = prefab_world.spawn(Health(5)).id();
+        prefab_world.entity_mut(root).add_child(child);
+        prefab_world.entity_mut(child).add_child(grandchild);
+        let prefab = DynamicScene::from_world(&prefab_world);
+        assert_eq!(prefab.root_entities().collect::<Vec<_>>(), vec![root]);
+
+        let mut world = create_world();
+        let existing_child = world.spawn_empty().id();
+        let entity = world.spawn(Health(200)).add_child(existing_child).id();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+
+        let mut entity_map = EntityMap::default();
+        prefab
+            .write_to_entity_with(&mut world, entity, &mut entity_map, &registry)
+            .unwrap();
+
+        assert_eq!(entity_map.get(root), Some(entity));
+        // the component of the entity overrides the one of the prefab
+        assert_eq!(world.get::<Health>(entity), Some(&Health(200)));
+        assert_eq!(world.get::<Speed>(entity), Some(&Speed(1.0)));
+
+        let child = entity_map.get(child).unwrap();
+        let grandchild = entity_map.get(grandchild).unwrap();
+        assert_eq!(world.get::<Parent>(child).unwrap().get(), entity);
+        assert_eq!(
+            &**world.get::<Children>(entity).unwrap(),
+            &[existing_child, child]
+        );
+        assert_eq!(world.get::<Parent>(grandchild).unwrap().get(), child);
+        assert_eq!(&**world.get::<Children>(child).unwrap(), &[grandchild]);
+        assert_eq!(world.get::<Health>(grandchild), Some(&Health(5)));
+    }
+}
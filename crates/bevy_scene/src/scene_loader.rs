@@ -1,12 +1,17 @@
 #[cfg(feature = "serialize")]
 use crate::serde::SceneDeserializer;
+#[cfg(feature = "serialize")]
+use crate::{DynamicScene, BINARY_SCENE_MAGIC};
 use anyhow::{anyhow, Result};
-use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
 use bevy_ecs::reflect::AppTypeRegistry;
 use bevy_ecs::world::{FromWorld, World};
 use bevy_reflect::TypeRegistryArc;
 use bevy_utils::BoxedFuture;
+use thiserror::Error;
 
+#[cfg(feature = "serialize")]
+use bevy_reflect::TypeRegistry;
 #[cfg(feature = "serialize")]
 use serde::de::DeserializeSeed;
 
@@ -47,7 +52,7 @@ impl AssetLoader for SceneLoader {
                         span_error.position,
                     )
                 })?;
-            load_context.set_default_asset(LoadedAsset::new(scene));
+            load_context.set_default_asset(with_prefab_dependencies(scene));
             Ok(())
         })
     }
@@ -56,3 +61,87 @@ impl AssetLoader for SceneLoader {
         &["scn", "scn.ron"]
     }
 }
+
+/// Loads [`DynamicScene`]s written with
+/// [`DynamicScene::serialize_binary`](crate::DynamicScene::serialize_binary).
+#[derive(Debug)]
+pub struct BinarySceneLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BinarySceneLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BinarySceneLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "serialize")]
+impl AssetLoader for BinarySceneLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let scene = deserialize_binary(bytes, &self.type_registry.read())
+                .map_err(|e| anyhow!("{} in {}", e, load_context.path().to_string_lossy()))?;
+            load_context.set_default_asset(with_prefab_dependencies(scene));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["scn.bin"]
+    }
+}
+
+/// Makes the scenes of the [`ScenePrefab`](crate::ScenePrefab)s of `scene` load along with it.
+#[cfg(feature = "serialize")]
+fn with_prefab_dependencies(scene: DynamicScene) -> LoadedAsset<DynamicScene> {
+    let dependencies = scene
+        .prefabs()
+        .into_iter()
+        .map(|(_, path)| AssetPath::from(path))
+        .collect();
+    LoadedAsset::new(scene).with_dependencies(dependencies)
+}
+
+/// An error that occurs when reading a scene in the binary scene format.
+#[derive(Error, Debug)]
+pub enum BinarySceneError {
+    /// The bytes don't start with [`BINARY_SCENE_MAGIC`].
+    #[error("not a binary scene")]
+    MissingMagic,
+    /// The scene was written with an unsupported version of the format, which is the last byte
+    /// of [`BINARY_SCENE_MAGIC`].
+    #[error("unsupported binary scene version {0}")]
+    UnsupportedVersion(u8),
+    /// The scene couldn't be deserialized, for instance because it contains unregistered types.
+    #[cfg(feature = "serialize")]
+    #[error("malformed binary scene: {0}")]
+    Deserialize(#[from] postcard::Error),
+}
+
+/// Reads a [`DynamicScene`] written with
+/// [`DynamicScene::serialize_binary`](crate::DynamicScene::serialize_binary).
+#[cfg(feature = "serialize")]
+pub fn deserialize_binary(
+    bytes: &[u8],
+    type_registry: &TypeRegistry,
+) -> Result<DynamicScene, BinarySceneError> {
+    let (magic, version) = BINARY_SCENE_MAGIC.split_at(BINARY_SCENE_MAGIC.len() - 1);
+    let Some(bytes) = bytes.strip_prefix(magic) else {
+        return Err(BinarySceneError::MissingMagic);
+    };
+    match bytes.split_first() {
+        Some((found, bytes)) if found == &version[0] => {
+            let mut deserializer = postcard::Deserializer::from_bytes(bytes);
+            Ok(SceneDeserializer { type_registry }.deserialize(&mut deserializer)?)
+        }
+        Some((found, _)) => Err(BinarySceneError::UnsupportedVersion(*found)),
+        None => Err(BinarySceneError::MissingMagic),
+    }
+}
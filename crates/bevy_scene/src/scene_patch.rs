@@ -0,0 +1,497 @@
+use std::any::TypeId;
+
+use crate::{DynamicEntity, DynamicScene, SceneSpawnError};
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{
+    DynamicStruct, Reflect, ReflectRef, Struct, TypeInfo, TypeRegistration, TypeRegistry,
+};
+use bevy_utils::HashMap;
+
+/// The changes between two versions of a [`DynamicScene`].
+///
+/// Writing a patch to a world only touches what changed between the two versions: fields of
+/// components and resources that kept their value in the scene are left alone, so any changes
+/// made to them at runtime are preserved. Components, resources and entities that were removed
+/// from the scene are removed from the world.
+///
+/// This is what [`SceneSpawner`](crate::SceneSpawner) uses to hot-reload scenes when its
+/// [`SceneReloadMode`](crate::SceneReloadMode) is set to `Patch`.
+#[derive(Default)]
+pub struct ScenePatch {
+    /// The resources that were added or changed.
+    pub resources: Vec<ReflectPatch>,
+    /// The type names of the resources that were removed.
+    pub removed_resources: Vec<String>,
+    /// The entities that were added or changed.
+    pub entities: Vec<EntityPatch>,
+    /// The scene ids of the entities that were removed.
+    pub removed_entities: Vec<Entity>,
+}
+
+/// The changes to a single entity of a [`DynamicScene`].
+pub struct EntityPatch {
+    /// The identifier of the entity in the scene.
+    pub entity: Entity,
+    /// The components that were added or changed.
+    pub components: Vec<ReflectPatch>,
+    /// The type names of the components that were removed.
+    pub removed_components: Vec<String>,
+}
+
+/// A component or resource that was added or changed.
+pub struct ReflectPatch {
+    /// The new value, inserted whole if the target doesn't have a value of this type yet.
+    pub value: Box<dyn Reflect>,
+    /// The fields that changed, applied with [`Reflect::apply`] if the target already has a
+    /// value of this type.
+    ///
+    /// This is `None` if the value was added to the scene, or if it can't be patched field by
+    /// field, in which case `value` is applied instead.
+    pub delta: Option<Box<dyn Reflect>>,
+}
+
+impl ScenePatch {
+    /// Computes the changes from `old` to `new`.
+    ///
+    /// Entities are matched by their [`DynamicEntity::entity`] id, components and resources by
+    /// their type name.
+    pub fn diff(old: &DynamicScene, new: &DynamicScene) -> Self {
+        let old_entities = old
+            .entities
+            .iter()
+            .map(|entity| (entity.entity, entity))
+            .collect::<HashMap<_, _>>();
+
+        let mut patch = ScenePatch {
+            resources: diff_values(&old.resources, &new.resources),
+            removed_resources: removed_values(&old.resources, &new.resources),
+            ..Default::default()
+        };
+
+        for new_entity in &new.entities {
+            let old_components = old_entities
+                .get(&new_entity.entity)
+                .map(|old_entity| old_entity.components.as_slice())
+                .unwrap_or_default();
+            let components = diff_values(old_components, &new_entity.components);
+            let removed_components = removed_values(old_components, &new_entity.components);
+            if !old_entities.contains_key(&new_entity.entity)
+                || !components.is_empty()
+                || !removed_components.is_empty()
+            {
+                patch.entities.push(EntityPatch {
+                    entity: new_entity.entity,
+                    components,
+                    removed_components,
+                });
+            }
+        }
+
+        patch.removed_entities = old
+            .entities
+            .iter()
+            .map(|entity| entity.entity)
+            .filter(|entity| !new.entities.iter().any(|new| new.entity == *entity))
+            .collect();
+
+        patch
+    }
+
+    /// Returns `true` if the two scenes this patch was computed from are the same.
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+            && self.removed_resources.is_empty()
+            && self.entities.is_empty()
+            && self.removed_entities.is_empty()
+    }
+
+    /// Write the changes of this patch to the given world.
+    ///
+    /// `entity_map` has to map the entities of the old scene to the entities they were spawned
+    /// as, as done by [`DynamicScene::write_to_world_with`]. It is updated with the entities
+    /// that were spawned or despawned.
+    ///
+    /// Components that reference other entities are always applied whole, so that all their
+    /// references can be mapped to the entities in the world again.
+    ///
+    /// This method will return a [`SceneSpawnError`] if a type either is not registered
+    /// in the provided [`AppTypeRegistry`] resource, or doesn't reflect the
+    /// [`Component`](bevy_ecs::component::Component) or [`Resource`](bevy_ecs::prelude::Resource) trait.
+    pub fn write_to_world_with(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+        type_registry: &AppTypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        let type_registry = type_registry.read();
+
+        for type_name in &self.removed_resources {
+            reflect_resource(&type_registry, type_name)?.remove(world);
+        }
+        for resource in &self.resources {
+            let reflect_resource = reflect_resource(&type_registry, resource.value.type_name())?;
+            if reflect_resource.reflect(world).is_some() {
+                reflect_resource.apply(world, resource.delta());
+            } else {
+                reflect_resource.insert(world, &*resource.value);
+            }
+        }
+
+        for scene_entity in &self.removed_entities {
+            if let Some(entity) = entity_map.remove(*scene_entity) {
+                world.despawn(entity);
+            }
+        }
+
+        let mut scene_mappings: HashMap<TypeId, Vec<Entity>> = HashMap::default();
+
+        for entity_patch in &self.entities {
+            let entity = *entity_map
+                .entry(entity_patch.entity)
+                .or_insert_with(|| world.spawn_empty().id());
+            // the entity may have been despawned at runtime
+            let Some(mut entity_mut) = world.get_entity_mut(entity) else {
+                continue;
+            };
+
+            for type_name in &entity_patch.removed_components {
+                let (_, reflect_component) = reflect_component(&type_registry, type_name)?;
+                reflect_component.remove(&mut entity_mut);
+            }
+
+            for component in &entity_patch.components {
+                let (registration, reflect_component) =
+                    reflect_component(&type_registry, component.value.type_name())?;
+
+                if registration.data::<ReflectMapEntities>().is_some() {
+                    scene_mappings
+                        .entry(registration.type_id())
+                        .or_insert(Vec::new())
+                        .push(entity);
+                    reflect_component.apply_or_insert(&mut entity_mut, &*component.value);
+                } else if entity_mut.contains_type_id(registration.type_id()) {
+                    reflect_component.apply(&mut entity_mut, component.delta());
+                } else {
+                    reflect_component.insert(&mut entity_mut, &*component.value);
+                }
+            }
+        }
+
+        for (type_id, entities) in scene_mappings.into_iter() {
+            let registration = type_registry.get(type_id).expect(
+                "we should be getting TypeId from this TypeRegistration in the first place",
+            );
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect.map_entities(world, entity_map, &entities);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the changes of this patch to the given world.
+    ///
+    /// See [`write_to_world_with`](Self::write_to_world_with), this uses the world's
+    /// [`AppTypeRegistry`] resource.
+    pub fn write_to_world(
+        &self,
+        world: &mut World,
+        entity_map: &mut EntityMap,
+    ) -> Result<(), SceneSpawnError> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        self.write_to_world_with(world, entity_map, &registry)
+    }
+}
+
+impl ReflectPatch {
+    /// The value to apply to a target that already has a value of this type.
+    pub fn delta(&self) -> &dyn Reflect {
+        self.delta.as_deref().unwrap_or(&*self.value)
+    }
+}
+
+impl DynamicScene {
+    /// Creates a copy of this scene, made of dynamic values.
+    pub fn clone_dynamic(&self) -> DynamicScene {
+        DynamicScene {
+            resources: self.resources.iter().map(|r| r.clone_value()).collect(),
+            entities: self
+                .entities
+                .iter()
+                .map(|entity| DynamicEntity {
+                    entity: entity.entity,
+                    components: entity.components.iter().map(|c| c.clone_value()).collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn reflect_resource<'a>(
+    type_registry: &'a TypeRegistry,
+    type_name: &str,
+) -> Result<&'a ReflectResource, SceneSpawnError> {
+    type_registry
+        .get_with_name(type_name)
+        .ok_or_else(|| SceneSpawnError::UnregisteredType {
+            type_name: type_name.to_string(),
+        })?
+        .data::<ReflectResource>()
+        .ok_or_else(|| SceneSpawnError::UnregisteredResource {
+            type_name: type_name.to_string(),
+        })
+}
+
+fn reflect_component<'a>(
+    type_registry: &'a TypeRegistry,
+    type_name: &str,
+) -> Result<(&'a TypeRegistration, &'a ReflectComponent), SceneSpawnError> {
+    let registration = type_registry.get_with_name(type_name).ok_or_else(|| {
+        SceneSpawnError::UnregisteredType {
+            type_name: type_name.to_string(),
+        }
+    })?;
+    let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+        SceneSpawnError::UnregisteredComponent {
+            type_name: type_name.to_string(),
+        }
+    })?;
+    Ok((registration, reflect_component))
+}
+
+fn diff_values(old: &[Box<dyn Reflect>], new: &[Box<dyn Reflect>]) -> Vec<ReflectPatch> {
+    new.iter()
+        .filter_map(
+            |new| match old.iter().find(|old| old.type_name() == new.type_name()) {
+                Some(old) => diff_value(&**old, &**new).map(|delta| ReflectPatch {
+                    value: new.clone_value(),
+                    delta: Some(delta),
+                }),
+                None => Some(ReflectPatch {
+                    value: new.clone_value(),
+                    delta: None,
+                }),
+            },
+        )
+        .collect()
+}
+
+fn removed_values(old: &[Box<dyn Reflect>], new: &[Box<dyn Reflect>]) -> Vec<String> {
+    old.iter()
+        .filter(|old| !new.iter().any(|new| new.type_name() == old.type_name()))
+        .map(|old| old.type_name().to_string())
+        .collect()
+}
+
+/// Returns the part of `new` that differs from `old`, or `None` if they are equal.
+///
+/// Structs are diffed field by field, as applying a struct only sets the fields it contains.
+/// Any other value that changed is returned whole.
+fn diff_value(old: &dyn Reflect, new: &dyn Reflect) -> Option<Box<dyn Reflect>> {
+    if let (ReflectRef::Struct(old_struct), ReflectRef::Struct(new_struct)) =
+        (old.reflect_ref(), new.reflect_ref())
+    {
+        let mut delta = DynamicStruct::default();
+        if let Some(info @ TypeInfo::Struct(_)) = new.get_represented_type_info() {
+            delta.set_represented_type(Some(info));
+        }
+        for (index, new_field) in new_struct.iter_fields().enumerate() {
+            let name = new_struct.name_at(index).unwrap();
+            let field_delta = match old_struct.field(name) {
+                Some(old_field) => diff_value(old_field, new_field),
+                None => Some(new_field.clone_value()),
+            };
+            if let Some(field_delta) = field_delta {
+                delta.insert_boxed(name, field_delta);
+            }
+        }
+        return (delta.field_len() > 0).then(|| Box::new(delta) as Box<dyn Reflect>);
+    }
+
+    match old.reflect_partial_eq(new) {
+        Some(true) => None,
+        _ => Some(new.clone_value()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{
+        entity::EntityMap,
+        prelude::{Component, ReflectComponent, ReflectResource, Resource, World},
+        reflect::AppTypeRegistry,
+    };
+    use bevy_reflect::Reflect;
+
+    use crate::{DynamicScene, DynamicSceneBuilder, ScenePatch};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health {
+        current: i32,
+        max: i32,
+    }
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[derive(Resource, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Resource)]
+    struct Gravity {
+        x: f32,
+        y: f32,
+    }
+
+    fn create_world() -> World {
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Name>();
+            registry.register::<Gravity>();
+        }
+        world.insert_resource(registry);
+        world
+    }
+
+    /// Builds a scene from the world returned by `f`, keeping the entity ids stable.
+    fn scene(f: impl FnOnce(&mut World)) -> DynamicScene {
+        let mut world = create_world();
+        f(&mut world);
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entities(world.iter_entities().map(|entity| entity.id()));
+        builder.extract_resources();
+        builder.build()
+    }
+
+    #[test]
+    fn diff_same_scene_is_empty() {
+        let make = |world: &mut World| {
+            world.spawn((
+                Health {
+                    current: 5,
+                    max: 10,
+                },
+                Name("a".into()),
+            ));
+            world.insert_resource(Gravity { x: 0.0, y: -9.8 });
+        };
+        assert!(ScenePatch::diff(&scene(make), &scene(make)).is_empty());
+    }
+
+    #[test]
+    fn patch_preserves_unchanged_fields() {
+        let old = scene(|world| {
+            world.spawn((
+                Health {
+                    current: 10,
+                    max: 10,
+                },
+                Name("a".into()),
+            ));
+            world.insert_resource(Gravity { x: 0.0, y: -9.8 });
+        });
+        let new = scene(|world| {
+            world.spawn((
+                Health {
+                    current: 10,
+                    max: 20,
+                },
+                Name("a".into()),
+            ));
+            world.insert_resource(Gravity { x: 1.0, y: -9.8 });
+        });
+
+        let patch = ScenePatch::diff(&old, &new);
+        assert_eq!(patch.entities.len(), 1);
+        assert_eq!(patch.entities[0].components.len(), 1);
+
+        let mut world = create_world();
+        let mut entity_map = EntityMap::default();
+        old.write_to_world(&mut world, &mut entity_map).unwrap();
+        let entity = entity_map.values().next().unwrap();
+
+        // modified at runtime
+        world.get_mut::<Health>(entity).unwrap().current = 3;
+        world.get_mut::<Name>(entity).unwrap().0 = "b".into();
+        world.resource_mut::<Gravity>().y = 0.0;
+
+        patch.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        assert_eq!(
+            world.get::<Health>(entity),
+            Some(&Health {
+                current: 3,
+                max: 20
+            })
+        );
+        assert_eq!(world.get::<Name>(entity), Some(&Name("b".into())));
+        assert_eq!(world.resource::<Gravity>(), &Gravity { x: 1.0, y: 0.0 });
+    }
+
+    #[test]
+    fn patch_adds_and_removes() {
+        let old = scene(|world| {
+            world.spawn((Health { current: 1, max: 1 }, Name("a".into())));
+            world.spawn(Name("removed".into()));
+            world.insert_resource(Gravity::default());
+        });
+        let new = scene(|world| {
+            world.spawn(Health { current: 1, max: 1 });
+            world.spawn_empty();
+            world.spawn(Name("added".into()));
+        });
+
+        let mut world = create_world();
+        let mut entity_map = EntityMap::default();
+        old.write_to_world(&mut world, &mut entity_map).unwrap();
+        let entities = old
+            .entities
+            .iter()
+            .map(|entity| entity_map.get(entity.entity).unwrap())
+            .collect::<Vec<_>>();
+
+        let patch = ScenePatch::diff(&old, &new);
+        patch.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        assert!(world.get::<Name>(entities[0]).is_none());
+        assert!(world.get::<Health>(entities[0]).is_some());
+        // the second entity lost all its components but is still in the scene
+        assert!(world.get_entity(entities[1]).is_some());
+        assert!(world.get::<Name>(entities[1]).is_none());
+        assert!(world.get_resource::<Gravity>().is_none());
+
+        assert_eq!(entity_map.len(), 3);
+        let added = entity_map.get(new.entities[2].entity).unwrap();
+        assert_eq!(world.get::<Name>(added), Some(&Name("added".into())));
+    }
+
+    #[test]
+    fn patch_despawns_removed_entities() {
+        let old = scene(|world| {
+            world.spawn(Name("kept".into()));
+            world.spawn(Name("removed".into()));
+        });
+        let new = scene(|world| {
+            world.spawn(Name("kept".into()));
+        });
+
+        let mut world = create_world();
+        let mut entity_map = EntityMap::default();
+        old.write_to_world(&mut world, &mut entity_map).unwrap();
+        let removed = entity_map.get(old.entities[1].entity).unwrap();
+
+        let patch = ScenePatch::diff(&old, &new);
+        assert_eq!(patch.removed_entities, vec![old.entities[1].entity]);
+        patch.write_to_world(&mut world, &mut entity_map).unwrap();
+
+        assert!(world.get_entity(removed).is_none());
+        assert_eq!(entity_map.len(), 1);
+    }
+}
@@ -1,5 +1,7 @@
-use crate::{DynamicScene, Scene};
-use bevy_asset::{AssetEvent, Assets, Handle};
+use std::any::TypeId;
+
+use crate::{prefab::component_type_ids, DynamicScene, Scene, ScenePatch};
+use bevy_asset::{AssetEvent, AssetServer, Assets, Handle, HandleId, LoadState};
 use bevy_ecs::{
     entity::{Entity, EntityMap},
     event::{Events, ManualEventReader},
@@ -28,10 +30,35 @@ impl InstanceId {
     }
 }
 
+/// How [`SceneSpawner`] updates the instances of a [`DynamicScene`] when it is modified,
+/// for example when its file is hot-reloaded.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum SceneReloadMode {
+    /// Write the whole scene to the instances again.
+    ///
+    /// All the components and resources of the scene are overwritten with the values from the
+    /// scene, discarding any changes made to them at runtime.
+    #[default]
+    Reapply,
+    /// Only write what changed in the scene, using a [`ScenePatch`].
+    ///
+    /// Fields that didn't change in the scene keep their runtime values, and components,
+    /// resources and entities that were removed from the scene are removed from the instances.
+    /// This keeps a copy of every spawned [`DynamicScene`] to compute the changes from.
+    Patch,
+}
+
 #[derive(Default, Resource)]
 pub struct SceneSpawner {
     spawned_scenes: HashMap<Handle<Scene>, Vec<InstanceId>>,
     spawned_dynamic_scenes: HashMap<Handle<DynamicScene>, Vec<InstanceId>>,
+    /// The version of each spawned dynamic scene its instances are up to date with, when
+    /// using [`SceneReloadMode::Patch`].
+    applied_dynamic_scenes: HashMap<Handle<DynamicScene>, DynamicScene>,
+    /// The dynamic scenes spawned before switching to [`SceneReloadMode::Patch`], to keep a
+    /// copy of once their instances are up to date.
+    scenes_to_track: Vec<Handle<DynamicScene>>,
+    reload_mode: SceneReloadMode,
     spawned_instances: HashMap<InstanceId, InstanceInfo>,
     scene_asset_event_reader: ManualEventReader<AssetEvent<DynamicScene>>,
     dynamic_scenes_to_spawn: Vec<(Handle<DynamicScene>, InstanceId)>,
@@ -39,6 +66,33 @@ pub struct SceneSpawner {
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
     instances_to_despawn: Vec<InstanceId>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    prefabs_to_spawn: Vec<PrefabToSpawn>,
+    /// The prefabs written to their entities, by scene, to write them again when it is modified.
+    spawned_prefabs: HashMap<Handle<DynamicScene>, Vec<SpawnedPrefab>>,
+    /// The entities spawned for the prefabs of each instance.
+    prefab_entities: HashMap<InstanceId, Vec<Entity>>,
+}
+
+/// A [`ScenePrefab`](crate::ScenePrefab) waiting for its scene to be loaded.
+struct PrefabToSpawn {
+    instance_id: InstanceId,
+    entity: Entity,
+    handle: Handle<DynamicScene>,
+    path: String,
+    /// The scenes of the prefabs this one is nested in, to detect cycles.
+    ancestors: Vec<HandleId>,
+}
+
+/// A [`ScenePrefab`](crate::ScenePrefab) whose scene was written to its entity.
+struct SpawnedPrefab {
+    instance_id: InstanceId,
+    entity: Entity,
+    /// Mapping of entities from the prefab scene to the instance world.
+    entity_map: EntityMap,
+    /// The types of the components the entity had before the prefab was written, which
+    /// override the ones of the prefab.
+    overrides: HashSet<TypeId>,
+    ancestors: Vec<HandleId>,
 }
 
 #[derive(Error, Debug)]
@@ -56,6 +110,30 @@ pub enum SceneSpawnError {
 }
 
 impl SceneSpawner {
+    /// Returns how instances of dynamic scenes are updated when their scene is modified.
+    pub fn reload_mode(&self) -> SceneReloadMode {
+        self.reload_mode
+    }
+
+    /// Sets how instances of dynamic scenes are updated when their scene is modified.
+    ///
+    /// Switching to [`SceneReloadMode::Patch`] also affects the instances spawned before: the
+    /// scene spawner keeps a copy of their scene the next time it runs, and patches them when
+    /// their scene is modified afterwards.
+    pub fn set_reload_mode(&mut self, reload_mode: SceneReloadMode) {
+        if reload_mode == SceneReloadMode::Patch && self.reload_mode != SceneReloadMode::Patch {
+            self.scenes_to_track = self
+                .spawned_dynamic_scenes
+                .keys()
+                .map(Handle::clone_weak)
+                .collect();
+        } else if reload_mode != SceneReloadMode::Patch {
+            self.applied_dynamic_scenes.clear();
+            self.scenes_to_track.clear();
+        }
+        self.reload_mode = reload_mode;
+    }
+
     pub fn spawn_dynamic(&mut self, scene_handle: Handle<DynamicScene>) -> InstanceId {
         let instance_id = InstanceId::new();
         self.dynamic_scenes_to_spawn
@@ -101,6 +179,7 @@ impl SceneSpawner {
         world: &mut World,
         scene_handle: Handle<DynamicScene>,
     ) -> Result<(), SceneSpawnError> {
+        self.applied_dynamic_scenes.remove(&scene_handle);
         if let Some(instance_ids) = self.spawned_dynamic_scenes.remove(&scene_handle) {
             for instance_id in instance_ids {
                 self.despawn_instance_sync(world, &instance_id);
@@ -115,6 +194,14 @@ impl SceneSpawner {
                 let _ = world.despawn(entity);
             }
         }
+        if let Some(entities) = self.prefab_entities.remove(instance_id) {
+            for entity in entities {
+                let _ = world.despawn(entity);
+            }
+        }
+        for spawned_prefabs in self.spawned_prefabs.values_mut() {
+            spawned_prefabs.retain(|prefab| prefab.instance_id != *instance_id);
+        }
     }
 
     pub fn spawn_dynamic_sync(
@@ -124,7 +211,9 @@ impl SceneSpawner {
     ) -> Result<(), SceneSpawnError> {
         let mut entity_map = EntityMap::default();
         Self::spawn_dynamic_internal(world, scene_handle, &mut entity_map)?;
+        self.track_applied_scene(world, scene_handle);
         let instance_id = InstanceId::new();
+        self.queue_scene_prefabs(world, instance_id, scene_handle, &entity_map);
         self.spawned_instances
             .insert(instance_id, InstanceInfo { entity_map });
         let spawned = self
@@ -192,8 +281,33 @@ impl SceneSpawner {
         scene_handles: &[Handle<DynamicScene>],
     ) -> Result<(), SceneSpawnError> {
         for scene_handle in scene_handles {
-            if let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle) {
-                for instance_id in spawned_instances {
+            self.update_spawned_prefabs(world, scene_handle)?;
+            let Some(spawned_instances) = self.spawned_dynamic_scenes.get(scene_handle).cloned()
+            else {
+                continue;
+            };
+            if let Some(applied_scene) = self.applied_dynamic_scenes.get_mut(scene_handle) {
+                let (patch, scene) =
+                    world.resource_scope(|_, scenes: Mut<Assets<DynamicScene>>| {
+                        let scene = scenes.get(scene_handle).ok_or_else(|| {
+                            SceneSpawnError::NonExistentScene {
+                                handle: scene_handle.clone_weak(),
+                            }
+                        })?;
+                        Ok((
+                            ScenePatch::diff(applied_scene, scene),
+                            scene.clone_dynamic(),
+                        ))
+                    })?;
+                for instance_id in &spawned_instances {
+                    if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
+                        patch.write_to_world(world, &mut instance_info.entity_map)?;
+                    }
+                }
+                // only once it was applied, so that a failed patch is computed again
+                *applied_scene = scene;
+            } else {
+                for instance_id in &spawned_instances {
                     if let Some(instance_info) = self.spawned_instances.get_mut(instance_id) {
                         Self::spawn_dynamic_internal(
                             world,
@@ -202,11 +316,106 @@ impl SceneSpawner {
                         )?;
                     }
                 }
+                self.track_applied_scene(world, scene_handle);
+            }
+
+            // spawn the prefabs added to the scene
+            for instance_id in spawned_instances {
+                if let Some(instance_info) = self.spawned_instances.remove(&instance_id) {
+                    self.queue_scene_prefabs(
+                        world,
+                        instance_id,
+                        scene_handle,
+                        &instance_info.entity_map,
+                    );
+                    self.spawned_instances.insert(instance_id, instance_info);
+                }
             }
         }
         Ok(())
     }
 
+    /// Writes the modified scene of the [`ScenePrefab`](crate::ScenePrefab)s using it to their
+    /// entities again.
+    ///
+    /// The components the entities had before their prefab was first written still override
+    /// the ones of the scene, and the prefabs added to the scene are queued to be spawned.
+    fn update_spawned_prefabs(
+        &mut self,
+        world: &mut World,
+        scene_handle: &Handle<DynamicScene>,
+    ) -> Result<(), SceneSpawnError> {
+        let Some(mut spawned_prefabs) = self.spawned_prefabs.remove(scene_handle) else {
+            return Ok(());
+        };
+        spawned_prefabs.retain(|prefab| {
+            self.spawned_instances.contains_key(&prefab.instance_id)
+                && world.get_entity(prefab.entity).is_some()
+        });
+
+        let result = world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            let Some(scene) = scenes.get(scene_handle) else {
+                return Ok(());
+            };
+            let type_registry = world.resource::<AppTypeRegistry>().clone();
+            for prefab in &mut spawned_prefabs {
+                let previous_entities: HashSet<Entity> = prefab.entity_map.values().collect();
+                let result = scene.write_prefab(
+                    world,
+                    prefab.entity,
+                    &mut prefab.entity_map,
+                    &type_registry,
+                    &prefab.overrides,
+                );
+                self.prefab_entities
+                    .entry(prefab.instance_id)
+                    .or_default()
+                    .extend(prefab.entity_map.values().filter(|entity| {
+                        *entity != prefab.entity && !previous_entities.contains(entity)
+                    }));
+                result?;
+
+                let mut ancestors = prefab.ancestors.clone();
+                ancestors.push(scene_handle.id());
+                self.queue_prefabs(
+                    world,
+                    prefab.instance_id,
+                    scene,
+                    &prefab.entity_map,
+                    &ancestors,
+                );
+            }
+            Ok(())
+        });
+        self.spawned_prefabs
+            .insert(scene_handle.clone_weak(), spawned_prefabs);
+        result
+    }
+
+    /// Keeps a copy of the given scene to patch its instances with when it is modified, if
+    /// using [`SceneReloadMode::Patch`].
+    fn track_applied_scene(&mut self, world: &World, scene_handle: &Handle<DynamicScene>) {
+        if self.reload_mode != SceneReloadMode::Patch
+            || self.applied_dynamic_scenes.contains_key(scene_handle)
+        {
+            return;
+        }
+        if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(scene_handle) {
+            self.applied_dynamic_scenes
+                .insert(scene_handle.clone_weak(), scene.clone_dynamic());
+        }
+    }
+
+    /// Keeps a copy of the scenes spawned before switching to [`SceneReloadMode::Patch`].
+    ///
+    /// This runs after their modifications were applied, so that the copies match their
+    /// instances.
+    fn track_scenes_spawned_before_patching(&mut self, world: &World) {
+        for scene_handle in std::mem::take(&mut self.scenes_to_track) {
+            self.track_applied_scene(world, &scene_handle);
+        }
+    }
+
     pub fn despawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
         let scenes_to_despawn = std::mem::take(&mut self.scenes_to_despawn);
 
@@ -232,6 +441,8 @@ impl SceneSpawner {
 
             match Self::spawn_dynamic_internal(world, &scene_handle, &mut entity_map) {
                 Ok(_) => {
+                    self.track_applied_scene(world, &scene_handle);
+                    self.queue_scene_prefabs(world, instance_id, &scene_handle, &entity_map);
                     self.spawned_instances
                         .insert(instance_id, InstanceInfo { entity_map });
                     let spawned = self
@@ -263,6 +474,163 @@ impl SceneSpawner {
         Ok(())
     }
 
+    /// Writes the scenes of the [`ScenePrefab`](crate::ScenePrefab)s of the spawned instances
+    /// to their entities, including the prefabs nested in them, once their scenes are loaded.
+    ///
+    /// Prefabs whose scene failed to load are dropped with an error. A prefab that can't be
+    /// written doesn't prevent the others from being spawned: it is dropped too, and the first
+    /// of these errors is returned once the queue is processed, while the others are logged.
+    pub fn spawn_queued_prefabs(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
+        let mut first_error = None;
+        let mut spawned_any = true;
+        while spawned_any && !self.prefabs_to_spawn.is_empty() {
+            spawned_any = false;
+            let prefabs_to_spawn = std::mem::take(&mut self.prefabs_to_spawn);
+
+            for prefab in prefabs_to_spawn {
+                // the instance or the entity may have been despawned while the scene was loading
+                if !self.spawned_instances.contains_key(&prefab.instance_id)
+                    || world.get_entity(prefab.entity).is_none()
+                {
+                    continue;
+                }
+
+                match self.spawn_prefab(world, &prefab) {
+                    Ok(true) => spawned_any = true,
+                    Ok(false) => {
+                        let failed = world.get_resource::<AssetServer>().is_some_and(|server| {
+                            server.get_load_state(&prefab.handle) == LoadState::Failed
+                        });
+                        if failed {
+                            error!("failed to load the scene of prefab `{}`", prefab.path);
+                        } else {
+                            self.prefabs_to_spawn.push(prefab);
+                        }
+                    }
+                    Err(err) if first_error.is_none() => first_error = Some(err),
+                    Err(err) => error!("failed to spawn prefab `{}`: {err}", prefab.path),
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Writes the scene of `prefab` to its entity if it is loaded, returning `false` otherwise.
+    fn spawn_prefab(
+        &mut self,
+        world: &mut World,
+        prefab: &PrefabToSpawn,
+    ) -> Result<bool, SceneSpawnError> {
+        world.resource_scope(|world, scenes: Mut<Assets<DynamicScene>>| {
+            let Some(scene) = scenes.get(&prefab.handle) else {
+                return Ok(false);
+            };
+            let type_registry = world.resource::<AppTypeRegistry>().clone();
+            let mut spawned = SpawnedPrefab {
+                instance_id: prefab.instance_id,
+                entity: prefab.entity,
+                entity_map: EntityMap::default(),
+                overrides: component_type_ids(world, prefab.entity),
+                ancestors: prefab.ancestors.clone(),
+            };
+            let result = scene.write_prefab(
+                world,
+                prefab.entity,
+                &mut spawned.entity_map,
+                &type_registry,
+                &spawned.overrides,
+            );
+
+            // the entities spawned before an error are despawned with the instance as well
+            self.prefab_entities
+                .entry(prefab.instance_id)
+                .or_default()
+                .extend(
+                    spawned
+                        .entity_map
+                        .values()
+                        .filter(|&entity| entity != prefab.entity),
+                );
+            result?;
+
+            let mut ancestors = prefab.ancestors.clone();
+            ancestors.push(prefab.handle.id());
+            self.queue_prefabs(
+                world,
+                prefab.instance_id,
+                scene,
+                &spawned.entity_map,
+                &ancestors,
+            );
+            self.spawned_prefabs
+                .entry(prefab.handle.clone_weak())
+                .or_default()
+                .push(spawned);
+            Ok(true)
+        })
+    }
+
+    fn queue_scene_prefabs(
+        &mut self,
+        world: &World,
+        instance_id: InstanceId,
+        scene_handle: &Handle<DynamicScene>,
+        entity_map: &EntityMap,
+    ) {
+        if let Some(scene) = world.resource::<Assets<DynamicScene>>().get(scene_handle) {
+            self.queue_prefabs(world, instance_id, scene, entity_map, &[scene_handle.id()]);
+        }
+    }
+
+    fn queue_prefabs(
+        &mut self,
+        world: &World,
+        instance_id: InstanceId,
+        scene: &DynamicScene,
+        entity_map: &EntityMap,
+        ancestors: &[HandleId],
+    ) {
+        let prefabs = scene.prefabs();
+        if prefabs.is_empty() {
+            return;
+        }
+        let Some(asset_server) = world.get_resource::<AssetServer>() else {
+            error!("scene contains prefabs, but there is no `AssetServer` to load them");
+            return;
+        };
+
+        for (scene_entity, path) in prefabs {
+            let Some(entity) = entity_map.get(scene_entity) else {
+                continue;
+            };
+            let handle: Handle<DynamicScene> = asset_server.load(path.as_str());
+            if ancestors.contains(&handle.id()) {
+                error!("prefab `{path}` is nested in itself");
+                continue;
+            }
+            // the prefabs of a modified scene are queued again
+            let is_instance = |other_instance: InstanceId, other_entity: Entity| {
+                other_instance == instance_id && other_entity == entity
+            };
+            if self.prefabs_to_spawn.iter().any(|prefab| {
+                prefab.handle == handle && is_instance(prefab.instance_id, prefab.entity)
+            }) || self.spawned_prefabs.get(&handle).is_some_and(|spawned| {
+                spawned
+                    .iter()
+                    .any(|prefab| is_instance(prefab.instance_id, prefab.entity))
+            }) {
+                continue;
+            }
+            self.prefabs_to_spawn.push(PrefabToSpawn {
+                instance_id,
+                entity,
+                handle,
+                path,
+                ancestors: ancestors.to_vec(),
+            });
+        }
+    }
+
     pub(crate) fn set_scene_instance_parent_sync(&mut self, world: &mut World) {
         let scenes_with_parent = std::mem::take(&mut self.scenes_with_parent);
 
@@ -311,6 +679,13 @@ impl SceneSpawner {
             .map(|instance| instance.entity_map.values())
             .into_iter()
             .flatten()
+            .chain(
+                self.prefab_entities
+                    .get(&instance_id)
+                    .into_iter()
+                    .flatten()
+                    .copied(),
+            )
     }
 }
 
@@ -345,7 +720,9 @@ pub fn scene_spawner_system(world: &mut World) {
             .iter(scene_asset_events)
         {
             if let AssetEvent::Modified { handle } = event {
-                if scene_spawner.spawned_dynamic_scenes.contains_key(handle) {
+                if scene_spawner.spawned_dynamic_scenes.contains_key(handle)
+                    || scene_spawner.spawned_prefabs.contains_key(handle)
+                {
                     updated_spawned_scenes.push(handle.clone_weak());
                 }
             }
@@ -356,9 +733,223 @@ pub fn scene_spawner_system(world: &mut World) {
         scene_spawner
             .spawn_queued_scenes(world)
             .unwrap_or_else(|err| panic!("{}", err));
+        scene_spawner
+            .spawn_queued_prefabs(world)
+            .unwrap_or_else(|err| panic!("{}", err));
         scene_spawner
             .update_spawned_scenes(world, &updated_spawned_scenes)
             .unwrap();
+        scene_spawner.track_scenes_spawned_before_patching(world);
         scene_spawner.set_scene_instance_parent_sync(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetServer, Assets, FileAssetIo, Handle, LoadState};
+    use bevy_ecs::{
+        entity::Entity,
+        prelude::{Component, ReflectComponent, World},
+    };
+    use bevy_reflect::Reflect;
+    use bevy_tasks::IoTaskPool;
+
+    use super::{SceneReloadMode, SceneSpawnError, SceneSpawner};
+    use crate::{DynamicEntity, DynamicScene, ScenePrefab};
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Health(i32);
+
+    #[derive(Component, Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Speed(i32);
+
+    #[derive(Reflect, Default)]
+    struct Unregistered;
+
+    fn setup() -> App {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        // the scenes are never found on disk, they are added to `Assets` by the tests
+        app.insert_resource(AssetServer::new(FileAssetIo::new("missing", &None)))
+            .add_asset::<DynamicScene>()
+            .register_type::<Health>()
+            .register_type::<Speed>()
+            .register_type::<ScenePrefab>()
+            .init_resource::<SceneSpawner>();
+        app
+    }
+
+    fn scene(entities: Vec<Vec<Box<dyn Reflect>>>) -> DynamicScene {
+        DynamicScene {
+            resources: Vec::new(),
+            entities: entities
+                .into_iter()
+                .enumerate()
+                .map(|(index, components)| DynamicEntity {
+                    entity: Entity::from_raw(index as u32),
+                    components,
+                })
+                .collect(),
+        }
+    }
+
+    fn add_scene(world: &mut World, path: &str, scene: DynamicScene) -> Handle<DynamicScene> {
+        let handle = world.resource::<AssetServer>().load(path);
+        world
+            .resource_mut::<Assets<DynamicScene>>()
+            .set_untracked(&handle, scene);
+        handle
+    }
+
+    fn wait_for_failure(world: &World, path: &str) {
+        let start = Instant::now();
+        while world.resource::<AssetServer>().get_load_state(path) != LoadState::Failed {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn spawn(world: &mut World, handle: Handle<DynamicScene>) -> Result<(), SceneSpawnError> {
+        world.resource_scope(|world, mut spawner: bevy_ecs::world::Mut<SceneSpawner>| {
+            spawner.spawn_dynamic(handle);
+            spawner.spawn_queued_scenes(world)?;
+            spawner.spawn_queued_prefabs(world)
+        })
+    }
+
+    fn with_health(world: &mut World) -> Vec<(Entity, i32)> {
+        let mut query = world.query::<(Entity, &Health)>();
+        let mut entities: Vec<_> = query
+            .iter(world)
+            .map(|(entity, health)| (entity, health.0))
+            .collect();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn failing_prefabs_dont_prevent_others_from_spawning() {
+        let mut app = setup();
+        let world = &mut app.world;
+        add_scene(
+            world,
+            "unregistered.scn.ron",
+            scene(vec![vec![Box::new(Unregistered)]]),
+        );
+        add_scene(
+            world,
+            "tree.scn.ron",
+            scene(vec![vec![Box::new(Health(10))]]),
+        );
+        let level = add_scene(
+            world,
+            "level.scn.ron",
+            scene(vec![
+                vec![Box::new(ScenePrefab::new("unregistered.scn.ron"))],
+                vec![Box::new(ScenePrefab::new("missing.scn.ron"))],
+                vec![Box::new(ScenePrefab::new("tree.scn.ron"))],
+            ]),
+        );
+
+        assert!(matches!(
+            spawn(world, level),
+            Err(SceneSpawnError::UnregisteredType { .. })
+        ));
+        assert_eq!(with_health(world).len(), 1);
+
+        // the missing prefab is dropped once its scene failed to load
+        wait_for_failure(world, "missing.scn.ron");
+        world.resource_scope(|world, mut spawner: bevy_ecs::world::Mut<SceneSpawner>| {
+            spawner.spawn_queued_prefabs(world).unwrap();
+            assert!(spawner.prefabs_to_spawn.is_empty());
+        });
+    }
+
+    #[test]
+    fn modified_prefabs_are_written_again() {
+        let mut app = setup();
+        let world = &mut app.world;
+        let tree = add_scene(
+            world,
+            "tree.scn.ron",
+            scene(vec![vec![Box::new(Health(10)), Box::new(Speed(1))]]),
+        );
+        let level = add_scene(
+            world,
+            "level.scn.ron",
+            scene(vec![
+                vec![Box::new(ScenePrefab::new("tree.scn.ron"))],
+                vec![
+                    Box::new(ScenePrefab::new("tree.scn.ron")),
+                    Box::new(Speed(5)),
+                ],
+            ]),
+        );
+        spawn(world, level.clone()).unwrap();
+        let trees = with_health(world);
+        assert_eq!(trees.len(), 2);
+
+        world.resource_mut::<Assets<DynamicScene>>().set_untracked(
+            &tree,
+            scene(vec![vec![Box::new(Health(20)), Box::new(Speed(2))]]),
+        );
+        world.resource_scope(|world, mut spawner: bevy_ecs::world::Mut<SceneSpawner>| {
+            spawner.update_spawned_scenes(world, &[tree]).unwrap();
+        });
+        assert_eq!(with_health(world), vec![(trees[0].0, 20), (trees[1].0, 20)]);
+        // the components of the entity still override the ones of the prefab
+        assert_eq!(world.get::<Speed>(trees[0].0), Some(&Speed(2)));
+        assert_eq!(world.get::<Speed>(trees[1].0), Some(&Speed(5)));
+
+        // prefabs added to the scene are spawned after it is modified
+        world.resource_mut::<Assets<DynamicScene>>().set_untracked(
+            &level,
+            scene(vec![
+                vec![Box::new(ScenePrefab::new("tree.scn.ron"))],
+                vec![
+                    Box::new(ScenePrefab::new("tree.scn.ron")),
+                    Box::new(Speed(5)),
+                ],
+                vec![Box::new(ScenePrefab::new("tree.scn.ron"))],
+            ]),
+        );
+        world.resource_scope(|world, mut spawner: bevy_ecs::world::Mut<SceneSpawner>| {
+            spawner.update_spawned_scenes(world, &[level]).unwrap();
+            spawner.spawn_queued_prefabs(world).unwrap();
+        });
+        assert_eq!(with_health(world).len(), 3);
+    }
+
+    #[test]
+    fn switching_to_patch_applies_to_spawned_instances() {
+        let mut app = setup();
+        let world = &mut app.world;
+        let tree = add_scene(
+            world,
+            "tree.scn.ron",
+            scene(vec![vec![Box::new(Health(10)), Box::new(Speed(1))]]),
+        );
+        spawn(world, tree.clone()).unwrap();
+        world.resource_scope(|world, mut spawner: bevy_ecs::world::Mut<SceneSpawner>| {
+            spawner.set_reload_mode(SceneReloadMode::Patch);
+            spawner.track_scenes_spawned_before_patching(world);
+        });
+        let entity = with_health(world)[0].0;
+        world.get_mut::<Health>(entity).unwrap().0 = 15;
+
+        world.resource_mut::<Assets<DynamicScene>>().set_untracked(
+            &tree,
+            scene(vec![vec![Box::new(Health(10)), Box::new(Speed(2))]]),
+        );
+        world.resource_scope(|world, mut spawner: bevy_ecs::world::Mut<SceneSpawner>| {
+            spawner.update_spawned_scenes(world, &[tree]).unwrap();
+        });
+        assert_eq!(world.get::<Health>(entity), Some(&Health(15)));
+        assert_eq!(world.get::<Speed>(entity), Some(&Speed(2)));
+    }
+}
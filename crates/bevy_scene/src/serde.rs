@@ -67,8 +67,12 @@ impl<'a> Serialize for EntitiesSerializer<'a> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_map(Some(self.entities.len()))?;
-        for entity in self.entities {
+        // entities are written in a stable order, see `DynamicSceneBuilder`
+        let mut entities = self.entities.iter().collect::<Vec<_>>();
+        entities.sort_by_key(|entity| entity.entity);
+
+        let mut state = serializer.serialize_map(Some(entities.len()))?;
+        for entity in entities {
             state.serialize_entry(
                 &entity.entity,
                 &EntitySerializer {
@@ -423,13 +427,16 @@ impl<'a, 'de> Visitor<'de> for SceneMapVisitor<'a> {
 #[cfg(test)]
 mod tests {
     use crate::serde::{SceneDeserializer, SceneSerializer};
-    use crate::{DynamicScene, DynamicSceneBuilder};
+    use crate::{
+        deserialize_binary, BinarySceneError, DynamicScene, DynamicSceneBuilder, PersistentId,
+        BINARY_SCENE_MAGIC,
+    };
     use bevy_ecs::entity::{Entity, EntityMap, EntityMapper, MapEntities};
     use bevy_ecs::prelude::{Component, ReflectComponent, ReflectResource, Resource, World};
     use bevy_ecs::query::{With, Without};
     use bevy_ecs::reflect::{AppTypeRegistry, ReflectMapEntities};
     use bevy_ecs::world::FromWorld;
-    use bevy_reflect::{Reflect, ReflectSerialize};
+    use bevy_reflect::{DynamicStruct, Reflect, ReflectSerialize, Struct};
     use bincode::Options;
     use serde::de::DeserializeSeed;
     use serde::Serialize;
@@ -502,6 +509,8 @@ mod tests {
             registry.register::<MyEntityRef>();
             registry.register::<Entity>();
             registry.register::<MyResource>();
+            registry.register::<PersistentId>();
+            registry.register::<bevy_utils::Uuid>();
         }
         world.insert_resource(registry);
         world
@@ -668,6 +677,112 @@ mod tests {
             .all(|r| world.get_entity(r.0).is_none()));
     }
 
+    #[test]
+    fn should_serialize_persistent_ids_regardless_of_spawn_order() {
+        let parent_id = PersistentId::new();
+        let child_id = PersistentId::new();
+
+        let save = |parent_first: bool| {
+            let mut world = create_world();
+            // an entity without a persistent id shifts the entities of one of the worlds
+            if !parent_first {
+                world.spawn_empty();
+            }
+            let (parent, child) = if parent_first {
+                let parent = world.spawn((parent_id, Foo(1))).id();
+                (parent, world.spawn(child_id).id())
+            } else {
+                let child = world.spawn(child_id).id();
+                (world.spawn((parent_id, Foo(1))).id(), child)
+            };
+            world.entity_mut(child).insert(MyEntityRef(parent));
+
+            let mut builder = DynamicSceneBuilder::from_world(&world);
+            builder.extract_entities([child, parent].into_iter());
+            let scene = builder.build();
+            let registry = world.resource::<AppTypeRegistry>();
+            scene.serialize_ron(&registry.0).unwrap()
+        };
+
+        let serialized_scene = save(true);
+        assert_eq!(serialized_scene, save(false));
+        assert!(serialized_scene.contains(&format!("{}: (", parent_id.scene_entity().to_bits())));
+
+        let mut world = create_world();
+        world.spawn_batch((0..10).map(Bar));
+        let scene = {
+            let registry = world.resource::<AppTypeRegistry>();
+            let scene_deserializer = SceneDeserializer {
+                type_registry: &registry.0.read(),
+            };
+            let mut deserializer = ron::de::Deserializer::from_str(&serialized_scene).unwrap();
+            scene_deserializer.deserialize(&mut deserializer).unwrap()
+        };
+        scene
+            .write_to_world(&mut world, &mut EntityMap::default())
+            .unwrap();
+
+        let (child, reference) = world
+            .query::<(&PersistentId, &MyEntityRef)>()
+            .single(&world);
+        assert_eq!(*child, child_id);
+        assert_eq!(world.get::<PersistentId>(reference.0), Some(&parent_id));
+        assert_eq!(world.get::<Foo>(reference.0).map(|foo| foo.0), Some(1));
+    }
+
+    #[test]
+    fn should_only_map_entities_declared_by_map_entities() {
+        #[derive(Component, Reflect)]
+        #[reflect(Component, MapEntities)]
+        struct Link {
+            target: Entity,
+            // not mapped, for instance because it refers to an entity of another world
+            foreign: Entity,
+        }
+
+        impl MapEntities for Link {
+            fn map_entities(&mut self, entity_mapper: &mut EntityMapper) {
+                self.target = entity_mapper.get_or_reserve(self.target);
+            }
+        }
+
+        impl FromWorld for Link {
+            fn from_world(_world: &mut World) -> Self {
+                Self {
+                    target: Entity::PLACEHOLDER,
+                    foreign: Entity::PLACEHOLDER,
+                }
+            }
+        }
+
+        let mut world = create_world();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Link>();
+        let target_id = PersistentId::new();
+        let target = world.spawn(target_id).id();
+        let source = world
+            .spawn(Link {
+                target,
+                foreign: target,
+            })
+            .id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&world);
+        builder.extract_entities([source, target].into_iter());
+        let scene = builder.build();
+        let link = scene
+            .entities
+            .iter()
+            .flat_map(|entity| &entity.components)
+            .find_map(|component| component.downcast_ref::<DynamicStruct>())
+            .unwrap();
+        let field = |name| *link.field(name).unwrap().downcast_ref::<Entity>().unwrap();
+        assert_eq!(field("target"), target_id.scene_entity());
+        assert_eq!(field("foreign"), target);
+    }
+
     #[test]
     fn should_roundtrip_postcard() {
         let mut world = create_world();
@@ -791,6 +906,115 @@ mod tests {
         assert_scene_eq(&scene, &deserialized_scene);
     }
 
+    fn create_binary_roundtrip_world() -> World {
+        let mut world = create_world();
+
+        let a = world.spawn(Foo(123)).id();
+        world.spawn((Foo(-1), Bar(345), MyEntityRef(a)));
+        world.spawn((
+            Baz(789),
+            MyComponent {
+                foo: [1, 2, 3],
+                bar: (1.3, 3.7),
+                baz: MyEnum::Unit,
+            },
+        ));
+        world.spawn(MyComponent {
+            foo: [usize::MAX, 0, 42],
+            bar: (-0.0, f32::MAX),
+            baz: MyEnum::Tuple("Hello World!".to_string()),
+        });
+        world.spawn((
+            Foo(i32::MIN),
+            MyComponent {
+                foo: [4, 5, 6],
+                bar: (f32::MIN_POSITIVE, 1e10),
+                baz: MyEnum::Struct { value: u32::MAX },
+            },
+        ));
+        world.spawn_batch((0..1000).map(|i| (Foo(i), Bar(-i))));
+
+        world.insert_resource(MyResource { foo: 123 });
+        world
+    }
+
+    #[test]
+    fn should_roundtrip_binary_identical_to_ron() {
+        let world = create_binary_roundtrip_world();
+        let registry = world.resource::<AppTypeRegistry>();
+        let scene = DynamicScene::from_world(&world);
+
+        let ron = scene.serialize_ron(&registry.0).unwrap();
+        let mut ron_deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let ron_scene = SceneDeserializer {
+            type_registry: &registry.0.read(),
+        }
+        .deserialize(&mut ron_deserializer)
+        .unwrap();
+
+        let binary = scene.serialize_binary(&registry.0).unwrap();
+        assert!(binary.starts_with(&BINARY_SCENE_MAGIC));
+        assert!(binary.len() < ron.len());
+        let binary_scene = deserialize_binary(&binary, &registry.0.read()).unwrap();
+
+        assert_eq!(1005, binary_scene.entities.len());
+        assert_scene_eq(&scene, &ron_scene);
+        assert_scene_eq(&ron_scene, &binary_scene);
+
+        // both formats must produce the same scene, including resources and component order
+        assert_eq!(ron, ron_scene.serialize_ron(&registry.0).unwrap());
+        assert_eq!(ron, binary_scene.serialize_ron(&registry.0).unwrap());
+        assert_eq!(binary, ron_scene.serialize_binary(&registry.0).unwrap());
+    }
+
+    #[test]
+    fn should_roundtrip_empty_binary_scene() {
+        let world = create_world();
+        let registry = world.resource::<AppTypeRegistry>();
+        let scene = DynamicScene::from_world(&world);
+
+        let binary = scene.serialize_binary(&registry.0).unwrap();
+        let binary_scene = deserialize_binary(&binary, &registry.0.read()).unwrap();
+
+        assert!(binary_scene.resources.is_empty());
+        assert!(binary_scene.entities.is_empty());
+    }
+
+    #[test]
+    fn should_reject_invalid_binary_scene() {
+        let world = create_binary_roundtrip_world();
+        let registry = world.resource::<AppTypeRegistry>();
+        let scene = DynamicScene::from_world(&world);
+        let binary = scene.serialize_binary(&registry.0).unwrap();
+        let ron = scene.serialize_ron(&registry.0).unwrap();
+
+        let registry = registry.0.read();
+        assert!(matches!(
+            deserialize_binary(ron.as_bytes(), &registry),
+            Err(BinarySceneError::MissingMagic)
+        ));
+
+        let mut wrong_version = binary.clone();
+        wrong_version[BINARY_SCENE_MAGIC.len() - 1] = 0;
+        assert!(matches!(
+            deserialize_binary(&wrong_version, &registry),
+            Err(BinarySceneError::UnsupportedVersion(0))
+        ));
+
+        let truncated = &binary[..binary.len() - 1];
+        assert!(matches!(
+            deserialize_binary(truncated, &registry),
+            Err(BinarySceneError::Deserialize(_))
+        ));
+
+        // a scene containing a type the registry doesn't know about can't be read
+        let empty_registry = bevy_reflect::TypeRegistry::empty();
+        assert!(matches!(
+            deserialize_binary(&binary, &empty_registry),
+            Err(BinarySceneError::Deserialize(_))
+        ));
+    }
+
     /// A crude equality checker for [`DynamicScene`], used solely for testing purposes.
     fn assert_scene_eq(expected: &DynamicScene, received: &DynamicScene) {
         assert_eq!(
@@ -43,6 +43,7 @@ fn main() {
                 asset_folder: std::env::var("CARGO_MANIFEST_DIR")
                     .unwrap_or_else(|_| ".".to_string()),
                 watch_for_changes: ChangeWatcher::with_delay(Duration::from_millis(200)),
+                ..default()
             }),
         CameraControllerPlugin,
         SceneViewerPlugin,